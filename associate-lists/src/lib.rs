@@ -1,4 +1,6 @@
 use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
 
 /// Associates elements of two lists with each other.
 /// Elements of the shorter list are copied and distributed evenly to match the larger list.
@@ -9,15 +11,59 @@ pub fn associate_lists<T>(first_list: &[T], second_list: &[T]) -> Vec<(T, T)>
 where
     T: Copy,
 {
-    assert!(!first_list.is_empty());
-    assert!(!second_list.is_empty());
+    associate_lists_checked(first_list, second_list)
+        .unwrap_or_else(|error| panic!("Unable to associate lists: {}", error))
+}
+
+/// Associates elements of two lists with each other like [`associate_lists`].
+///
+/// # Errors
+/// Returns an [`AssociateListsError`] if one or both of the given lists are empty.
+pub fn associate_lists_checked<T>(
+    first_list: &[T],
+    second_list: &[T],
+) -> Result<Vec<(T, T)>, AssociateListsError>
+where
+    T: Copy,
+{
+    match (first_list.is_empty(), second_list.is_empty()) {
+        (true, true) => return Err(AssociateListsError::BothListsEmpty),
+        (true, false) => return Err(AssociateListsError::FirstListEmpty),
+        (false, true) => return Err(AssociateListsError::SecondListEmpty),
+        (false, false) => {}
+    }
 
-    match first_list.len().cmp(&second_list.len()) {
+    let associated_lists = match first_list.len().cmp(&second_list.len()) {
         Ordering::Equal => associate_lists_with_equal_lengths(first_list, second_list).collect(),
         Ordering::Less => associate_lists_where_second_is_longer(first_list, second_list).collect(),
         Ordering::Greater => associate_lists_where_second_is_longer(second_list, first_list)
             .map(|(first, second)| (second, first))
             .collect(),
+    };
+
+    Ok(associated_lists)
+}
+
+/// The reason why two lists could not be associated by [`associate_lists_checked`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AssociateListsError {
+    /// The first list did not contain any elements
+    FirstListEmpty,
+    /// The second list did not contain any elements
+    SecondListEmpty,
+    /// Neither list contained any elements
+    BothListsEmpty,
+}
+
+impl Error for AssociateListsError {}
+
+impl fmt::Display for AssociateListsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssociateListsError::FirstListEmpty => write!(f, "The first list is empty"),
+            AssociateListsError::SecondListEmpty => write!(f, "The second list is empty"),
+            AssociateListsError::BothListsEmpty => write!(f, "Both lists are empty"),
+        }
     }
 }
 
@@ -30,14 +76,16 @@ where
 {
     let last_index_of_first_list = first_list.len() - 1;
     let last_index_of_second_list = second_list.len() - 1;
-    let ratio = last_index_of_first_list as f64 / last_index_of_second_list as f64;
 
     second_list
         .iter()
         .copied()
         .enumerate()
         .map(move |(index, second_value)| {
-            let first_list_index = (ratio * index as f64).round() as usize;
+            // Integer arithmetic equivalent to rounding index * ratio half up,
+            // so that the result doesn't depend on the platform's floating point behaviour
+            let scaled_index = index * last_index_of_first_list + last_index_of_second_list / 2;
+            let first_list_index = scaled_index / last_index_of_second_list;
             let first_value = first_list[first_list_index];
 
             (first_value, second_value)
@@ -76,6 +124,50 @@ mod tests {
         assert_eq!(expected_pairs, associate_lists(&first_list, &second_list));
     }
 
+    #[test]
+    fn rounds_halfway_indices_up() {
+        let first_list = vec![10, 11];
+        let second_list = vec![20, 21, 22];
+        let expected_pairs = vec![(10, 20), (11, 21), (11, 22)];
+
+        assert_eq!(expected_pairs, associate_lists(&first_list, &second_list));
+
+        let first_list = vec![10, 11, 12, 13];
+        let second_list = vec![20, 21, 22, 23, 24, 25, 26];
+        let expected_pairs = vec![
+            (10, 20),
+            (11, 21),
+            (11, 22),
+            (12, 23),
+            (12, 24),
+            (13, 25),
+            (13, 26),
+        ];
+
+        assert_eq!(expected_pairs, associate_lists(&first_list, &second_list));
+    }
+
+    #[test]
+    fn distributes_every_element_in_order_for_all_lengths() {
+        for first_length in 1..=16 {
+            for second_length in first_length..=16 {
+                let first_list: Vec<_> = (0..first_length).collect();
+                let second_list: Vec<_> = (0..second_length).collect();
+
+                let pairs = associate_lists(&first_list, &second_list);
+                let first_values: Vec<_> = pairs.iter().map(|&(first, _)| first).collect();
+
+                let mut distinct_first_values = first_values.clone();
+                distinct_first_values.dedup();
+
+                assert_eq!(second_length, pairs.len());
+                assert_eq!(first_list, distinct_first_values);
+                assert!(first_values.windows(2).all(|window| window[0] <= window[1]));
+                assert_eq!(Some(&(first_length - 1)), first_values.last());
+            }
+        }
+    }
+
     #[test]
     fn generates_correct_result_when_both_lists_have_the_equal_length() {
         let first_list = vec![10, 20, 30];
@@ -108,4 +200,49 @@ mod tests {
         let second_list = vec![];
         let _: Vec<_> = associate_lists(&first_list, &second_list);
     }
+
+    #[test]
+    fn checked_association_succeeds_for_non_empty_lists() {
+        let first_list = vec![10, 11, 12];
+        let second_list = vec![20, 21, 22];
+        let expected_pairs = vec![(10, 20), (11, 21), (12, 22)];
+
+        assert_eq!(
+            Ok(expected_pairs),
+            associate_lists_checked(&first_list, &second_list)
+        );
+    }
+
+    #[test]
+    fn checked_association_returns_error_when_first_list_is_empty() {
+        let first_list = vec![];
+        let second_list = vec![10, 11, 12];
+
+        assert_eq!(
+            Err(AssociateListsError::FirstListEmpty),
+            associate_lists_checked(&first_list, &second_list)
+        );
+    }
+
+    #[test]
+    fn checked_association_returns_error_when_second_list_is_empty() {
+        let first_list = vec![10, 11, 12];
+        let second_list = vec![];
+
+        assert_eq!(
+            Err(AssociateListsError::SecondListEmpty),
+            associate_lists_checked(&first_list, &second_list)
+        );
+    }
+
+    #[test]
+    fn checked_association_returns_error_when_both_lists_are_empty() {
+        let first_list: Vec<()> = vec![];
+        let second_list = vec![];
+
+        assert_eq!(
+            Err(AssociateListsError::BothListsEmpty),
+            associate_lists_checked(&first_list, &second_list)
+        );
+    }
 }
@@ -1,25 +1,27 @@
 //! Contains types for the full [`Genome`]
 
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
 
 /// The index of a [`Neuron`] in a [`ClusterGene`]
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct ClusterNeuronIndex(pub usize);
 
 /// The index of a [`HoxGene`] in a [`Genome`]
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct HoxGeneIndex(pub usize);
 
 /// The index of a [`ClusterGene`] in a [`Genome`]
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct ClusterGeneIndex(pub usize);
 
 /// The index of a [`Connection`] in a [`ClusterGene`]
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct ClusterConnectionIndex(pub usize);
 
 /// A neuron
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Neuron;
 
 impl Neuron {
@@ -33,7 +35,7 @@ impl Neuron {
 pub type Weight = f64;
 
 /// Definition of the connection between two neurons.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Connection {
     /// The index of the neuron that will be used for the start of the connection
     pub from: ClusterNeuronIndex,
@@ -45,7 +47,7 @@ pub struct Connection {
 
 /// The definition of a cluster blueprint, defining the neurons, the neuron that will be attached
 /// to the target when the cluster is placed, and the connections inside the cluster.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClusterGene {
     /// The neurons of the cluster
     pub neurons: Vec<Neuron>,
@@ -64,7 +66,7 @@ pub struct ClusterGene {
 }
 
 /// Additional information about a the responsibilities of a placed [`ClusterGene`].
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ClusterGeneSpecialization {
     /// A normal, good ol' cluster.
     None,
@@ -89,7 +91,7 @@ impl Default for ClusterGeneSpecialization {
 }
 
 /// Describes the placement behaviour of a [`HoxGene`].
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum HoxPlacement {
     /// This hox gene's cluster will be placed once for each previously placed cluster of the given [`ClusterGene`].
     ClusterGene {
@@ -111,7 +113,7 @@ pub enum HoxPlacement {
 }
 
 /// A gene defining the placement of neuron clusters.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct HoxGene {
     /// The target(s) that the hox places its cluster(s) on
     pub placement_target: HoxPlacement,
@@ -121,11 +123,343 @@ pub struct HoxGene {
     pub disabled_connections: HashSet<ClusterConnectionIndex>,
 }
 
-/// The set of all genes in an organism
+/// Weights of the terms that make up the [distance] between two [`Genome`]s.
+///
+/// [distance]: ./struct.Genome.html#method.distance
 #[derive(Debug, Default, Clone, PartialEq)]
+pub struct DistanceCoefficients {
+    /// Weight of the number of excess genes
+    pub excess_genes: f64,
+    /// Weight of the number of disjoint genes
+    pub disjoint_genes: f64,
+    /// Weight of the average weight difference of matching genes
+    pub weight_difference: f64,
+}
+
+/// The set of all genes in an organism
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Genome {
     /// The hox genes of the genome
     pub hox_genes: Vec<HoxGene>,
     /// Clusters than can be placed by hox genes
     pub cluster_genes: Vec<ClusterGene>,
 }
+
+impl Genome {
+    /// Renders the genome as a graph in the [Graphviz] DOT language.
+    /// Every [`ClusterGene`] becomes a subgraph containing its neurons and weighted connections.
+    /// Input and output neurons are styled differently from hidden ones, and the placements
+    /// defined by the [`HoxGene`]s are drawn as dashed edges between clusters.
+    ///
+    /// [Graphviz]: https://www.graphviz.org/
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph genome {\n");
+
+        for (cluster_gene_index, cluster_gene) in self.cluster_genes.iter().enumerate() {
+            write_cluster_gene(&mut dot, ClusterGeneIndex(cluster_gene_index), cluster_gene);
+        }
+
+        for hox_gene in &self.hox_genes {
+            self.write_hox_placement(&mut dot, hox_gene);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Calculates how different this genome is from another one, e.g. in order to group
+    /// genomes into species. Only the connections of the [`ClusterGene`]s are compared.
+    /// A connection is identified by the index of its [`ClusterGene`] and the neurons it connects.
+    ///
+    /// Connections that are only found in one genome are excess genes if they come after
+    /// the last connection of the other genome and disjoint genes otherwise.
+    /// The distance is the weighted sum of the number of excess genes, the number of
+    /// disjoint genes and the average weight difference of the connections found in both genomes.
+    pub fn distance(&self, other: &Genome, coefficients: &DistanceCoefficients) -> f64 {
+        let connection_genes = self.connection_genes();
+        let other_connection_genes = other.connection_genes();
+
+        let (excess_gene_count, disjoint_gene_count) =
+            count_unmatched_genes(&connection_genes, &other_connection_genes);
+        let (other_excess_gene_count, other_disjoint_gene_count) =
+            count_unmatched_genes(&other_connection_genes, &connection_genes);
+
+        let weight_differences: Vec<_> = connection_genes
+            .iter()
+            .filter_map(|(key, weight)| {
+                other_connection_genes
+                    .get(key)
+                    .map(|other_weight| (weight - other_weight).abs())
+            })
+            .collect();
+        let average_weight_difference = if weight_differences.is_empty() {
+            0.0
+        } else {
+            weight_differences.iter().sum::<f64>() / weight_differences.len() as f64
+        };
+
+        coefficients.excess_genes * (excess_gene_count + other_excess_gene_count) as f64
+            + coefficients.disjoint_genes * (disjoint_gene_count + other_disjoint_gene_count) as f64
+            + coefficients.weight_difference * average_weight_difference
+    }
+
+    fn connection_genes(&self) -> BTreeMap<ConnectionGeneKey, Weight> {
+        self.cluster_genes
+            .iter()
+            .enumerate()
+            .flat_map(|(cluster_gene_index, cluster_gene)| {
+                cluster_gene.connections.iter().map(move |connection| {
+                    (
+                        (cluster_gene_index, connection.from.0, connection.to.0),
+                        connection.weight,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn write_hox_placement(&self, dot: &mut String, hox_gene: &HoxGene) {
+        let (target_cluster_gene, target_neuron) = match hox_gene.placement_target {
+            HoxPlacement::ClusterGene {
+                cluster_gene,
+                target_neuron,
+            } => (cluster_gene, target_neuron),
+            HoxPlacement::HoxGene {
+                hox_gene,
+                target_neuron,
+            } => match self.hox_genes.get(hox_gene.0) {
+                Some(target_hox_gene) => (target_hox_gene.cluster_gene, target_neuron),
+                None => return,
+            },
+            HoxPlacement::Standalone => return,
+        };
+
+        let placement_neuron = match self.cluster_genes.get(hox_gene.cluster_gene.0) {
+            Some(cluster_gene) => cluster_gene.placement_neuron,
+            None => return,
+        };
+
+        writeln!(
+            dot,
+            "    {} -> {} [style = dashed];",
+            neuron_node_id(target_cluster_gene, target_neuron),
+            neuron_node_id(hox_gene.cluster_gene, placement_neuron),
+        )
+        .expect("Writing to a String never fails");
+    }
+}
+
+/// Index of the cluster gene, index of the origin neuron and index of the target neuron
+type ConnectionGeneKey = (usize, usize, usize);
+
+/// Returns the number of excess and disjoint genes found in `genes`, but not in `other_genes`.
+fn count_unmatched_genes(
+    genes: &BTreeMap<ConnectionGeneKey, Weight>,
+    other_genes: &BTreeMap<ConnectionGeneKey, Weight>,
+) -> (usize, usize) {
+    let last_other_key = other_genes.keys().next_back();
+
+    genes
+        .keys()
+        .filter(|key| !other_genes.contains_key(key))
+        .fold((0, 0), |(excess_gene_count, disjoint_gene_count), key| {
+            if last_other_key.map_or(true, |last_other_key| key > last_other_key) {
+                (excess_gene_count + 1, disjoint_gene_count)
+            } else {
+                (excess_gene_count, disjoint_gene_count + 1)
+            }
+        })
+}
+
+fn write_cluster_gene(
+    dot: &mut String,
+    cluster_gene_index: ClusterGeneIndex,
+    cluster_gene: &ClusterGene,
+) {
+    writeln!(dot, "    subgraph cluster_{} {{", cluster_gene_index.0)
+        .expect("Writing to a String never fails");
+    writeln!(
+        dot,
+        "        label = \"cluster gene {}\";",
+        cluster_gene_index.0
+    )
+    .expect("Writing to a String never fails");
+
+    for neuron_index in (0..cluster_gene.neurons.len()).map(ClusterNeuronIndex) {
+        writeln!(
+            dot,
+            "        {} [shape = {}];",
+            neuron_node_id(cluster_gene_index, neuron_index),
+            neuron_shape(&cluster_gene.specialization, neuron_index),
+        )
+        .expect("Writing to a String never fails");
+    }
+
+    for connection in &cluster_gene.connections {
+        writeln!(
+            dot,
+            "        {} -> {} [label = \"{}\"];",
+            neuron_node_id(cluster_gene_index, connection.from),
+            neuron_node_id(cluster_gene_index, connection.to),
+            connection.weight,
+        )
+        .expect("Writing to a String never fails");
+    }
+
+    dot.push_str("    }\n");
+}
+
+fn neuron_node_id(
+    cluster_gene_index: ClusterGeneIndex,
+    neuron_index: ClusterNeuronIndex,
+) -> String {
+    format!("cluster_{}_neuron_{}", cluster_gene_index.0, neuron_index.0)
+}
+
+fn neuron_shape(
+    specialization: &ClusterGeneSpecialization,
+    neuron_index: ClusterNeuronIndex,
+) -> &'static str {
+    match specialization {
+        ClusterGeneSpecialization::Input(input_neuron) if *input_neuron == neuron_index => "box",
+        ClusterGeneSpecialization::Output(output_neuron) if *output_neuron == neuron_index => {
+            "doublecircle"
+        }
+        _ => "circle",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nearly_eq::assert_nearly_eq;
+
+    #[test]
+    fn dot_contains_neurons_and_connections_of_cluster_gene() {
+        let genome = Genome {
+            hox_genes: vec![HoxGene {
+                placement_target: HoxPlacement::Standalone,
+                cluster_gene: ClusterGeneIndex(0),
+                disabled_connections: HashSet::new(),
+            }],
+            cluster_genes: vec![ClusterGene {
+                neurons: vec![Neuron::new(), Neuron::new()],
+                connections: vec![Connection {
+                    from: ClusterNeuronIndex(0),
+                    to: ClusterNeuronIndex(1),
+                    weight: 0.5,
+                }],
+                placement_neuron: ClusterNeuronIndex(0),
+                specialization: ClusterGeneSpecialization::Input(ClusterNeuronIndex(0)),
+            }],
+        };
+
+        let dot = genome.to_dot();
+
+        assert!(dot.starts_with("digraph genome {"));
+        assert!(dot.contains("subgraph cluster_0 {"));
+        assert!(dot.contains("cluster_0_neuron_0 [shape = box];"));
+        assert!(dot.contains("cluster_0_neuron_1 [shape = circle];"));
+        assert!(dot.contains("cluster_0_neuron_0 -> cluster_0_neuron_1 [label = \"0.5\"];"));
+        assert!(!dot.contains("style = dashed"));
+    }
+
+    #[test]
+    fn dot_contains_placement_of_hox_gene() {
+        let cluster_gene = ClusterGene {
+            neurons: vec![Neuron::new(), Neuron::new()],
+            connections: Vec::new(),
+            placement_neuron: ClusterNeuronIndex(1),
+            specialization: ClusterGeneSpecialization::None,
+        };
+        let genome = Genome {
+            hox_genes: vec![
+                HoxGene {
+                    placement_target: HoxPlacement::Standalone,
+                    cluster_gene: ClusterGeneIndex(0),
+                    disabled_connections: HashSet::new(),
+                },
+                HoxGene {
+                    placement_target: HoxPlacement::HoxGene {
+                        hox_gene: HoxGeneIndex(0),
+                        target_neuron: ClusterNeuronIndex(0),
+                    },
+                    cluster_gene: ClusterGeneIndex(1),
+                    disabled_connections: HashSet::new(),
+                },
+            ],
+            cluster_genes: vec![cluster_gene.clone(), cluster_gene],
+        };
+
+        let dot = genome.to_dot();
+
+        assert!(dot.contains("cluster_0_neuron_0 -> cluster_1_neuron_1 [style = dashed];"));
+    }
+
+    const COEFFICIENTS: DistanceCoefficients = DistanceCoefficients {
+        excess_genes: 1.0,
+        disjoint_genes: 2.0,
+        weight_difference: 0.5,
+    };
+
+    fn genome_with_connections(connections: Vec<Connection>) -> Genome {
+        Genome {
+            hox_genes: vec![HoxGene {
+                placement_target: HoxPlacement::Standalone,
+                cluster_gene: ClusterGeneIndex(0),
+                disabled_connections: HashSet::new(),
+            }],
+            cluster_genes: vec![ClusterGene {
+                neurons: vec![Neuron::new(); 3],
+                connections,
+                placement_neuron: ClusterNeuronIndex(0),
+                specialization: ClusterGeneSpecialization::None,
+            }],
+        }
+    }
+
+    fn connection(from: usize, to: usize, weight: Weight) -> Connection {
+        Connection {
+            from: ClusterNeuronIndex(from),
+            to: ClusterNeuronIndex(to),
+            weight,
+        }
+    }
+
+    #[test]
+    fn distance_between_identical_genomes_is_zero() {
+        let genome = genome_with_connections(vec![connection(0, 1, 1.0), connection(1, 2, 0.5)]);
+
+        assert_nearly_eq!(0.0, genome.distance(&genome, &COEFFICIENTS));
+    }
+
+    #[test]
+    fn excess_gene_increases_distance_by_excess_coefficient() {
+        let genome = genome_with_connections(vec![connection(0, 1, 1.0), connection(1, 2, 0.5)]);
+        let mut other_genome = genome.clone();
+        other_genome.cluster_genes[0]
+            .connections
+            .push(connection(2, 0, 1.0));
+
+        assert_nearly_eq!(
+            COEFFICIENTS.excess_genes,
+            genome.distance(&other_genome, &COEFFICIENTS)
+        );
+        assert_nearly_eq!(
+            COEFFICIENTS.excess_genes,
+            other_genome.distance(&genome, &COEFFICIENTS)
+        );
+    }
+
+    #[test]
+    fn disjoint_genes_and_weight_differences_increase_distance() {
+        let genome = genome_with_connections(vec![connection(0, 1, 1.0), connection(2, 0, 0.5)]);
+        let other_genome =
+            genome_with_connections(vec![connection(1, 2, 1.0), connection(2, 0, 0.25)]);
+
+        assert_nearly_eq!(
+            2.0 * COEFFICIENTS.disjoint_genes + 0.25 * COEFFICIENTS.weight_difference,
+            genome.distance(&other_genome, &COEFFICIENTS)
+        );
+    }
+}
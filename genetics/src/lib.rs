@@ -19,13 +19,16 @@ use crate::genome::Genome;
 #[cfg(any(test, feature = "use-mocks"))]
 use mockiato::mockable;
 use myelin_clone_box::clone_box;
+use myelin_neural_network::dot;
 use myelin_neural_network::{Connection, Handle, NeuralNetwork};
 use std::fmt::Debug;
 use std::num::NonZeroUsize;
 
 pub mod genome;
 pub mod genome_generator_impl;
+pub mod mutation_operators;
 pub mod neural_network_development_orchestrator_impl;
+pub mod population;
 
 mod constant;
 
@@ -100,6 +103,24 @@ pub struct DevelopedNeuralNetwork {
     pub output_neuron_handles: Vec<Handle>,
 }
 
+impl DevelopedNeuralNetwork {
+    /// Renders the developed [`NeuralNetwork`] as a graph in the [Graphviz] DOT language,
+    /// highlighting the neurons referenced by [`input_neuron_handles`] and
+    /// [`output_neuron_handles`].
+    ///
+    /// [`NeuralNetwork`]: ../myelin-neural-network/trait.NeuralNetwork.html
+    /// [Graphviz]: https://www.graphviz.org/
+    /// [`input_neuron_handles`]: ./struct.DevelopedNeuralNetwork.html#structfield.input_neuron_handles
+    /// [`output_neuron_handles`]: ./struct.DevelopedNeuralNetwork.html#structfield.output_neuron_handles
+    pub fn to_dot(&self) -> String {
+        dot::to_dot(
+            &*self.neural_network,
+            &self.input_neuron_handles,
+            &self.output_neuron_handles,
+        )
+    }
+}
+
 /// A factory for producing a [`NeuralNetwork`] out of a [`Genome`]
 ///
 /// [`NeuralNetwork`]: ../myelin-neural-network/trait.NeuralNetwork.html
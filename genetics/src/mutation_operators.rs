@@ -0,0 +1,373 @@
+//! Individual mutation operators that can be composed freely, e.g. in experiments.
+//!
+//! Every operator picks its target at random and reports whether it changed the [`Genome`].
+//! If the topology of the [`Genome`] doesn't allow the mutation, it is left untouched.
+
+use crate::genome::*;
+use crate::neural_network_development_orchestrator_impl::{
+    Mutation, MutationApplier, MutationApplierImpl,
+};
+use myelin_random::Random;
+
+/// Places a new neuron on a random existing connection of a random [`ClusterGene`].
+/// A → B becomes A → C → B, where C is the new neuron.
+///
+/// Returns `false` if no [`ClusterGene`] contains any connections.
+pub fn add_neuron(genome: &mut Genome, random: &dyn Random) -> bool {
+    let cluster_genes = cluster_genes_with_connections(genome);
+
+    if cluster_genes.is_empty() {
+        return false;
+    }
+
+    let cluster_gene_index = cluster_genes[random.usize_in_range(0, cluster_genes.len())];
+    let connection_count = genome.cluster_genes[cluster_gene_index.0].connections.len();
+
+    apply_mutation(
+        genome,
+        Mutation::AddNeuron {
+            cluster_gene: cluster_gene_index,
+            connection: ClusterConnectionIndex(random.usize_in_range(0, connection_count)),
+            new_connection_weight: random_connection_weight(random),
+        },
+    )
+}
+
+/// Connects a random pair of neurons of a [`ClusterGene`] that are not yet connected.
+///
+/// Returns `false` if all neurons are already connected with each other.
+pub fn add_connection(genome: &mut Genome, random: &dyn Random) -> bool {
+    let unconnected_neurons = unconnected_neurons(genome);
+
+    if unconnected_neurons.is_empty() {
+        return false;
+    }
+
+    let (cluster_gene_index, from, to) =
+        unconnected_neurons[random.usize_in_range(0, unconnected_neurons.len())];
+
+    apply_mutation(
+        genome,
+        Mutation::AddConnection {
+            cluster_gene: cluster_gene_index,
+            connection: Connection {
+                from,
+                to,
+                weight: random_connection_weight(random),
+            },
+        },
+    )
+}
+
+/// Nudges the weight of every connection of every [`ClusterGene`] by a small random delta.
+///
+/// Returns `false` if the [`Genome`] contains no connections.
+pub fn perturb_weights(genome: &mut Genome, random: &dyn Random) -> bool {
+    let connections: Vec<_> = genome
+        .cluster_genes
+        .iter()
+        .enumerate()
+        .flat_map(|(cluster_gene_index, cluster_gene)| {
+            (0..cluster_gene.connections.len()).map(move |connection_index| {
+                (
+                    ClusterGeneIndex(cluster_gene_index),
+                    ClusterConnectionIndex(connection_index),
+                )
+            })
+        })
+        .collect();
+
+    if connections.is_empty() {
+        return false;
+    }
+
+    for (cluster_gene_index, connection_index) in connections {
+        apply_mutation(
+            genome,
+            Mutation::NudgeWeight {
+                cluster_gene: cluster_gene_index,
+                connection: connection_index,
+                weight_delta: random.f64_in_range(-MAX_WEIGHT_DELTA, MAX_WEIGHT_DELTA),
+            },
+        );
+    }
+
+    true
+}
+
+/// Disables a random connection of the cluster placed by a random [`HoxGene`],
+/// or enables it again if it was already disabled.
+///
+/// Returns `false` if no [`HoxGene`] places a cluster containing any connections.
+pub fn toggle_connection(genome: &mut Genome, random: &dyn Random) -> bool {
+    let hox_genes: Vec<_> = genome
+        .hox_genes
+        .iter()
+        .enumerate()
+        .filter_map(|(hox_gene_index, hox_gene)| {
+            let connection_count = genome
+                .cluster_genes
+                .get(hox_gene.cluster_gene.0)?
+                .connections
+                .len();
+
+            if connection_count > 0 {
+                Some((HoxGeneIndex(hox_gene_index), connection_count))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if hox_genes.is_empty() {
+        return false;
+    }
+
+    let (hox_gene_index, connection_count) = hox_genes[random.usize_in_range(0, hox_genes.len())];
+    let connection_index = ClusterConnectionIndex(random.usize_in_range(0, connection_count));
+    let disabled_connections = &mut genome.hox_genes[hox_gene_index.0].disabled_connections;
+
+    if !disabled_connections.remove(&connection_index) {
+        disabled_connections.insert(connection_index);
+    }
+
+    true
+}
+
+fn apply_mutation(genome: &mut Genome, mutation: Mutation) -> bool {
+    MutationApplierImpl::new()
+        .apply_mutation(genome, mutation)
+        .is_ok()
+}
+
+fn random_connection_weight(random: &dyn Random) -> Weight {
+    random.f64_in_range(MIN_CONNECTION_WEIGHT, MAX_CONNECTION_WEIGHT)
+}
+
+fn cluster_genes_with_connections(genome: &Genome) -> Vec<ClusterGeneIndex> {
+    genome
+        .cluster_genes
+        .iter()
+        .enumerate()
+        .filter(|(_, cluster_gene)| !cluster_gene.connections.is_empty())
+        .map(|(cluster_gene_index, _)| ClusterGeneIndex(cluster_gene_index))
+        .collect()
+}
+
+fn unconnected_neurons(
+    genome: &Genome,
+) -> Vec<(ClusterGeneIndex, ClusterNeuronIndex, ClusterNeuronIndex)> {
+    let mut unconnected_neurons = Vec::new();
+
+    for (cluster_gene_index, cluster_gene) in genome.cluster_genes.iter().enumerate() {
+        let neuron_count = cluster_gene.neurons.len();
+
+        for from in (0..neuron_count).map(ClusterNeuronIndex) {
+            for to in (0..neuron_count).map(ClusterNeuronIndex) {
+                let is_connected = cluster_gene
+                    .connections
+                    .iter()
+                    .any(|connection| connection.from == from && connection.to == to);
+
+                if from != to && !is_connected {
+                    unconnected_neurons.push((ClusterGeneIndex(cluster_gene_index), from, to));
+                }
+            }
+        }
+    }
+
+    unconnected_neurons
+}
+
+const MIN_CONNECTION_WEIGHT: f64 = 0.000_000_1;
+const MAX_CONNECTION_WEIGHT: f64 = 1.0;
+
+/// Arbitrary value
+const MAX_WEIGHT_DELTA: f64 = 0.1;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maplit::hashset;
+    use myelin_random::RandomMock;
+    use std::collections::HashSet;
+
+    const NEW_CONNECTION_WEIGHT: f64 = 0.5;
+
+    #[test]
+    fn add_neuron_splits_existing_connection() {
+        let mut genome = genome_with_cluster_gene(vec![connection(0, 1, 1.0)]);
+        let mut random = RandomMock::new();
+        random
+            .expect_usize_in_range(|arg| arg.partial_eq(0), |arg| arg.partial_eq(1))
+            .returns(0)
+            .times(2);
+        random
+            .expect_f64_in_range(
+                |arg| arg.partial_eq(MIN_CONNECTION_WEIGHT),
+                |arg| arg.partial_eq(MAX_CONNECTION_WEIGHT),
+            )
+            .returns(NEW_CONNECTION_WEIGHT);
+
+        assert!(add_neuron(&mut genome, &random));
+
+        let cluster_gene = &genome.cluster_genes[0];
+        assert_eq!(3, cluster_gene.neurons.len());
+        assert_eq!(
+            vec![
+                connection(0, 2, 1.0),
+                connection(2, 1, NEW_CONNECTION_WEIGHT)
+            ],
+            cluster_gene.connections
+        );
+    }
+
+    #[test]
+    fn add_neuron_does_nothing_without_connections() {
+        let mut genome = genome_with_cluster_gene(Vec::new());
+        let expected_genome = genome.clone();
+
+        assert!(!add_neuron(&mut genome, &RandomMock::new()));
+        assert_eq!(expected_genome, genome);
+    }
+
+    #[test]
+    fn add_connection_connects_unconnected_neurons() {
+        let mut genome = genome_with_cluster_gene(vec![connection(0, 1, 1.0)]);
+        let mut random = RandomMock::new();
+        random
+            .expect_usize_in_range(|arg| arg.partial_eq(0), |arg| arg.partial_eq(1))
+            .returns(0);
+        random
+            .expect_f64_in_range(
+                |arg| arg.partial_eq(MIN_CONNECTION_WEIGHT),
+                |arg| arg.partial_eq(MAX_CONNECTION_WEIGHT),
+            )
+            .returns(NEW_CONNECTION_WEIGHT);
+
+        assert!(add_connection(&mut genome, &random));
+        assert_eq!(
+            vec![
+                connection(0, 1, 1.0),
+                connection(1, 0, NEW_CONNECTION_WEIGHT)
+            ],
+            genome.cluster_genes[0].connections
+        );
+    }
+
+    #[test]
+    fn add_connection_does_nothing_when_all_neurons_are_connected() {
+        let mut genome =
+            genome_with_cluster_gene(vec![connection(0, 1, 1.0), connection(1, 0, 1.0)]);
+        let expected_genome = genome.clone();
+
+        assert!(!add_connection(&mut genome, &RandomMock::new()));
+        assert_eq!(expected_genome, genome);
+    }
+
+    #[test]
+    fn perturb_weights_nudges_every_connection() {
+        let mut genome =
+            genome_with_cluster_gene(vec![connection(0, 1, 1.0), connection(1, 0, 1.0)]);
+        let mut random = RandomMock::new();
+        random.expect_f64_in_range_calls_in_order();
+        for &weight_delta in &[0.125, -0.25] {
+            random
+                .expect_f64_in_range(
+                    |arg| arg.partial_eq(-MAX_WEIGHT_DELTA),
+                    |arg| arg.partial_eq(MAX_WEIGHT_DELTA),
+                )
+                .returns(weight_delta);
+        }
+
+        assert!(perturb_weights(&mut genome, &random));
+        assert_eq!(
+            vec![connection(0, 1, 1.125), connection(1, 0, 0.75)],
+            genome.cluster_genes[0].connections
+        );
+    }
+
+    #[test]
+    fn perturb_weights_does_nothing_without_connections() {
+        let mut genome = genome_with_cluster_gene(Vec::new());
+        let expected_genome = genome.clone();
+
+        assert!(!perturb_weights(&mut genome, &RandomMock::new()));
+        assert_eq!(expected_genome, genome);
+    }
+
+    #[test]
+    fn toggle_connection_disables_enabled_connection() {
+        let mut genome =
+            genome_with_cluster_gene(vec![connection(0, 1, 1.0), connection(1, 0, 1.0)]);
+
+        assert!(toggle_connection(
+            &mut genome,
+            &random_picking_second_connection()
+        ));
+        assert_eq!(
+            hashset! { ClusterConnectionIndex(1) },
+            genome.hox_genes[0].disabled_connections
+        );
+    }
+
+    #[test]
+    fn toggle_connection_enables_disabled_connection() {
+        let mut genome =
+            genome_with_cluster_gene(vec![connection(0, 1, 1.0), connection(1, 0, 1.0)]);
+        genome.hox_genes[0]
+            .disabled_connections
+            .insert(ClusterConnectionIndex(1));
+
+        assert!(toggle_connection(
+            &mut genome,
+            &random_picking_second_connection()
+        ));
+        assert!(genome.hox_genes[0].disabled_connections.is_empty());
+    }
+
+    #[test]
+    fn toggle_connection_does_nothing_when_hox_gene_has_no_cluster_gene() {
+        let mut genome = genome_with_cluster_gene(vec![connection(0, 1, 1.0)]);
+        genome.hox_genes[0].cluster_gene = ClusterGeneIndex(1);
+        let expected_genome = genome.clone();
+
+        assert!(!toggle_connection(&mut genome, &RandomMock::new()));
+        assert_eq!(expected_genome, genome);
+    }
+
+    fn random_picking_second_connection() -> RandomMock<'static> {
+        let mut random = RandomMock::new();
+        random
+            .expect_usize_in_range(|arg| arg.partial_eq(0), |arg| arg.partial_eq(1))
+            .returns(0);
+        random
+            .expect_usize_in_range(|arg| arg.partial_eq(0), |arg| arg.partial_eq(2))
+            .returns(1);
+        random
+    }
+
+    fn genome_with_cluster_gene(connections: Vec<Connection>) -> Genome {
+        Genome {
+            hox_genes: vec![HoxGene {
+                placement_target: HoxPlacement::Standalone,
+                cluster_gene: ClusterGeneIndex(0),
+                disabled_connections: HashSet::new(),
+            }],
+            cluster_genes: vec![ClusterGene {
+                neurons: vec![Neuron::new(); 2],
+                connections,
+                placement_neuron: ClusterNeuronIndex(0),
+                specialization: ClusterGeneSpecialization::None,
+            }],
+        }
+    }
+
+    fn connection(from: usize, to: usize, weight: Weight) -> Connection {
+        Connection {
+            from: ClusterNeuronIndex(from),
+            to: ClusterNeuronIndex(to),
+            weight,
+        }
+    }
+}
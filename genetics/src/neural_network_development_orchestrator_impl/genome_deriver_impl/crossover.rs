@@ -4,19 +4,60 @@ use crate::neural_network_development_orchestrator_impl::GenomeDeriver;
 use itertools::Itertools;
 use myelin_random::Random;
 
+/// Scheme used to recombine the genes of two parent [`Genome`]s.
+/// Genes are aligned by their position in the [`Genome`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CrossoverStrategy {
+    /// Every gene is picked from either parent by flipping a coin.
+    /// Extra genes that are only found in the longer parent are inherited with a fixed probability.
+    Uniform,
+
+    /// A crossover point is picked at random. The genes in front of it are taken
+    /// from the first parent, all genes from the crossover point onwards from the second parent.
+    SinglePoint,
+}
+
+impl Default for CrossoverStrategy {
+    fn default() -> Self {
+        CrossoverStrategy::Uniform
+    }
+}
+
 /// Implementation of chromosomal crossover
 #[derive(Debug, Clone)]
 pub struct ChromosomalCrossoverGenomeDeriver {
     random: Box<dyn Random>,
+    crossover_strategy: CrossoverStrategy,
 }
 
 impl ChromosomalCrossoverGenomeDeriver {
-    /// Creates a new instance of [`ChromosomalCrossoverGenomeDeriver`].
+    /// Creates a new instance of [`ChromosomalCrossoverGenomeDeriver`]
+    /// that uses [`CrossoverStrategy::Uniform`].
+    ///
+    /// [`CrossoverStrategy::Uniform`]: ./enum.CrossoverStrategy.html#variant.Uniform
     pub fn new(random: Box<dyn Random>) -> Self {
-        Self { random }
+        Self {
+            random,
+            crossover_strategy: CrossoverStrategy::default(),
+        }
+    }
+
+    /// Uses the given [`CrossoverStrategy`] instead of the default one.
+    pub fn with_crossover_strategy(mut self, crossover_strategy: CrossoverStrategy) -> Self {
+        self.crossover_strategy = crossover_strategy;
+        self
     }
 
     fn crossover_genes<T>(&self, genome_one: Vec<T>, genome_two: Vec<T>) -> Vec<T> {
+        match self.crossover_strategy {
+            CrossoverStrategy::Uniform => self.uniform_crossover_genes(genome_one, genome_two),
+            CrossoverStrategy::SinglePoint => {
+                self.single_point_crossover_genes(genome_one, genome_two)
+            }
+        }
+    }
+
+    fn uniform_crossover_genes<T>(&self, genome_one: Vec<T>, genome_two: Vec<T>) -> Vec<T> {
         use itertools::EitherOrBoth::*;
 
         genome_one
@@ -29,6 +70,17 @@ impl ChromosomalCrossoverGenomeDeriver {
             .collect()
     }
 
+    fn single_point_crossover_genes<T>(&self, genome_one: Vec<T>, genome_two: Vec<T>) -> Vec<T> {
+        let shortest_length = genome_one.len().min(genome_two.len());
+        let crossover_point = self.random.usize_in_range(0, shortest_length + 1);
+
+        genome_one
+            .into_iter()
+            .take(crossover_point)
+            .chain(genome_two.into_iter().skip(crossover_point))
+            .collect()
+    }
+
     fn pick_one<T>(&self, gene_one: T, gene_two: T) -> T {
         if self.random.flip_coin() {
             gene_one
@@ -198,4 +250,92 @@ mod tests {
 
         assert_eq!(expected_genome, actual_genome);
     }
+
+    #[test]
+    fn uniform_crossover_with_coin_always_landing_on_true_yields_first_parent() {
+        let genome_one = Genome {
+            hox_genes: vec![hox_gene(0), hox_gene(1)],
+            cluster_genes: vec![cluster_gene(2), cluster_gene(3), cluster_gene(4)],
+        };
+
+        let genome_two = Genome {
+            hox_genes: vec![hox_gene(10), hox_gene(11)],
+            cluster_genes: vec![cluster_gene(12), cluster_gene(13), cluster_gene(14)],
+        };
+
+        let mut random = RandomMock::new();
+        random.expect_flip_coin().returns(true).times(5);
+
+        let deriver = ChromosomalCrossoverGenomeDeriver::new(box random)
+            .with_crossover_strategy(CrossoverStrategy::Uniform);
+
+        let actual_genome = deriver.derive_genome_from_parents((genome_one.clone(), genome_two));
+
+        assert_eq!(genome_one, actual_genome);
+    }
+
+    #[test]
+    fn single_point_crossover_takes_genes_behind_crossover_point_from_second_parent() {
+        let genome_one = Genome {
+            hox_genes: vec![hox_gene(0), hox_gene(1)],
+            cluster_genes: vec![cluster_gene(2), cluster_gene(3), cluster_gene(4)],
+        };
+
+        let genome_two = Genome {
+            hox_genes: vec![hox_gene(10), hox_gene(11), hox_gene(12)],
+            cluster_genes: vec![cluster_gene(13), cluster_gene(14)],
+        };
+
+        let expected_genome = Genome {
+            hox_genes: vec![hox_gene(0), hox_gene(11), hox_gene(12)],
+            cluster_genes: vec![cluster_gene(13), cluster_gene(14)],
+        };
+
+        let mut random = RandomMock::new();
+        random.expect_usize_in_range_calls_in_order();
+        random
+            .expect_usize_in_range(|arg| arg.partial_eq(0), |arg| arg.partial_eq(3))
+            .returns(1);
+        random
+            .expect_usize_in_range(|arg| arg.partial_eq(0), |arg| arg.partial_eq(3))
+            .returns(0);
+
+        let deriver = ChromosomalCrossoverGenomeDeriver::new(box random)
+            .with_crossover_strategy(CrossoverStrategy::SinglePoint);
+
+        let actual_genome = deriver.derive_genome_from_parents((genome_one, genome_two));
+
+        assert_eq!(expected_genome, actual_genome);
+    }
+
+    #[test]
+    fn single_point_crossover_drops_genes_of_first_parent_behind_crossover_point() {
+        let genome_one = Genome {
+            hox_genes: vec![hox_gene(0), hox_gene(1)],
+            cluster_genes: vec![cluster_gene(2), cluster_gene(3), cluster_gene(4)],
+        };
+
+        let genome_two = Genome {
+            hox_genes: vec![hox_gene(10)],
+            cluster_genes: vec![cluster_gene(12)],
+        };
+
+        let expected_genome = Genome {
+            hox_genes: vec![hox_gene(0)],
+            cluster_genes: vec![cluster_gene(2)],
+        };
+
+        let mut random = RandomMock::new();
+        random
+            .expect_usize_in_range(|arg| arg.partial_eq(0), |arg| arg.partial_eq(2))
+            .returns(1)
+            .times(2);
+
+        let deriver = ChromosomalCrossoverGenomeDeriver::new(box random)
+            .with_crossover_strategy(CrossoverStrategy::SinglePoint);
+
+        let actual_genome = deriver.derive_genome_from_parents((genome_one, genome_two));
+
+        assert_eq!(expected_genome, actual_genome);
+    }
 }
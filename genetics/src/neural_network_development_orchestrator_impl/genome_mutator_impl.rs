@@ -3,6 +3,9 @@
 pub use self::mutation::*;
 pub use self::mutation_applier_impl::*;
 use crate::genome::Genome;
+use crate::mutation_operators;
+use crate::neural_network_development_orchestrator_impl::GenomeMutator;
+use myelin_random::Random;
 use std::error::Error;
 
 mod mutation;
@@ -23,3 +26,163 @@ pub trait MutationApplier {
     fn apply_mutation(&self, genome: &mut Genome, mutation: Mutation)
         -> Result<(), Box<dyn Error>>;
 }
+
+/// Probabilities with which the individual [mutation operators] are applied to a [`Genome`].
+/// Every probability is defined in the range `[0.0; 1.0]`.
+///
+/// [mutation operators]: ../mutation_operators/index.html
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MutationConfiguration {
+    /// Probability of nudging the weights of all connections
+    pub perturb_weights_probability: f64,
+    /// Probability of connecting two previously unconnected neurons
+    pub add_connection_probability: f64,
+    /// Probability of splitting a connection by placing a new neuron on it
+    pub add_neuron_probability: f64,
+    /// Probability of disabling or enabling a connection
+    pub toggle_connection_probability: f64,
+}
+
+/// A [`GenomeMutator`] that applies each of the [mutation operators]
+/// with the probability specified in its [`MutationConfiguration`].
+///
+/// [mutation operators]: ../mutation_operators/index.html
+#[derive(Debug, Clone)]
+pub struct GenomeMutatorImpl {
+    random: Box<dyn Random>,
+    configuration: MutationConfiguration,
+}
+
+impl GenomeMutatorImpl {
+    /// Creates a new [`GenomeMutatorImpl`].
+    pub fn new(random: Box<dyn Random>, configuration: MutationConfiguration) -> Self {
+        Self {
+            random,
+            configuration,
+        }
+    }
+
+    /// Applies the mutation operators to a copy of the given [`Genome`]
+    /// according to the given [`MutationConfiguration`].
+    ///
+    /// # Panics
+    /// Panics if a probability is outside the range [0.0; 1.0].
+    pub fn mutate(&self, genome: &Genome, configuration: &MutationConfiguration) -> Genome {
+        let operators: [(f64, fn(&mut Genome, &dyn Random) -> bool); 4] = [
+            (
+                configuration.perturb_weights_probability,
+                mutation_operators::perturb_weights,
+            ),
+            (
+                configuration.add_connection_probability,
+                mutation_operators::add_connection,
+            ),
+            (
+                configuration.add_neuron_probability,
+                mutation_operators::add_neuron,
+            ),
+            (
+                configuration.toggle_connection_probability,
+                mutation_operators::toggle_connection,
+            ),
+        ];
+
+        let mut genome = genome.clone();
+
+        for &(probability, operator) in &operators {
+            if self.random.flip_coin_with_probability(probability) {
+                operator(&mut genome, &*self.random);
+            }
+        }
+
+        genome
+    }
+}
+
+impl GenomeMutator for GenomeMutatorImpl {
+    fn mutate_genome(&self, genome: Genome) -> Genome {
+        self.mutate(&genome, &self.configuration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::*;
+    use myelin_random::RandomMock;
+    use std::collections::HashSet;
+
+    #[test]
+    fn mutate_adds_exactly_one_connection_when_add_connection_probability_is_one() {
+        let genome = genome();
+        let configuration = MutationConfiguration {
+            add_connection_probability: 1.0,
+            ..MutationConfiguration::default()
+        };
+        let mut random = RandomMock::new();
+        random
+            .expect_flip_coin_with_probability(|arg| arg.partial_eq(0.0))
+            .returns(false)
+            .times(3);
+        random
+            .expect_flip_coin_with_probability(|arg| arg.partial_eq(1.0))
+            .returns(true);
+        random
+            .expect_usize_in_range(|arg| arg.partial_eq(0), |arg| arg.any())
+            .returns(0);
+        random
+            .expect_f64_in_range(|arg| arg.any(), |arg| arg.any())
+            .returns(0.5);
+        let mutator = GenomeMutatorImpl::new(box random, configuration.clone());
+
+        let mutated_genome = mutator.mutate(&genome, &configuration);
+
+        assert_eq!(
+            connection_count(&genome) + 1,
+            connection_count(&mutated_genome)
+        );
+    }
+
+    #[test]
+    fn mutate_genome_does_not_mutate_genome_when_all_probabilities_are_zero() {
+        let genome = genome();
+        let mut random = RandomMock::new();
+        random
+            .expect_flip_coin_with_probability(|arg| arg.partial_eq(0.0))
+            .returns(false)
+            .times(4);
+        let mutator = GenomeMutatorImpl::new(box random, MutationConfiguration::default());
+
+        let mutated_genome = mutator.mutate_genome(genome.clone());
+
+        assert_eq!(genome, mutated_genome);
+    }
+
+    fn connection_count(genome: &Genome) -> usize {
+        genome
+            .cluster_genes
+            .iter()
+            .map(|cluster_gene| cluster_gene.connections.len())
+            .sum()
+    }
+
+    fn genome() -> Genome {
+        Genome {
+            hox_genes: vec![HoxGene {
+                placement_target: HoxPlacement::Standalone,
+                cluster_gene: ClusterGeneIndex(0),
+                disabled_connections: HashSet::new(),
+            }],
+            cluster_genes: vec![ClusterGene {
+                neurons: vec![Neuron::new(); 3],
+                connections: vec![Connection {
+                    from: ClusterNeuronIndex(0),
+                    to: ClusterNeuronIndex(1),
+                    weight: 1.0,
+                }],
+                placement_neuron: ClusterNeuronIndex(0),
+                specialization: ClusterGeneSpecialization::None,
+            }],
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! A versioned binary file format for persisting a population of [`Genome`]s between runs.
+//!
+//! A population file starts with the version of the format (`u32`, little endian).
+//! It is followed by one entry per genome, each consisting of
+//! the length of the serialized genome in bytes (`u32`, little endian)
+//! and the genome serialized with [`bincode`].
+//!
+//! [`Genome`]: ../genome/struct.Genome.html
+//! [`bincode`]: https://github.com/TyOverby/bincode
+
+use crate::genome::Genome;
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+/// The newest version of the format that can be loaded
+pub const POPULATION_FORMAT_VERSION: u32 = 1;
+
+const VERSION_SIZE: usize = 4;
+const LENGTH_SIZE: usize = 4;
+
+/// Writes a population of [`Genome`]s in the current version of the format.
+///
+/// # Errors
+/// Returns an error if writing fails or if a serialized genome does not fit into an entry.
+pub fn save_population(writer: &mut dyn Write, population: &[Genome]) -> io::Result<()> {
+    writer.write_all(&POPULATION_FORMAT_VERSION.to_le_bytes())?;
+
+    for genome in population {
+        let serialized_genome = bincode::serialize(genome)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        let length: u32 = serialized_genome
+            .len()
+            .try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Genome is too large"))?;
+
+        writer.write_all(&length.to_le_bytes())?;
+        writer.write_all(&serialized_genome)?;
+    }
+
+    Ok(())
+}
+
+/// Reads a population of [`Genome`]s written by [`save_population`].
+///
+/// # Errors
+/// Returns a [`LoadPopulationError`] if the population was written in an unsupported version
+/// of the format or is otherwise invalid.
+pub fn load_population(mut population: &[u8]) -> Result<Vec<Genome>, LoadPopulationError> {
+    let (version, rest) = split_part(population, VERSION_SIZE)?;
+    let version = u32::from_le_bytes(version.try_into().unwrap());
+
+    if version != POPULATION_FORMAT_VERSION {
+        return Err(LoadPopulationError::UnsupportedVersion(version));
+    }
+
+    population = rest;
+    let mut genomes = Vec::new();
+
+    while !population.is_empty() {
+        let (length, rest) = split_part(population, LENGTH_SIZE)?;
+        let length = u32::from_le_bytes(length.try_into().unwrap()) as usize;
+        let (serialized_genome, rest) = split_part(rest, length)?;
+
+        let genome = bincode::deserialize(serialized_genome)
+            .map_err(|error| LoadPopulationError::InvalidGenome(error.to_string()))?;
+        genomes.push(genome);
+
+        population = rest;
+    }
+
+    Ok(genomes)
+}
+
+fn split_part(buf: &[u8], size: usize) -> Result<(&[u8], &[u8]), LoadPopulationError> {
+    if buf.len() < size {
+        Err(LoadPopulationError::Truncated)
+    } else {
+        Ok(buf.split_at(size))
+    }
+}
+
+/// The reason why a population could not be loaded by [`load_population`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LoadPopulationError {
+    /// The population ended in the middle of the version or an entry
+    Truncated,
+    /// The population was written in a version of the format that is not supported,
+    /// e.g. by a newer version of this crate
+    UnsupportedVersion(u32),
+    /// A genome could not be deserialized
+    InvalidGenome(String),
+}
+
+impl Error for LoadPopulationError {}
+
+impl fmt::Display for LoadPopulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadPopulationError::Truncated => write!(f, "The population is truncated"),
+            LoadPopulationError::UnsupportedVersion(version) => write!(
+                f,
+                "The population was saved in version {} of the format, but only version {} is \
+                 supported",
+                version, POPULATION_FORMAT_VERSION
+            ),
+            LoadPopulationError::InvalidGenome(reason) => {
+                write!(f, "The population contains an invalid genome: {}", reason)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::genome::*;
+    use maplit::hashset;
+    use std::collections::HashSet;
+
+    fn population() -> Vec<Genome> {
+        let input_cluster_gene = ClusterGene {
+            neurons: vec![Neuron::new(); 3],
+            connections: vec![
+                Connection {
+                    from: ClusterNeuronIndex(0),
+                    to: ClusterNeuronIndex(1),
+                    weight: 0.5,
+                },
+                Connection {
+                    from: ClusterNeuronIndex(1),
+                    to: ClusterNeuronIndex(2),
+                    weight: 0.25,
+                },
+            ],
+            placement_neuron: ClusterNeuronIndex(0),
+            specialization: ClusterGeneSpecialization::Input(ClusterNeuronIndex(0)),
+        };
+        let output_cluster_gene = ClusterGene {
+            specialization: ClusterGeneSpecialization::Output(ClusterNeuronIndex(2)),
+            ..input_cluster_gene.clone()
+        };
+
+        vec![
+            Genome::default(),
+            Genome {
+                hox_genes: vec![HoxGene {
+                    placement_target: HoxPlacement::Standalone,
+                    cluster_gene: ClusterGeneIndex(0),
+                    disabled_connections: HashSet::new(),
+                }],
+                cluster_genes: vec![input_cluster_gene.clone()],
+            },
+            Genome {
+                hox_genes: vec![
+                    HoxGene {
+                        placement_target: HoxPlacement::Standalone,
+                        cluster_gene: ClusterGeneIndex(0),
+                        disabled_connections: hashset! { ClusterConnectionIndex(1) },
+                    },
+                    HoxGene {
+                        placement_target: HoxPlacement::HoxGene {
+                            hox_gene: HoxGeneIndex(0),
+                            target_neuron: ClusterNeuronIndex(2),
+                        },
+                        cluster_gene: ClusterGeneIndex(1),
+                        disabled_connections: HashSet::new(),
+                    },
+                ],
+                cluster_genes: vec![input_cluster_gene, output_cluster_gene],
+            },
+        ]
+    }
+
+    #[test]
+    fn population_round_trip() {
+        let mut saved_population = Vec::new();
+        save_population(&mut saved_population, &population()).unwrap();
+
+        assert_eq!(Ok(population()), load_population(&saved_population));
+    }
+
+    #[test]
+    fn empty_population_round_trip() {
+        let mut saved_population = Vec::new();
+        save_population(&mut saved_population, &[]).unwrap();
+
+        assert_eq!(Ok(Vec::new()), load_population(&saved_population));
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let mut saved_population = Vec::new();
+        save_population(&mut saved_population, &population()).unwrap();
+        let newer_version = POPULATION_FORMAT_VERSION + 1;
+        saved_population[..VERSION_SIZE].copy_from_slice(&newer_version.to_le_bytes());
+
+        assert_eq!(
+            Err(LoadPopulationError::UnsupportedVersion(newer_version)),
+            load_population(&saved_population)
+        );
+    }
+
+    #[test]
+    fn truncated_population_is_rejected() {
+        let mut saved_population = Vec::new();
+        save_population(&mut saved_population, &population()).unwrap();
+        saved_population.pop();
+
+        assert_eq!(
+            Err(LoadPopulationError::Truncated),
+            load_population(&saved_population)
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use super::{Handle, Weight};
+use serde::{Deserialize, Serialize};
 
 /// The synaptic connection between two neurons and its strength.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Connection {
     /// The handle of the origin neuron.
     pub from: Handle,
@@ -0,0 +1,143 @@
+//! Rendering of neural networks as graphs in the [Graphviz] DOT language
+//!
+//! [Graphviz]: https://www.graphviz.org/
+
+use crate::{Connection, Handle, NeuralNetwork};
+
+const INPUT_NEURON_ATTRIBUTES: &str = "shape = box, style = filled, fillcolor = lightblue";
+const OUTPUT_NEURON_ATTRIBUTES: &str =
+    "shape = doublecircle, style = filled, fillcolor = lightgreen";
+const HIDDEN_NEURON_ATTRIBUTES: &str = "shape = circle";
+
+/// Renders the `neural_network` as a DOT graph that flows from the `input_neurons`
+/// on the left to the `output_neurons` on the right.
+/// The output can be rendered with e.g. `dot -Tsvg`.
+pub fn to_dot(
+    neural_network: &dyn NeuralNetwork,
+    input_neurons: &[Handle],
+    output_neurons: &[Handle],
+) -> String {
+    neural_network_to_dot(
+        &neural_network.neurons(),
+        &neural_network.connections(),
+        input_neurons,
+        output_neurons,
+    )
+}
+
+/// Renders the given neurons and connections as a DOT graph.
+/// Neurons contained in `input_neurons` are placed on the left and those contained
+/// in `output_neurons` on the right. Both are highlighted with a different shape
+/// and color than hidden neurons.
+pub fn neural_network_to_dot(
+    neurons: &[Handle],
+    connections: &[Connection],
+    input_neurons: &[Handle],
+    output_neurons: &[Handle],
+) -> String {
+    let mut dot = String::from("digraph neural_network {\n    rankdir = LR;\n");
+
+    for neuron in neurons {
+        let attributes = if input_neurons.contains(neuron) {
+            INPUT_NEURON_ATTRIBUTES
+        } else if output_neurons.contains(neuron) {
+            OUTPUT_NEURON_ATTRIBUTES
+        } else {
+            HIDDEN_NEURON_ATTRIBUTES
+        };
+
+        dot.push_str(&format!("    neuron_{} [{}];\n", neuron.0, attributes));
+    }
+
+    dot.push_str(&rank_of_neurons("source", input_neurons));
+    dot.push_str(&rank_of_neurons("sink", output_neurons));
+
+    for connection in connections {
+        dot.push_str(&format!(
+            "    neuron_{} -> neuron_{} [label = \"{}\"];\n",
+            connection.from.0, connection.to.0, connection.weight
+        ));
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Places all `neurons` on the same rank, e.g. on the first one for a `rank` of `source`
+fn rank_of_neurons(rank: &str, neurons: &[Handle]) -> String {
+    if neurons.is_empty() {
+        return String::new();
+    }
+
+    let neurons: Vec<_> = neurons
+        .iter()
+        .map(|neuron| format!("neuron_{};", neuron.0))
+        .collect();
+
+    format!("    {{ rank = {}; {} }}\n", rank, neurons.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NeuralNetworkMock;
+
+    #[test]
+    fn input_and_output_neurons_are_highlighted() {
+        let neurons = [Handle(0), Handle(1), Handle(2)];
+        let connections = [
+            Connection {
+                from: Handle(0),
+                to: Handle(1),
+                weight: 0.5,
+            },
+            Connection {
+                from: Handle(1),
+                to: Handle(2),
+                weight: 1.0,
+            },
+        ];
+
+        let dot = neural_network_to_dot(&neurons, &connections, &[Handle(0)], &[Handle(2)]);
+
+        assert!(dot.starts_with("digraph neural_network {"));
+        assert!(dot.contains("rankdir = LR;"));
+        assert!(dot.contains("neuron_0 [shape = box, style = filled, fillcolor = lightblue];"));
+        assert!(dot.contains("neuron_1 [shape = circle];"));
+        assert!(dot
+            .contains("neuron_2 [shape = doublecircle, style = filled, fillcolor = lightgreen];"));
+        assert!(dot.contains("{ rank = source; neuron_0; }"));
+        assert!(dot.contains("{ rank = sink; neuron_2; }"));
+        assert!(dot.contains("neuron_0 -> neuron_1 [label = \"0.5\"];"));
+        assert!(dot.contains("neuron_1 -> neuron_2 [label = \"1\"];"));
+    }
+
+    #[test]
+    fn to_dot_renders_neural_network() {
+        let mut neural_network = NeuralNetworkMock::new();
+        neural_network
+            .expect_neurons()
+            .returns(vec![Handle(0), Handle(1), Handle(2)]);
+        neural_network.expect_connections().returns(vec![
+            Connection {
+                from: Handle(0),
+                to: Handle(2),
+                weight: 0.25,
+            },
+            Connection {
+                from: Handle(1),
+                to: Handle(2),
+                weight: -0.5,
+            },
+        ]);
+
+        let dot = to_dot(&neural_network, &[Handle(0), Handle(1)], &[Handle(2)]);
+
+        assert!(dot.contains("{ rank = source; neuron_0; neuron_1; }"));
+        assert!(dot.contains("{ rank = sink; neuron_2; }"));
+        assert!(dot.contains("neuron_0 -> neuron_2 [label = \"0.25\"];"));
+        assert!(dot.contains("neuron_1 -> neuron_2 [label = \"-0.5\"];"));
+        assert!(!dot.contains("shape = circle"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
@@ -16,19 +16,26 @@
 )]
 
 mod connection;
+pub mod dot;
+mod serializable_network;
+pub mod spike_recorder;
 pub mod spiking_neural_network;
 pub use self::connection::*;
+pub use self::serializable_network::*;
 
 #[cfg(any(test, feature = "use-mocks"))]
 use mockiato::mockable;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Debug;
 
 /// A handle to a neuron
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Handle(pub usize);
 
-/// A modifier of incoming spikes
+/// A modifier of incoming spikes.
+/// Positive weights are excitatory, i.e. they raise the membrane potential of the receiving neuron,
+/// while negative weights are inhibitory and lower it.
 pub type Weight = f64;
 
 /// A representation of time
@@ -56,10 +63,57 @@ pub trait NeuralNetwork: Debug + NeuralNetworkClone {
     /// Add a new unconnected neuron to the network
     fn push_neuron(&mut self) -> Handle;
 
+    /// Add a new unconnected bias neuron to the network, which passes `constant_potential`
+    /// on to its connected neurons on every step, regardless of its own inputs.
+    fn push_bias_neuron(&mut self, constant_potential: MembranePotential) -> Handle;
+
+    /// The constant potential of a bias neuron, or `None` if the neuron is a regular one.
+    /// # Errors
+    /// Returns `Err` if the handle is invalid
+    fn bias_potential_of_neuron(&self, neuron: Handle) -> Result<Option<MembranePotential>>;
+
+    /// Returns all neurons to their resting state
+    /// while keeping the neurons and their connections
+    fn reset(&mut self);
+
     /// Add a new connection between two neurons.
     /// # Errors
-    /// Returns `Err` if an involved handle is invalid
+    /// Returns `Err` if an involved handle is invalid or if the weight is not finite
     fn add_connection(&mut self, connection: Connection) -> Result<()>;
+
+    /// Remove the connection from `connection.from` to `connection.to`, regardless of its weight.
+    /// # Errors
+    /// Returns `Err` if an involved handle is invalid or if the neurons are not connected
+    fn remove_connection(&mut self, connection: Connection) -> Result<()>;
+
+    /// Remove a neuron together with all connections from and to it.
+    /// The handles of all other neurons stay valid, while the removed handle
+    /// may be reused by neurons pushed afterwards.
+    /// # Errors
+    /// Returns `Err` if the handle is invalid
+    fn remove_neuron(&mut self, neuron: Handle) -> Result<()>;
+
+    /// The weight of the connection from the neuron `from` to the neuron `to`,
+    /// or `None` if the neurons are not connected.
+    /// # Errors
+    /// Returns `Err` if an involved handle is invalid
+    fn weight_between(&self, from: Handle, to: Handle) -> Result<Option<Weight>>;
+
+    /// The handles of all neurons in the network
+    fn neurons(&self) -> Vec<Handle>;
+
+    /// The number of neurons in the network
+    fn neuron_count(&self) -> usize;
+
+    /// All connections between the neurons of the network, each of them exactly once
+    fn connections(&self) -> Vec<Connection>;
+
+    /// Renders the network as a graph in the [Graphviz] DOT language
+    ///
+    /// [Graphviz]: https://www.graphviz.org/
+    fn to_dot(&self) -> String {
+        dot::neural_network_to_dot(&self.neurons(), &self.connections(), &[], &[])
+    }
 }
 
 /// Supertrait used to make sure that all implementors
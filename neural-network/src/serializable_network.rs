@@ -0,0 +1,212 @@
+//! Persistence of the topology of neural networks
+
+use crate::spiking_neural_network::DefaultSpikingNeuralNetwork;
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// The topology of a [`NeuralNetwork`] in a form that can be persisted,
+/// e.g. in order to reload a trained organism later on.
+///
+/// All spiking neurons share the same parameters, so only their handles
+/// and the constant potentials of bias neurons are stored.
+/// The membrane potentials are not part of the topology,
+/// which is why a rebuilt network starts out in its resting state.
+///
+/// [`NeuralNetwork`]: ./trait.NeuralNetwork.html
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SerializableNetwork {
+    neurons: Vec<Handle>,
+    connections: Vec<Connection>,
+    #[serde(default)]
+    bias_potentials: Vec<(Handle, MembranePotential)>,
+}
+
+impl SerializableNetwork {
+    /// The number of neurons in the network
+    pub fn neuron_count(&self) -> usize {
+        self.neurons.len()
+    }
+
+    /// Builds a spiking neural network with the stored neurons and connections.
+    /// The neurons keep their handles.
+    /// # Errors
+    /// Returns `Err` if the stored topology is invalid, e.g. because a connection
+    /// references a neuron that doesn't exist or a handle is implausibly large
+    /// for the number of neurons, see [`MAX_HANDLES_PER_NEURON`].
+    ///
+    /// [`MAX_HANDLES_PER_NEURON`]: ./spiking_neural_network/constant.MAX_HANDLES_PER_NEURON.html
+    pub fn build(&self) -> Result<Box<dyn NeuralNetwork>> {
+        let mut neural_network = DefaultSpikingNeuralNetwork::with_neuron_handles(&self.neurons)?;
+
+        for &(handle, constant_potential) in &self.bias_potentials {
+            neural_network.set_bias_potential(handle, constant_potential)?;
+        }
+
+        for connection in &self.connections {
+            neural_network.add_connection(connection.clone())?;
+        }
+
+        Ok(box neural_network)
+    }
+}
+
+impl From<&dyn NeuralNetwork> for SerializableNetwork {
+    fn from(neural_network: &dyn NeuralNetwork) -> Self {
+        let neurons = neural_network.neurons();
+        let bias_potentials = neurons
+            .iter()
+            .filter_map(|&handle| {
+                neural_network
+                    .bias_potential_of_neuron(handle)
+                    .expect("Handle returned by the neural network is invalid")
+                    .map(|constant_potential| (handle, constant_potential))
+            })
+            .collect();
+
+        Self {
+            neurons,
+            connections: neural_network.connections(),
+            bias_potentials,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maplit::hashmap;
+
+    fn neural_network() -> DefaultSpikingNeuralNetwork {
+        let mut neural_network = DefaultSpikingNeuralNetwork::new();
+        let sensor_handle = neural_network.push_neuron();
+        let first_handle = neural_network.push_neuron();
+        let second_handle = neural_network.push_neuron();
+
+        for &(from, to, weight) in &[
+            (sensor_handle, first_handle, 1.0),
+            (sensor_handle, second_handle, 0.5),
+            (first_handle, second_handle, 0.75),
+        ] {
+            neural_network
+                .add_connection(Connection { from, to, weight })
+                .unwrap();
+        }
+
+        neural_network
+    }
+
+    #[test]
+    fn rebuilt_network_steps_like_original() {
+        let mut neural_network: Box<dyn NeuralNetwork> = box neural_network();
+
+        let json = serde_json::to_string(&SerializableNetwork::from(&*neural_network)).unwrap();
+        let serializable_network: SerializableNetwork = serde_json::from_str(&json).unwrap();
+        let mut rebuilt_neural_network = serializable_network.build().unwrap();
+
+        assert_eq!(neural_network.neurons(), rebuilt_neural_network.neurons());
+        assert_eq!(
+            neural_network.connections(),
+            rebuilt_neural_network.connections()
+        );
+
+        let inputs = hashmap! {
+            Handle(0) => 1.0,
+        };
+
+        for _ in 0..10 {
+            neural_network.step(0.1, &inputs);
+            rebuilt_neural_network.step(0.1, &inputs);
+
+            for handle in neural_network.neurons() {
+                assert_eq!(
+                    neural_network.normalized_potential_of_neuron(handle),
+                    rebuilt_neural_network.normalized_potential_of_neuron(handle)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rebuilt_network_keeps_handles_and_their_order() {
+        let mut neural_network = neural_network();
+        neural_network.remove_neuron(Handle(0)).unwrap();
+        let reused_handle = neural_network.push_neuron();
+        neural_network.push_neuron();
+        neural_network.remove_neuron(Handle(1)).unwrap();
+        let neural_network: &dyn NeuralNetwork = &neural_network;
+
+        let rebuilt_neural_network = SerializableNetwork::from(neural_network).build().unwrap();
+
+        assert_eq!(
+            vec![Handle(2), reused_handle, Handle(3)],
+            neural_network.neurons()
+        );
+        assert_eq!(neural_network.neurons(), rebuilt_neural_network.neurons());
+    }
+
+    #[test]
+    fn build_returns_err_for_connection_to_missing_neuron() {
+        let serializable_network = SerializableNetwork {
+            neurons: vec![Handle(0)],
+            connections: vec![Connection {
+                from: Handle(0),
+                to: Handle(1),
+                weight: 1.0,
+            }],
+            bias_potentials: Vec::new(),
+        };
+
+        assert!(serializable_network.build().is_err());
+    }
+
+    #[test]
+    fn build_returns_err_for_duplicate_neurons() {
+        let serializable_network = SerializableNetwork {
+            neurons: vec![Handle(0), Handle(0)],
+            connections: Vec::new(),
+            bias_potentials: Vec::new(),
+        };
+
+        assert!(serializable_network.build().is_err());
+    }
+
+    #[test]
+    fn build_returns_err_for_largest_possible_handle() {
+        let serializable_network = SerializableNetwork {
+            neurons: vec![Handle(std::usize::MAX)],
+            connections: Vec::new(),
+            bias_potentials: Vec::new(),
+        };
+
+        assert!(serializable_network.build().is_err());
+    }
+
+    #[test]
+    fn build_returns_err_for_handles_far_beyond_neuron_count() {
+        let serializable_network = SerializableNetwork {
+            neurons: vec![Handle(0), Handle(1_000_000_000)],
+            connections: Vec::new(),
+            bias_potentials: Vec::new(),
+        };
+
+        assert!(serializable_network.build().is_err());
+    }
+
+    #[test]
+    fn rebuilt_network_keeps_bias_neurons() {
+        let mut neural_network = neural_network();
+        let bias_handle = neural_network.push_bias_neuron(-50.0);
+        let neural_network: &dyn NeuralNetwork = &neural_network;
+
+        let rebuilt_neural_network = SerializableNetwork::from(neural_network).build().unwrap();
+
+        assert_eq!(
+            Ok(Some(-50.0)),
+            rebuilt_neural_network.bias_potential_of_neuron(bias_handle)
+        );
+        assert_eq!(
+            Ok(None),
+            rebuilt_neural_network.bias_potential_of_neuron(Handle(0))
+        );
+    }
+}
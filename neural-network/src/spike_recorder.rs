@@ -0,0 +1,157 @@
+//! Recording of the spikes emitted by a [`NeuralNetwork`] for later analysis
+//!
+//! [`NeuralNetwork`]: ../trait.NeuralNetwork.html
+
+use crate::{Handle, MembranePotential, Milliseconds, NeuralNetwork};
+use std::collections::HashMap;
+
+/// Records which neurons of a [`NeuralNetwork`] fired on which step.
+/// A neuron is considered to be firing if it currently has a membrane potential,
+/// i.e. if [`NeuralNetwork::normalized_potential_of_neuron`] returns `Some`.
+///
+/// [`NeuralNetwork`]: ../trait.NeuralNetwork.html
+/// [`NeuralNetwork::normalized_potential_of_neuron`]: ../trait.NeuralNetwork.html#tymethod.normalized_potential_of_neuron
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SpikeRecorder {
+    spike_trains: Vec<Vec<Handle>>,
+}
+
+impl SpikeRecorder {
+    /// Creates a recorder without any recorded steps
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Steps the `neural_network` and records the neurons firing afterwards
+    pub fn step(
+        &mut self,
+        neural_network: &mut dyn NeuralNetwork,
+        time_since_last_step: Milliseconds,
+        external_inputs: &HashMap<Handle, MembranePotential>,
+    ) {
+        neural_network.step(time_since_last_step, external_inputs);
+        self.record(&*neural_network);
+    }
+
+    /// Records the neurons of the `neural_network` that are currently firing as a new step
+    pub fn record(&mut self, neural_network: &dyn NeuralNetwork) {
+        let firing_neurons = neural_network
+            .neurons()
+            .into_iter()
+            .filter(|&neuron| {
+                neural_network
+                    .normalized_potential_of_neuron(neuron)
+                    .expect("Neural network returned an invalid neuron handle")
+                    .is_some()
+            })
+            .collect();
+
+        self.spike_trains.push(firing_neurons);
+    }
+
+    /// The neurons that fired, indexed by the step they were recorded in
+    pub fn spike_trains(&self) -> &[Vec<Handle>] {
+        &self.spike_trains
+    }
+
+    /// The fraction of recorded steps in which `neuron` fired.
+    /// Returns 0 if no steps have been recorded yet.
+    pub fn firing_rate(&self, neuron: Handle) -> f64 {
+        if self.spike_trains.is_empty() {
+            return 0.0;
+        }
+
+        let spike_count = self
+            .spike_trains
+            .iter()
+            .filter(|firing_neurons| firing_neurons.contains(&neuron))
+            .count();
+
+        spike_count as f64 / self.spike_trains.len() as f64
+    }
+
+    /// The firing rates of all neurons that fired at least once
+    pub fn firing_rates(&self) -> HashMap<Handle, f64> {
+        self.spike_trains
+            .iter()
+            .flatten()
+            .map(|&neuron| (neuron, self.firing_rate(neuron)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NeuralNetworkMock;
+    use maplit::hashmap;
+    use nearly_eq::assert_nearly_eq;
+
+    const NEURONS: [Handle; 3] = [Handle(0), Handle(1), Handle(2)];
+
+    fn neural_network_firing(firing_neurons: &[Handle]) -> NeuralNetworkMock {
+        let mut neural_network = NeuralNetworkMock::new();
+        neural_network.expect_neurons().returns(NEURONS.to_vec());
+
+        for &neuron in &NEURONS {
+            let potential = if firing_neurons.contains(&neuron) {
+                Some(1.0)
+            } else {
+                None
+            };
+            neural_network
+                .expect_normalized_potential_of_neuron(|arg| arg.partial_eq(neuron))
+                .returns(Ok(potential));
+        }
+
+        neural_network
+    }
+
+    #[test]
+    fn records_spike_trains_and_firing_rates() {
+        let mut spike_recorder = SpikeRecorder::new();
+
+        spike_recorder.record(&neural_network_firing(&[Handle(0)]));
+        spike_recorder.record(&neural_network_firing(&[Handle(0), Handle(2)]));
+        spike_recorder.record(&neural_network_firing(&[]));
+        spike_recorder.record(&neural_network_firing(&[Handle(0)]));
+
+        assert_eq!(
+            &[
+                vec![Handle(0)],
+                vec![Handle(0), Handle(2)],
+                vec![],
+                vec![Handle(0)],
+            ],
+            spike_recorder.spike_trains()
+        );
+        assert_nearly_eq!(0.75, spike_recorder.firing_rate(Handle(0)));
+        assert_nearly_eq!(0.0, spike_recorder.firing_rate(Handle(1)));
+        assert_nearly_eq!(0.25, spike_recorder.firing_rate(Handle(2)));
+        assert_eq!(
+            hashmap! {
+                Handle(0) => 0.75,
+                Handle(2) => 0.25,
+            },
+            spike_recorder.firing_rates()
+        );
+    }
+
+    #[test]
+    fn step_steps_neural_network_before_recording() {
+        let mut neural_network = neural_network_firing(&[Handle(1)]);
+        neural_network
+            .expect_step(|arg| arg.partial_eq(10.0), |arg| arg.any())
+            .times(1);
+        let mut spike_recorder = SpikeRecorder::new();
+
+        spike_recorder.step(&mut neural_network, 10.0, &HashMap::new());
+
+        assert_eq!(&[vec![Handle(1)]], spike_recorder.spike_trains());
+    }
+
+    #[test]
+    fn firing_rate_is_zero_without_recorded_steps() {
+        assert_nearly_eq!(0.0, SpikeRecorder::new().firing_rate(Handle(0)));
+    }
+}
@@ -14,6 +14,12 @@ use std::collections::HashMap;
 /// [SpikingNeuronImpl]: ./struct.SpikingNeuronImpl.html
 pub type DefaultSpikingNeuralNetwork = SpikingNeuralNetwork<SpikingNeuronImpl>;
 
+/// Every handle must be less than this many times the number of neurons
+/// when a network is created with given handles.
+/// Every handle up to the largest one needs a slot, so this bounds the memory
+/// that malformed handles, e.g. deserialized ones, can claim.
+pub const MAX_HANDLES_PER_NEURON: usize = 2;
+
 /// A spiking neural network
 #[derive(Debug, Default, Clone)]
 pub struct SpikingNeuralNetwork<N>
@@ -23,6 +29,7 @@ where
     neurons: Slab<N>,
     neuron_handles: Vec<Handle>,
     incoming_connections: HashMap<Handle, Vec<(Handle, Weight)>>,
+    bias_potentials: HashMap<Handle, MembranePotential>,
 }
 
 impl<N> SpikingNeuralNetwork<N>
@@ -36,15 +43,86 @@ where
         Self::default()
     }
 
-    /// Returns the last calculated state of the neuron referenced by `handle`
+    /// Creates a network of unconnected neurons that have exactly the given handles
+    /// and are updated in the given order.
+    /// # Errors
+    /// Returns `Err` if a handle occurs more than once or if a handle is not less than
+    /// [`MAX_HANDLES_PER_NEURON`] times the number of neurons.
+    ///
+    /// [`MAX_HANDLES_PER_NEURON`]: ./constant.MAX_HANDLES_PER_NEURON.html
+    pub(crate) fn with_neuron_handles(neuron_handles: &[Handle]) -> Result<Self> {
+        let max_handle_count = neuron_handles
+            .len()
+            .checked_mul(MAX_HANDLES_PER_NEURON)
+            .ok_or(())?;
+
+        if neuron_handles
+            .iter()
+            .any(|handle| handle.0 >= max_handle_count)
+        {
+            return Err(());
+        }
+
+        let handle_count = neuron_handles
+            .iter()
+            .map(|handle| handle.0 + 1)
+            .max()
+            .unwrap_or_default();
+
+        let mut neural_network = Self::new();
+
+        for _ in 0..handle_count {
+            neural_network.push_neuron();
+        }
+
+        for handle in (0..handle_count).map(Handle) {
+            if !neuron_handles.contains(&handle) {
+                neural_network.remove_neuron(handle)?;
+            }
+        }
+
+        if neural_network.neuron_count() != neuron_handles.len() {
+            return Err(());
+        }
+
+        neural_network.neuron_handles = neuron_handles.to_vec();
+
+        Ok(neural_network)
+    }
+
+    /// Returns the last calculated state of the neuron referenced by `handle`.
+    /// Bias neurons always return their constant potential.
     pub fn membrane_potential_of_neuron(
         &self,
         neuron: Handle,
     ) -> Result<Option<MembranePotential>> {
-        self.neurons
+        let membrane_potential = self
+            .neurons
             .get(neuron.0)
             .ok_or(())
-            .map(SpikingNeuron::membrane_potential)
+            .map(SpikingNeuron::membrane_potential)?;
+
+        Ok(self
+            .bias_potentials
+            .get(&neuron)
+            .cloned()
+            .or(membrane_potential))
+    }
+
+    /// Turns an existing neuron into a bias neuron with the given `constant_potential`
+    /// # Errors
+    /// Returns `Err` if the handle is invalid
+    pub(crate) fn set_bias_potential(
+        &mut self,
+        neuron: Handle,
+        constant_potential: MembranePotential,
+    ) -> Result<()> {
+        if !self.neurons.contains(neuron.0) {
+            return Err(());
+        }
+
+        self.bias_potentials.insert(neuron, constant_potential);
+        Ok(())
     }
 }
 
@@ -60,10 +138,11 @@ where
     }
 
     /// A normalized value between 0 and 1 representing the current membrane potential
-    fn normalized_potential_of_neuron(&self, neuron: Handle) -> Result<Option<f64>> {
-        let neuron = self.neurons.get(neuron.0).ok_or(())?;
+    fn normalized_potential_of_neuron(&self, neuron_handle: Handle) -> Result<Option<f64>> {
+        let membrane_potential = self.membrane_potential_of_neuron(neuron_handle)?;
+        let neuron = self.neurons.get(neuron_handle.0).ok_or(())?;
 
-        let normalized_value = neuron.membrane_potential().map(|membrane_potential| {
+        let normalized_value = membrane_potential.map(|membrane_potential| {
             // The membrane potential is converted from [threshold; action_potential] into [0, 1]
             let threshold = neuron.threshold();
             (membrane_potential - threshold) / (neuron.action_potential() - threshold)
@@ -79,14 +158,43 @@ where
         handle
     }
 
+    /// Add a new unconnected bias neuron to the network, which passes `constant_potential`
+    /// on to its connected neurons on every step, regardless of its own inputs.
+    /// Bias neurons ignore external inputs as well.
+    fn push_bias_neuron(&mut self, constant_potential: MembranePotential) -> Handle {
+        let handle = self.push_neuron();
+        self.bias_potentials.insert(handle, constant_potential);
+        handle
+    }
+
+    /// The constant potential of a bias neuron, or `None` if the neuron is a regular one.
+    /// # Errors
+    /// Returns `Err` if the handle is invalid
+    fn bias_potential_of_neuron(&self, neuron: Handle) -> Result<Option<MembranePotential>> {
+        if !self.neurons.contains(neuron.0) {
+            return Err(());
+        }
+
+        Ok(self.bias_potentials.get(&neuron).cloned())
+    }
+
+    /// Returns all neurons to their resting state
+    /// while keeping the neurons and their connections
+    fn reset(&mut self) {
+        for (_, neuron) in self.neurons.iter_mut() {
+            *neuron = N::default();
+        }
+    }
+
     /// Add a new connection between two neurons.
     /// # Errors
-    /// Returns `Err` if an involved handle is invalid
+    /// Returns `Err` if an involved handle is invalid or if the weight is not finite
     fn add_connection(&mut self, connection: Connection) -> Result<()> {
         let is_origin_same_as_destination = connection.from == connection.to;
         let valid_origin = self.neurons.contains(connection.from.0);
         let valid_destination = self.neurons.contains(connection.to.0);
-        if is_origin_same_as_destination || !valid_origin || !valid_destination {
+        let valid_weight = connection.weight.is_finite();
+        if is_origin_same_as_destination || !valid_origin || !valid_destination || !valid_weight {
             Err(())
         } else {
             self.incoming_connections
@@ -96,6 +204,93 @@ where
             Ok(())
         }
     }
+
+    /// Remove the connection from `connection.from` to `connection.to`, regardless of its weight.
+    /// # Errors
+    /// Returns `Err` if an involved handle is invalid or if the neurons are not connected
+    fn remove_connection(&mut self, connection: Connection) -> Result<()> {
+        let incoming_connections = self
+            .incoming_connections
+            .get_mut(&connection.to)
+            .ok_or(())?;
+        let index = incoming_connections
+            .iter()
+            .position(|&(origin, _)| origin == connection.from)
+            .ok_or(())?;
+
+        incoming_connections.remove(index);
+        Ok(())
+    }
+
+    /// Remove a neuron together with all connections from and to it.
+    /// The handles of all other neurons stay valid, while the removed handle
+    /// may be reused by neurons pushed afterwards.
+    /// # Errors
+    /// Returns `Err` if the handle is invalid
+    fn remove_neuron(&mut self, neuron: Handle) -> Result<()> {
+        if !self.neurons.contains(neuron.0) {
+            return Err(());
+        }
+
+        self.neurons.remove(neuron.0);
+        self.neuron_handles.retain(|&handle| handle != neuron);
+        self.incoming_connections.remove(&neuron);
+        self.bias_potentials.remove(&neuron);
+
+        for incoming_connections in self.incoming_connections.values_mut() {
+            incoming_connections.retain(|&(origin, _)| origin != neuron);
+        }
+
+        Ok(())
+    }
+
+    /// The weight of the connection from the neuron `from` to the neuron `to`,
+    /// or `None` if the neurons are not connected.
+    /// # Errors
+    /// Returns `Err` if an involved handle is invalid
+    fn weight_between(&self, from: Handle, to: Handle) -> Result<Option<Weight>> {
+        if !self.neurons.contains(from.0) || !self.neurons.contains(to.0) {
+            return Err(());
+        }
+
+        let weight = self
+            .incoming_connections
+            .get(&to)
+            .and_then(|incoming_connections| {
+                incoming_connections
+                    .iter()
+                    .find(|&&(origin, _)| origin == from)
+            })
+            .map(|&(_, weight)| weight);
+
+        Ok(weight)
+    }
+
+    /// The handles of all neurons in the network, in the order they were pushed
+    fn neurons(&self) -> Vec<Handle> {
+        self.neuron_handles.clone()
+    }
+
+    /// The number of neurons in the network
+    fn neuron_count(&self) -> usize {
+        self.neurons.len()
+    }
+
+    /// All connections between the neurons of the network,
+    /// sorted by their destination and origin
+    fn connections(&self) -> Vec<Connection> {
+        let mut connections: Vec<_> = self
+            .incoming_connections
+            .iter()
+            .flat_map(|(&to, incoming_connections)| {
+                incoming_connections
+                    .iter()
+                    .map(move |&(from, weight)| Connection { from, to, weight })
+            })
+            .collect();
+        connections.sort_by_key(|connection| (connection.to.0, connection.from.0));
+        connections
+    }
 }
 
 impl<N> SpikingNeuralNetwork<N>
@@ -137,6 +332,13 @@ where
         external_inputs: &HashMap<Handle, f64>,
     ) {
         for (&handle_of_neuron_receiving_input, &normalized_input) in external_inputs {
+            if self
+                .bias_potentials
+                .contains_key(&handle_of_neuron_receiving_input)
+            {
+                continue;
+            }
+
             let mut inputs =
                 self.cached_incoming_connection_inputs(handle_of_neuron_receiving_input);
 
@@ -160,10 +362,13 @@ where
         time_since_last_step: Milliseconds,
         external_inputs: &HashMap<Handle, MembranePotential>,
     ) {
+        let bias_potentials = &self.bias_potentials;
+
         for &neuron_handle in self
             .neuron_handles
             .iter()
             .filter(|handle| !external_inputs.contains_key(handle))
+            .filter(|handle| !bias_potentials.contains_key(handle))
         {
             let inputs = self.cached_incoming_connection_inputs(neuron_handle);
             let neuron = self.neurons.get_mut(neuron_handle.0).ok_or(()).unwrap();
@@ -443,6 +648,66 @@ mod tests {
         assert!(neuron_membrane_potential.is_some());
     }
 
+    #[test]
+    fn returns_err_when_adding_connection_with_non_finite_weight() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let sensor_handle = neural_network.push_neuron();
+        let neuron_handle = neural_network.push_neuron();
+
+        for &weight in &[std::f64::NAN, std::f64::INFINITY, std::f64::NEG_INFINITY] {
+            let connection = Connection {
+                from: sensor_handle,
+                to: neuron_handle,
+                weight,
+            };
+            assert!(neural_network.add_connection(connection).is_err());
+        }
+        assert!(neural_network.connections().is_empty());
+    }
+
+    #[test]
+    fn inhibitory_connection_prevents_firing() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let excitatory_sensor_handle = neural_network.push_neuron();
+        let inhibitory_sensor_handle = neural_network.push_neuron();
+        let neuron_handle = neural_network.push_neuron();
+        neural_network
+            .add_connection(Connection {
+                from: excitatory_sensor_handle,
+                to: neuron_handle,
+                weight: 1.0,
+            })
+            .unwrap();
+        neural_network
+            .add_connection(Connection {
+                from: inhibitory_sensor_handle,
+                to: neuron_handle,
+                weight: -1.0,
+            })
+            .unwrap();
+
+        let elapsed_time = 1.0;
+        let inputs = hashmap! {
+            excitatory_sensor_handle => NORMALIZED_THRESHOLD_POTENTIAL,
+            inhibitory_sensor_handle => NORMALIZED_THRESHOLD_POTENTIAL,
+        };
+        neural_network.step(elapsed_time, &inputs);
+
+        let excitatory_sensor_membrane_potential = neural_network
+            .membrane_potential_of_neuron(excitatory_sensor_handle)
+            .unwrap();
+        let inhibitory_sensor_membrane_potential = neural_network
+            .membrane_potential_of_neuron(inhibitory_sensor_handle)
+            .unwrap();
+        let neuron_membrane_potential = neural_network
+            .membrane_potential_of_neuron(neuron_handle)
+            .unwrap();
+
+        assert!(excitatory_sensor_membrane_potential.is_some());
+        assert!(inhibitory_sensor_membrane_potential.is_some());
+        assert!(neuron_membrane_potential.is_none());
+    }
+
     #[test]
     fn spike_ends_after_many_small_time_steps() {
         let mut neural_network = DefaultSpikingNeuralNetwork::default();
@@ -508,4 +773,311 @@ mod tests {
         let neuron = SpikingNeuronMock::new();
         convert_input_to_membrane_potential(1.1, &neuron);
     }
+
+    #[test]
+    fn dot_contains_each_neuron_and_connection() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let first_handle = neural_network.push_neuron();
+        let second_handle = neural_network.push_neuron();
+        let third_handle = neural_network.push_neuron();
+        let connections = vec![
+            Connection {
+                from: first_handle,
+                to: second_handle,
+                weight: 0.5,
+            },
+            Connection {
+                from: first_handle,
+                to: third_handle,
+                weight: 0.25,
+            },
+        ];
+        for connection in connections.clone() {
+            neural_network.add_connection(connection).unwrap();
+        }
+
+        assert_eq!(
+            vec![first_handle, second_handle, third_handle],
+            neural_network.neurons()
+        );
+        assert_eq!(connections, neural_network.connections());
+
+        let dot = neural_network.to_dot();
+
+        for handle in &[first_handle, second_handle, third_handle] {
+            assert!(dot.contains(&format!("neuron_{} [shape = circle];", handle.0)));
+        }
+        assert!(dot.contains(&format!(
+            "neuron_{} -> neuron_{} [label = \"0.5\"];",
+            first_handle.0, second_handle.0
+        )));
+        assert!(dot.contains(&format!(
+            "neuron_{} -> neuron_{} [label = \"0.25\"];",
+            first_handle.0, third_handle.0
+        )));
+    }
+
+    #[test]
+    fn weight_between_returns_weight_of_existing_connection() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let from = neural_network.push_neuron();
+        let to = neural_network.push_neuron();
+        neural_network
+            .add_connection(Connection {
+                from,
+                to,
+                weight: 0.75,
+            })
+            .unwrap();
+
+        assert_eq!(Ok(Some(0.75)), neural_network.weight_between(from, to));
+    }
+
+    #[test]
+    fn weight_between_returns_none_for_missing_connection() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let from = neural_network.push_neuron();
+        let to = neural_network.push_neuron();
+        neural_network
+            .add_connection(Connection {
+                from,
+                to,
+                weight: 0.75,
+            })
+            .unwrap();
+
+        assert_eq!(Ok(None), neural_network.weight_between(to, from));
+    }
+
+    #[test]
+    fn weight_between_returns_err_for_invalid_handle() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let from = neural_network.push_neuron();
+        let invalid_handle = Handle(from.0 + 1);
+
+        assert!(neural_network.weight_between(from, invalid_handle).is_err());
+        assert!(neural_network.weight_between(invalid_handle, from).is_err());
+    }
+
+    #[test]
+    fn reset_returns_fired_neuron_to_resting_state() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let sensor_handle = neural_network.push_neuron();
+        let neuron_handle = neural_network.push_neuron();
+        let connection = Connection {
+            from: sensor_handle,
+            to: neuron_handle,
+            weight: 1.0,
+        };
+        neural_network.add_connection(connection.clone()).unwrap();
+
+        let inputs = hashmap! {
+            sensor_handle => 1.0
+        };
+        neural_network.step(1.0, &inputs);
+        assert!(neural_network
+            .normalized_potential_of_neuron(sensor_handle)
+            .unwrap()
+            .is_some());
+
+        neural_network.reset();
+
+        assert_eq!(
+            Ok(None),
+            neural_network.normalized_potential_of_neuron(sensor_handle)
+        );
+        assert_eq!(
+            Ok(None),
+            neural_network.normalized_potential_of_neuron(neuron_handle)
+        );
+        assert_eq!(vec![connection], neural_network.connections());
+    }
+
+    #[test]
+    fn remove_connection_disconnects_neurons() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let from = neural_network.push_neuron();
+        let to = neural_network.push_neuron();
+        let connection = Connection {
+            from,
+            to,
+            weight: 0.75,
+        };
+        neural_network.add_connection(connection.clone()).unwrap();
+
+        assert!(neural_network.remove_connection(connection).is_ok());
+        assert_eq!(Ok(None), neural_network.weight_between(from, to));
+        assert!(neural_network.connections().is_empty());
+    }
+
+    #[test]
+    fn remove_connection_returns_err_for_nonexistent_connection() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let from = neural_network.push_neuron();
+        let to = neural_network.push_neuron();
+        neural_network
+            .add_connection(Connection {
+                from,
+                to,
+                weight: 0.75,
+            })
+            .unwrap();
+
+        let reversed_connection = Connection {
+            from: to,
+            to: from,
+            weight: 0.75,
+        };
+        assert!(neural_network
+            .remove_connection(reversed_connection)
+            .is_err());
+    }
+
+    #[test]
+    fn remove_neuron_removes_its_connections_and_keeps_other_handles() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let first_handle = neural_network.push_neuron();
+        let second_handle = neural_network.push_neuron();
+        let third_handle = neural_network.push_neuron();
+        let remaining_connection = Connection {
+            from: first_handle,
+            to: third_handle,
+            weight: 0.25,
+        };
+        for connection in vec![
+            Connection {
+                from: first_handle,
+                to: second_handle,
+                weight: 0.5,
+            },
+            Connection {
+                from: second_handle,
+                to: third_handle,
+                weight: 1.0,
+            },
+            remaining_connection.clone(),
+        ] {
+            neural_network.add_connection(connection).unwrap();
+        }
+
+        assert!(neural_network.remove_neuron(second_handle).is_ok());
+
+        assert_eq!(vec![first_handle, third_handle], neural_network.neurons());
+        assert_eq!(vec![remaining_connection], neural_network.connections());
+        assert!(neural_network
+            .membrane_potential_of_neuron(second_handle)
+            .is_err());
+        assert!(neural_network
+            .membrane_potential_of_neuron(third_handle)
+            .is_ok());
+    }
+
+    #[test]
+    fn remove_neuron_returns_err_for_invalid_handle() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let handle = neural_network.push_neuron();
+
+        assert!(neural_network.remove_neuron(Handle(handle.0 + 1)).is_err());
+    }
+
+    #[test]
+    fn topology_can_be_read_back() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let handles: Vec<_> = (0..4).map(|_| neural_network.push_neuron()).collect();
+        let connections = vec![
+            Connection {
+                from: handles[2],
+                to: handles[0],
+                weight: 0.5,
+            },
+            Connection {
+                from: handles[0],
+                to: handles[1],
+                weight: 0.25,
+            },
+            Connection {
+                from: handles[3],
+                to: handles[1],
+                weight: 0.125,
+            },
+            Connection {
+                from: handles[1],
+                to: handles[3],
+                weight: 1.0,
+            },
+        ];
+        for connection in connections.clone() {
+            neural_network.add_connection(connection).unwrap();
+        }
+
+        assert_eq!(4, neural_network.neuron_count());
+        assert_eq!(handles, neural_network.neurons());
+        assert_eq!(connections, neural_network.connections());
+    }
+
+    #[test]
+    fn neuron_count_does_not_include_removed_neurons() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let handle = neural_network.push_neuron();
+        neural_network.push_neuron();
+
+        neural_network.remove_neuron(handle).unwrap();
+
+        assert_eq!(1, neural_network.neuron_count());
+    }
+
+    #[test]
+    fn bias_neuron_keeps_output_firing_without_external_inputs() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let bias_handle = neural_network.push_bias_neuron(constant::THRESHOLD_POTENTIAL);
+        let output_handle = neural_network.push_neuron();
+        neural_network
+            .add_connection(Connection {
+                from: bias_handle,
+                to: output_handle,
+                weight: 1.0,
+            })
+            .unwrap();
+
+        let elapsed_time = 1.0;
+        let no_inputs = HashMap::new();
+        let mut steps_with_output = 0;
+
+        for _ in 0..100 {
+            neural_network.step(elapsed_time, &no_inputs);
+
+            assert_eq!(
+                Ok(Some(constant::THRESHOLD_POTENTIAL)),
+                neural_network.membrane_potential_of_neuron(bias_handle)
+            );
+
+            let output = neural_network
+                .normalized_potential_of_neuron(output_handle)
+                .unwrap();
+            if output.is_some() {
+                steps_with_output += 1;
+            }
+        }
+
+        assert!(steps_with_output > 1);
+    }
+
+    #[test]
+    fn bias_potential_is_only_reported_for_bias_neurons() {
+        let mut neural_network = DefaultSpikingNeuralNetwork::default();
+        let bias_handle = neural_network.push_bias_neuron(constant::THRESHOLD_POTENTIAL);
+        let neuron_handle = neural_network.push_neuron();
+
+        assert_eq!(
+            Ok(Some(constant::THRESHOLD_POTENTIAL)),
+            neural_network.bias_potential_of_neuron(bias_handle)
+        );
+        assert_eq!(
+            Ok(None),
+            neural_network.bias_potential_of_neuron(neuron_handle)
+        );
+        assert!(neural_network
+            .bias_potential_of_neuron(Handle(neuron_handle.0 + 1))
+            .is_err());
+    }
 }
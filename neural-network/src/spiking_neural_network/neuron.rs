@@ -211,6 +211,8 @@ fn sum_inputs(inputs: &[(MembranePotential, Weight)]) -> MembranePotential {
         .map(|(membrane_potential, weight)| {
             let input_shifted_into_positive_range =
                 membrane_potential - constant::RESTING_POTENTIAL;
+            // Inputs of inhibitory connections have a negative weight
+            // and therefore lower the membrane potential
             input_shifted_into_positive_range * weight
         })
         .sum()
@@ -351,6 +353,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inhibitory_input_lowers_membrane_potential() {
+        let inputs = [(constant::THRESHOLD_POTENTIAL, -1.0)];
+        assert!(sum_inputs(&inputs) < 0.0);
+    }
+
     #[test]
     fn spike_ends_after_many_small_time_steps() {
         let mut neuron = SpikingNeuronImpl::default();
@@ -0,0 +1,154 @@
+//! Contains the [`Flee`] behavior.
+
+use crate::nearest_object::find_nearest_object_of_kind;
+use myelin_engine::prelude::*;
+use myelin_object_data::{AdditionalObjectDescription, Kind};
+
+/// A behavior that moves away from the nearest object of a [`Kind`].
+/// Every step, a force of `max_force` is applied directly away
+/// from the nearest such object within `flight_radius`.
+/// Objects further away are ignored.
+/// No force is applied while the object is exactly at the location of its pursuer.
+#[derive(Debug, Clone)]
+pub struct Flee {
+    pursuer_kind: Kind,
+    flight_radius: f64,
+    max_force: f64,
+}
+
+impl Flee {
+    /// Creates a behavior that flees from objects of `pursuer_kind`
+    /// as soon as they are at most `flight_radius` away.
+    ///
+    /// # Panics
+    /// Panics if `flight_radius` is not positive.
+    pub fn new(pursuer_kind: Kind, flight_radius: f64, max_force: f64) -> Self {
+        assert!(flight_radius > 0.0, "flight_radius must be positive");
+
+        Self {
+            pursuer_kind,
+            flight_radius,
+            max_force,
+        }
+    }
+}
+
+impl ObjectBehavior<AdditionalObjectDescription> for Flee {
+    fn step(
+        &mut self,
+        world_interactor: Box<dyn WorldInteractor<AdditionalObjectDescription> + '_>,
+    ) -> Option<Action<AdditionalObjectDescription>> {
+        let own_object = world_interactor.own_object();
+        let pursuer = find_nearest_object_of_kind(
+            &*world_interactor,
+            &own_object,
+            self.pursuer_kind,
+            self.flight_radius,
+        )?;
+
+        let offset = Vector::from(own_object.description.location - pursuer.description.location);
+        if offset.magnitude() == 0.0 {
+            return None;
+        }
+
+        Some(Action::ApplyForce(Force {
+            linear: offset.unit() * self.max_force,
+            torque: Torque(0.0),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nearest_object::test_utils::{mock_behavior, object_description_at_location};
+    use myelin_object_data::Object;
+    use nearly_eq::assert_nearly_eq;
+
+    const FLIGHT_RADIUS: f64 = 20.0;
+    const MAX_FORCE: f64 = 10.0;
+
+    #[test]
+    fn applies_force_away_from_pursuer_within_radius() {
+        let behavior = mock_behavior();
+        let mut world_interactor = WorldInteractorMock::new();
+        world_interactor.expect_own_object().returns(Object {
+            id: 0,
+            description: object_description_at_location(Kind::Plant, 100.0, 100.0),
+            behavior: behavior.as_ref(),
+        });
+        world_interactor
+            .expect_find_objects_in_area(|arg| {
+                arg.partial_eq(Aabb::try_new((80.0, 80.0), (120.0, 120.0)).unwrap())
+            })
+            .returns(vec![Object {
+                id: 1,
+                description: object_description_at_location(Kind::Organism, 88.0, 109.0),
+                behavior: behavior.as_ref(),
+            }]);
+
+        let mut flee = Flee::new(Kind::Organism, FLIGHT_RADIUS, MAX_FORCE);
+        let action = flee.step(box world_interactor);
+
+        match action {
+            Some(Action::ApplyForce(force)) => {
+                assert_nearly_eq!(8.0, force.linear.x);
+                assert_nearly_eq!(-6.0, force.linear.y);
+                assert_nearly_eq!(0.0, force.torque.0);
+            }
+            action => panic!("Expected Action::ApplyForce, got {:#?}", action),
+        }
+    }
+
+    #[test]
+    fn does_nothing_when_pursuer_is_outside_of_radius() {
+        let behavior = mock_behavior();
+        let mut world_interactor = WorldInteractorMock::new();
+        world_interactor.expect_own_object().returns(Object {
+            id: 0,
+            description: object_description_at_location(Kind::Plant, 100.0, 100.0),
+            behavior: behavior.as_ref(),
+        });
+        world_interactor
+            .expect_find_objects_in_area(|arg| arg.any())
+            .returns(vec![Object {
+                id: 1,
+                description: object_description_at_location(Kind::Organism, 118.0, 118.0),
+                behavior: behavior.as_ref(),
+            }]);
+
+        let mut flee = Flee::new(Kind::Organism, FLIGHT_RADIUS, MAX_FORCE);
+        let action = flee.step(box world_interactor);
+
+        assert!(action.is_none());
+    }
+
+    #[test]
+    fn does_nothing_when_at_location_of_pursuer() {
+        let behavior = mock_behavior();
+        let mut world_interactor = WorldInteractorMock::new();
+        world_interactor.expect_own_object().returns(Object {
+            id: 0,
+            description: object_description_at_location(Kind::Plant, 100.0, 100.0),
+            behavior: behavior.as_ref(),
+        });
+        world_interactor
+            .expect_find_objects_in_area(|arg| arg.any())
+            .returns(vec![Object {
+                id: 1,
+                description: object_description_at_location(Kind::Organism, 100.0, 100.0),
+                behavior: behavior.as_ref(),
+            }]);
+
+        let mut flee = Flee::new(Kind::Organism, FLIGHT_RADIUS, MAX_FORCE);
+        let action = flee.step(box world_interactor);
+
+        assert!(action.is_none());
+    }
+
+    #[should_panic]
+    #[test]
+    fn panics_on_non_positive_flight_radius() {
+        Flee::new(Kind::Organism, -1.0, MAX_FORCE);
+    }
+}
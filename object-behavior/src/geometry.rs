@@ -0,0 +1,78 @@
+//! Geometric helpers for worlds whose edges wrap around
+
+use myelin_engine::prelude::*;
+
+/// Calculates the shortest distance between two points in a toroidal world,
+/// i.e. a world where an object leaving `bounds` on one side reappears on the opposite side.
+/// Along each axis, the distance across the seam is used if it is shorter than the direct one.
+/// Points outside of `bounds` are treated as if they had wrapped around into it.
+pub fn toroidal_distance(first_point: Point, second_point: Point, bounds: Aabb) -> f64 {
+    let width = bounds.lower_right.x - bounds.upper_left.x;
+    let height = bounds.lower_right.y - bounds.upper_left.y;
+
+    let delta_x = wrapped_delta(first_point.x - second_point.x, width);
+    let delta_y = wrapped_delta(first_point.y - second_point.y, height);
+
+    delta_x.hypot(delta_y)
+}
+
+fn wrapped_delta(delta: f64, extent: f64) -> f64 {
+    let delta = delta.rem_euclid(extent);
+    delta.min(extent - delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nearly_eq::assert_nearly_eq;
+
+    fn bounds() -> Aabb {
+        Aabb::try_new((0.0, 0.0), (100.0, 50.0)).unwrap()
+    }
+
+    #[test]
+    fn distance_within_bounds_is_euclidean() {
+        let first_point = Point { x: 10.0, y: 10.0 };
+        let second_point = Point { x: 13.0, y: 14.0 };
+
+        assert_nearly_eq!(5.0, toroidal_distance(first_point, second_point, bounds()));
+    }
+
+    #[test]
+    fn distance_across_horizontal_seam_is_shorter_than_euclidean() {
+        let first_point = Point { x: 2.0, y: 20.0 };
+        let second_point = Point { x: 98.0, y: 20.0 };
+
+        let euclidean_distance = Vector::from(first_point - second_point).magnitude();
+
+        assert_nearly_eq!(96.0, euclidean_distance);
+        assert_nearly_eq!(4.0, toroidal_distance(first_point, second_point, bounds()));
+    }
+
+    #[test]
+    fn distance_across_both_seams_wraps_along_both_axes() {
+        let first_point = Point { x: 1.0, y: 1.0 };
+        let second_point = Point { x: 97.0, y: 47.0 };
+
+        assert_nearly_eq!(5.0, toroidal_distance(first_point, second_point, bounds()));
+    }
+
+    #[test]
+    fn distance_to_points_outside_of_bounds_wraps_them_into_bounds() {
+        let first_point = Point { x: 10.0, y: 10.0 };
+        let second_point = Point { x: 313.0, y: -86.0 };
+
+        assert_nearly_eq!(5.0, toroidal_distance(first_point, second_point, bounds()));
+    }
+
+    #[test]
+    fn distance_is_symmetric() {
+        let first_point = Point { x: 5.0, y: 45.0 };
+        let second_point = Point { x: 60.0, y: 3.0 };
+
+        assert_nearly_eq!(
+            toroidal_distance(first_point, second_point, bounds()),
+            toroidal_distance(second_point, first_point, bounds())
+        );
+    }
+}
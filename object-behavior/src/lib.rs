@@ -3,7 +3,6 @@
 
 #![feature(specialization)]
 #![feature(box_syntax)]
-#![feature(clamp)]
 #![warn(missing_docs, clippy::dbg_macro, clippy::unimplemented)]
 #![deny(
     rust_2018_idioms,
@@ -21,5 +20,12 @@
 mod static_behavior;
 pub use self::static_behavior::Static;
 
+mod nearest_object;
+
+pub mod flee;
+pub mod geometry;
+pub mod num;
 pub mod organism;
+pub mod scent_field;
+pub mod seek;
 pub mod stochastic_spreading;
@@ -0,0 +1,73 @@
+//! Lookup of the closest object of a certain kind
+
+use myelin_engine::prelude::*;
+use myelin_object_data::{AdditionalObjectDescription, Kind, Object};
+
+/// Finds the object of the given [`Kind`] that is closest to the own object
+/// and at most `radius` away from it. The own object is never returned.
+pub(crate) fn find_nearest_object_of_kind<'a>(
+    world_interactor: &'a dyn WorldInteractor<AdditionalObjectDescription>,
+    own_object: &Object<'_>,
+    kind: Kind,
+    radius: f64,
+) -> Option<Object<'a>> {
+    let own_location = own_object.description.location;
+    let area = Aabb::try_new(
+        (own_location.x - radius, own_location.y - radius),
+        (own_location.x + radius, own_location.y + radius),
+    )
+    .expect("Radius must be positive");
+
+    world_interactor
+        .find_objects_in_area(area)
+        .into_iter()
+        .filter(|object| object.id != own_object.id)
+        .filter(|object| object.description.associated_data.kind == kind)
+        .map(|object| {
+            let distance = distance_between(own_location, object.description.location);
+            (object, distance)
+        })
+        .filter(|&(_, distance)| distance <= radius)
+        .min_by(|(_, distance_one), (_, distance_two)| {
+            distance_one
+                .partial_cmp(distance_two)
+                .expect("Tried to compare distances with non-comparable values")
+        })
+        .map(|(object, _)| object)
+}
+
+fn distance_between(first_location: Point, second_location: Point) -> f64 {
+    Vector::from(first_location - second_location).magnitude()
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use myelin_engine::prelude::*;
+    use myelin_object_data::{AdditionalObjectDescription, Kind, ObjectDescription};
+
+    pub(crate) fn object_description_at_location(kind: Kind, x: f64, y: f64) -> ObjectDescription {
+        ObjectBuilder::default()
+            .shape(
+                PolygonBuilder::default()
+                    .vertex(-5.0, -5.0)
+                    .vertex(5.0, -5.0)
+                    .vertex(5.0, 5.0)
+                    .vertex(-5.0, 5.0)
+                    .build()
+                    .unwrap(),
+            )
+            .location(x, y)
+            .mobility(Mobility::Movable(Vector::default()))
+            .associated_data(AdditionalObjectDescription {
+                name: None,
+                kind,
+                height: 1.0,
+            })
+            .build()
+            .unwrap()
+    }
+
+    pub(crate) fn mock_behavior() -> Box<dyn ObjectBehavior<AdditionalObjectDescription>> {
+        box ObjectBehaviorMock::new()
+    }
+}
@@ -0,0 +1,51 @@
+//! Numeric helpers shared by the behaviors
+
+/// Restricts `value` to the range [`min`, `max`].
+/// Unlike `f64::clamp`, this does not propagate NaN but maps it to `min`,
+/// which makes the result safe to use as a neural network input.
+///
+/// # Panics
+/// Panics if `min` is greater than `max` or if either of them is NaN.
+pub fn clamp(value: f64, min: f64, max: f64) -> f64 {
+    assert!(min <= max, "min must not be greater than max");
+
+    if value.is_nan() || value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nearly_eq::assert_nearly_eq;
+
+    #[test]
+    fn value_below_min_is_clamped_to_min() {
+        assert_nearly_eq!(0.0, clamp(-0.5, 0.0, 1.0));
+    }
+
+    #[test]
+    fn value_above_max_is_clamped_to_max() {
+        assert_nearly_eq!(1.0, clamp(1.5, 0.0, 1.0));
+    }
+
+    #[test]
+    fn value_in_range_is_unchanged() {
+        assert_nearly_eq!(0.25, clamp(0.25, 0.0, 1.0));
+    }
+
+    #[test]
+    fn nan_is_clamped_to_min() {
+        assert_nearly_eq!(0.0, clamp(std::f64::NAN, 0.0, 1.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_when_min_is_greater_than_max() {
+        clamp(0.5, 1.0, 0.0);
+    }
+}
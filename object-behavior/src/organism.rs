@@ -1,6 +1,8 @@
 //! Behavior of an organism that can interact with its surroundings
 
+use crate::num::clamp;
 use itertools::Itertools;
+use myelin_clone_box::clone_box;
 use myelin_engine::prelude::*;
 use myelin_genetics::{
     DevelopedNeuralNetwork, GenomeGenerator, GenomeGeneratorConfiguration, GenomeOrigin,
@@ -10,6 +12,7 @@ use myelin_neural_network::{Handle, Milliseconds, NeuralNetwork};
 use myelin_object_data::{AdditionalObjectDescription, Object, ObjectDescription};
 
 use std::collections::HashMap;
+use std::fmt::Debug;
 use std::num::NonZeroUsize;
 /// The hightest relative acceleration an organism can detect.
 /// The value was chosen as many sources, [including Wikipedia](https://en.wikipedia.org/wiki/G-LOC#Thresholds) report
@@ -29,16 +32,70 @@ const MAX_ANGULAR_FORCE: f64 = MAX_ACCELERATION_FORCE;
 const RAYCAST_COUNT: usize = 10;
 /// Number of objects that can be detected by a vision ray
 const MAX_OBJECTS_PER_RAYCAST: usize = 3;
+/// The grid in degrees to which the angles of vision rays are snapped by default
+const DEFAULT_RAY_ANGLE_QUANTIZATION_STEP: f64 = 0.000_001;
+/// The grid to which the components of vision ray directions are snapped,
+/// which hides differences in the last bits of trigonometric functions across platforms
+const RAY_DIRECTION_QUANTIZATION_STEP: f64 = 0.000_000_000_001;
+/// Number of recurrent neurons whose outputs are fed back as inputs in the next step,
+/// which gives an organism a short-term memory. Arbitrary value
+const CONTEXT_NEURON_COUNT: usize = 4;
 
 /// An organism that can interact with its surroundings via a neural network,
 /// built from a set of genes
 #[derive(Debug, Clone)]
 pub struct OrganismBehavior {
     previous_velocity: Vector,
+    previous_context_outputs: Vec<Option<f64>>,
+    fitness: f64,
+    fitness_function: Option<Box<dyn FitnessFunction>>,
+    ray_angle_quantization_step: f64,
+    energy_budget: Option<EnergyBudget>,
     developed_neural_network: DevelopedNeuralNetwork,
     neural_network_developer: Box<dyn NeuralNetworkDevelopmentOrchestrator>,
 }
 
+/// Energy per millisecond that an idle organism regenerates. Arbitrary value
+const ENERGY_REGENERATION_RATE: f64 = 0.01;
+
+/// The energy an organism spends on applying forces
+#[derive(Debug, Clone)]
+struct EnergyBudget {
+    energy: f64,
+    initial_energy: f64,
+    consumption_rate: f64,
+}
+
+impl EnergyBudget {
+    /// Spends energy proportionally to the magnitude of the applied force
+    /// or regenerates some of it, up to the initial energy, if no force was applied.
+    fn spend(&mut self, force: Option<&Force>, elapsed_time: Milliseconds) {
+        match force {
+            Some(force) => {
+                let magnitude = force.linear.magnitude() + force.torque.0.abs();
+                self.energy -= self.consumption_rate * magnitude * elapsed_time;
+            }
+            None => {
+                self.energy = (self.energy + ENERGY_REGENERATION_RATE * elapsed_time)
+                    .min(self.initial_energy);
+            }
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.energy <= 0.0
+    }
+}
+
+/// Scores how well an organism did in a single step, e.g. by the distance it travelled.
+/// The scores of all steps are summed up to the organism's fitness.
+pub trait FitnessFunction: Debug + FitnessFunctionClone {
+    /// Scores the step that took `elapsed_time` and resulted in `own_description`
+    fn score_step(&self, own_description: &ObjectDescription, elapsed_time: Milliseconds) -> f64;
+}
+
+clone_box!(FitnessFunction, FitnessFunctionClone);
+
 /// Number of inputs reserved for visible objects
 const VISION_INPUT_COUNT: usize = RAYCAST_COUNT * MAX_OBJECTS_PER_RAYCAST;
 
@@ -46,13 +103,18 @@ const VISION_INPUT_COUNT: usize = RAYCAST_COUNT * MAX_OBJECTS_PER_RAYCAST;
 /// 2. Average axial acceleration since last step (backward)
 /// 3. Average lateral acceleration since last step (left)
 /// 4. Average lateral acceleration since last step (right)
-/// Rest: Distances to objects in FOV from right to left
+/// Then: Distances to objects in FOV from right to left
+/// Rest: Outputs of the context neurons in the previous step
 fn input_neuron_count() -> NonZeroUsize {
-    NonZeroUsize::new(4 + VISION_INPUT_COUNT).unwrap()
+    NonZeroUsize::new(4 + VISION_INPUT_COUNT + CONTEXT_NEURON_COUNT).unwrap()
 }
 
 fn first_vision_index() -> usize {
-    input_neuron_count().get() - VISION_INPUT_COUNT + 1
+    first_context_input_index() - VISION_INPUT_COUNT + 1
+}
+
+fn first_context_input_index() -> usize {
+    input_neuron_count().get() - CONTEXT_NEURON_COUNT
 }
 
 /// 2. axial force (backward)
@@ -60,8 +122,13 @@ fn first_vision_index() -> usize {
 /// 4. lateral force (right)
 /// 5. torque (counterclockwise)
 /// 6. torque (clockwise)
+/// Rest: context neurons, which are fed back as inputs in the next step
 fn output_neuron_count() -> NonZeroUsize {
-    NonZeroUsize::new(6).unwrap()
+    NonZeroUsize::new(6 + CONTEXT_NEURON_COUNT).unwrap()
+}
+
+fn first_context_output_index() -> usize {
+    output_neuron_count().get() - CONTEXT_NEURON_COUNT
 }
 
 impl OrganismBehavior {
@@ -83,6 +150,11 @@ impl OrganismBehavior {
 
         Self {
             previous_velocity: Vector::default(),
+            previous_context_outputs: vec![None; CONTEXT_NEURON_COUNT],
+            fitness: 0.0,
+            fitness_function: None,
+            ray_angle_quantization_step: DEFAULT_RAY_ANGLE_QUANTIZATION_STEP,
+            energy_budget: None,
             developed_neural_network: neural_network_developer
                 .develop_neural_network(&configuration),
             neural_network_developer,
@@ -106,6 +178,92 @@ impl OrganismBehavior {
         let genome = genome_generator.generate_genome(&configuration);
         Self::new(GenomeOrigin::Genesis(genome), neural_network_developer)
     }
+
+    /// Scores every step of this organism using the given [`FitnessFunction`].
+    /// Without a [`FitnessFunction`], the fitness stays at zero.
+    pub fn with_fitness_function(mut self, fitness_function: Box<dyn FitnessFunction>) -> Self {
+        self.fitness_function = Some(fitness_function);
+        self
+    }
+
+    /// Snaps the angles of the rays used for vision to multiples of `step_in_degrees`,
+    /// so that the same rotation always results in exactly the same rays.
+    /// Defaults to a millionth of a degree.
+    ///
+    /// # Panics
+    /// Panics if `step_in_degrees` is not positive.
+    pub fn with_ray_angle_quantization_step(mut self, step_in_degrees: f64) -> Self {
+        assert!(step_in_degrees > 0.0, "step_in_degrees must be positive");
+        self.ray_angle_quantization_step = step_in_degrees;
+        self
+    }
+
+    /// Makes applying forces cost energy. Every step, the organism spends
+    /// `consumption_rate` energy per unit of force and millisecond.
+    /// The energy slowly regenerates, up to `initial_energy`, while the organism is idle.
+    /// When the energy is used up, the organism destroys itself.
+    /// Without an energy budget, the organism can apply forces indefinitely.
+    ///
+    /// # Panics
+    /// Panics if `initial_energy` is not positive or if `consumption_rate` is negative.
+    pub fn with_energy(mut self, initial_energy: f64, consumption_rate: f64) -> Self {
+        assert!(initial_energy > 0.0, "initial_energy must be positive");
+        assert!(
+            consumption_rate >= 0.0,
+            "consumption_rate must not be negative"
+        );
+        self.energy_budget = Some(EnergyBudget {
+            energy: initial_energy,
+            initial_energy,
+            consumption_rate,
+        });
+        self
+    }
+
+    /// The sum of the scores of all steps so far
+    pub fn fitness(&self) -> f64 {
+        self.fitness
+    }
+
+    /// The energy left, if the organism has an energy budget
+    pub fn energy(&self) -> Option<f64> {
+        self.energy_budget
+            .as_ref()
+            .map(|energy_budget| energy_budget.energy)
+    }
+
+    fn spend_energy(
+        &mut self,
+        action: Option<Action<AdditionalObjectDescription>>,
+        elapsed_time: Milliseconds,
+    ) -> Option<Action<AdditionalObjectDescription>> {
+        let energy_budget = match self.energy_budget {
+            Some(ref mut energy_budget) => energy_budget,
+            None => return action,
+        };
+
+        let force = match action {
+            Some(Action::ApplyForce(ref force)) => Some(force),
+            _ => None,
+        };
+        energy_budget.spend(force, elapsed_time);
+
+        if energy_budget.is_exhausted() {
+            Some(Action::DestroySelf)
+        } else {
+            action
+        }
+    }
+
+    fn accumulate_fitness(
+        &mut self,
+        own_description: &ObjectDescription,
+        elapsed_time: Milliseconds,
+    ) {
+        if let Some(ref fitness_function) = self.fitness_function {
+            self.fitness += fitness_function.score_step(own_description, elapsed_time);
+        }
+    }
 }
 
 impl ObjectBehavior<AdditionalObjectDescription> for OrganismBehavior {
@@ -125,6 +283,8 @@ impl ObjectBehavior<AdditionalObjectDescription> for OrganismBehavior {
 
         self.previous_velocity = current_velocity;
 
+        self.accumulate_fitness(&own_object.description, elapsed_time);
+
         let mut inputs = HashMap::with_capacity(2);
         let mut insert_input_fn = |key, value| {
             inputs.insert(key, value);
@@ -136,7 +296,11 @@ impl ObjectBehavior<AdditionalObjectDescription> for OrganismBehavior {
             &mut insert_input_fn,
         );
 
-        let objects_in_fov = objects_in_fov(&own_object.description, &*world_interactor);
+        let objects_in_fov = objects_in_fov(
+            &own_object.description,
+            &*world_interactor,
+            self.ray_angle_quantization_step,
+        );
         let vision_neuron_inputs =
             objects_in_fov_to_neuron_inputs(&own_object.description, objects_in_fov);
 
@@ -146,17 +310,28 @@ impl ObjectBehavior<AdditionalObjectDescription> for OrganismBehavior {
             &mut insert_input_fn,
         );
 
+        add_context_inputs(
+            &self.previous_context_outputs,
+            &neuron_handle_mapping.input,
+            &mut insert_input_fn,
+        );
+
         let neural_network = &mut self.developed_neural_network.neural_network;
         neural_network.step(
             world_interactor.elapsed_time_in_update().as_millis() as Milliseconds,
             &inputs,
         );
 
-        convert_neural_network_output_to_action(
+        self.previous_context_outputs =
+            get_context_outputs(&neuron_handle_mapping.output, neural_network.as_ref());
+
+        let action = convert_neural_network_output_to_action(
             neuron_handle_mapping,
             neural_network.as_ref(),
             &own_object.description,
-        )
+        );
+
+        self.spend_energy(action, elapsed_time)
     }
 }
 
@@ -195,49 +370,80 @@ fn convert_neural_network_output_to_action(
     let angular_force = angular_acceleration_force
         .map(|force| position_vector.normal().unit() * MAX_ANGULAR_FORCE * force);
 
-    let torque = angular_force.map(|force| position_vector.cross_product(force));
+    let torque = angular_force
+        .map(|force| position_vector.cross_product(force))
+        .unwrap_or_default();
 
-    if axial_force.is_some() || lateral_force.is_some() || torque.is_some() {
-        let relative_linear_force = Vector {
-            x: axial_force.unwrap_or_default(),
-            y: lateral_force.unwrap_or_default(),
-        };
-        let global_linear_force = relative_linear_force.rotate(object_description.rotation);
-        let scaled_linear_force = global_linear_force * MAX_ACCELERATION_FORCE;
+    let relative_linear_force = Vector {
+        x: axial_force.unwrap_or_default(),
+        y: lateral_force.unwrap_or_default(),
+    };
+    let global_linear_force = relative_linear_force.rotate(object_description.rotation);
+    let scaled_linear_force = global_linear_force * MAX_ACCELERATION_FORCE;
+
+    if is_effectively_zero(scaled_linear_force.magnitude()) && is_effectively_zero(torque) {
+        None
+    } else {
         Some(Action::ApplyForce(Force {
             linear: scaled_linear_force,
-            torque: Torque(torque.unwrap_or_default()),
+            torque: Torque(torque),
         }))
-    } else {
-        None
     }
 }
 
+/// Arbitrary value
+const MIN_EFFECTIVE_FORCE: f64 = 0.000_1;
+
+/// Forces this small are not worth registering in the world
+fn is_effectively_zero(force: f64) -> bool {
+    force.abs() < MIN_EFFECTIVE_FORCE
+}
+
 fn objects_in_fov<'a>(
     own_description: &'a ObjectDescription,
     world_interactor: &'a dyn WorldInteractor<AdditionalObjectDescription>,
+    ray_angle_quantization_step: f64,
 ) -> impl Iterator<Item = (impl Iterator<Item = Object<'a>> + 'a)> + 'a {
+    ray_directions(own_description.rotation, ray_angle_quantization_step).map(move |direction| {
+        world_interactor
+            .find_objects_in_ray(own_description.location, direction)
+            .into_iter()
+    })
+}
+
+/// The directions of the vision rays from right to left.
+/// Every angle is calculated from the own rotation instead of rotating the previous ray,
+/// so that rounding errors don't accumulate.
+fn ray_directions(
+    own_rotation: Radians,
+    angle_quantization_step: f64,
+) -> impl Iterator<Item = Vector> {
     /// The angle in degrees describing the field of view. [Wikipedia](https://en.wikipedia.org/wiki/Human_eye#Field_of_view).
     const FOV_ANGLE: usize = 200;
     const ANGLE_PER_RAYCAST: f64 = FOV_ANGLE as f64 / RAYCAST_COUNT as f64;
 
     let unit_vector = Vector { x: 1.0, y: 0.0 };
-    let own_direction = unit_vector.rotate(own_description.rotation);
+    let rightmost_angle_in_degrees = own_rotation.value().to_degrees() - FOV_ANGLE as f64 / 2.0;
 
-    let half_of_fov_angle = Radians::try_from_degrees(FOV_ANGLE as f64 / 2.0).unwrap();
-    let rightmost_angle = own_direction.rotate_clockwise(half_of_fov_angle);
     (0..RAYCAST_COUNT).map(move |angle_step| {
-        // Todo(#361): The following three lines produce slightly different numbers on macOS
-        let angle_in_degrees = angle_step as f64 * ANGLE_PER_RAYCAST;
-        let angle_in_radians = Radians::try_from_degrees(angle_in_degrees).unwrap();
-        let fov_direction = rightmost_angle.rotate(angle_in_radians);
-
-        world_interactor
-            .find_objects_in_ray(own_description.location, fov_direction)
-            .into_iter()
+        let angle_in_degrees = rightmost_angle_in_degrees + angle_step as f64 * ANGLE_PER_RAYCAST;
+        let quantized_angle_in_degrees =
+            quantize(angle_in_degrees, angle_quantization_step).rem_euclid(360.0);
+        let angle = Radians::try_from_degrees(quantized_angle_in_degrees).unwrap();
+        let direction = unit_vector.rotate(angle);
+
+        Vector {
+            x: quantize(direction.x, RAY_DIRECTION_QUANTIZATION_STEP),
+            y: quantize(direction.y, RAY_DIRECTION_QUANTIZATION_STEP),
+        }
     })
 }
 
+/// Snaps `value` to the nearest multiple of `step`
+fn quantize(value: f64, step: f64) -> f64 {
+    (value / step).round() * step
+}
+
 fn objects_in_fov_to_neuron_inputs<'a, T, U>(
     own_description: &'a ObjectDescription,
     objects: T,
@@ -370,11 +576,35 @@ fn add_vision_inputs<T>(
             let input_intensity_by_proximity = MAX_DISTINGUISHABLE_DISTANCE_IN_METERS - distance;
             let scaled_input =
                 input_intensity_by_proximity / MAX_DISTINGUISHABLE_DISTANCE_IN_METERS;
-            let clamped_input = scaled_input.clamp(0.0, 1.0);
+            let clamped_input = clamp(scaled_input, 0.0, 1.0);
             add_input_fn(*handle, clamped_input);
         });
 }
 
+fn add_context_inputs(
+    previous_context_outputs: &[Option<f64>],
+    input_neuron_handle_mapping: &InputNeuronHandleMapping,
+    mut add_input_fn: impl FnMut(Handle, f64),
+) {
+    input_neuron_handle_mapping
+        .context
+        .iter()
+        .zip(previous_context_outputs)
+        .filter_map(|(handle, output)| Some((handle, (*output)?)))
+        .for_each(|(handle, output)| add_input_fn(*handle, output));
+}
+
+fn get_context_outputs(
+    output_neuron_handle_mapping: &OutputNeuronHandleMapping,
+    neural_network: &dyn NeuralNetwork,
+) -> Vec<Option<f64>> {
+    output_neuron_handle_mapping
+        .context
+        .iter()
+        .map(|&handle| get_normalized_potential(handle, neural_network))
+        .collect()
+}
+
 /// Arbitrary value
 const MAX_DISTINGUISHABLE_DISTANCE_IN_METERS: f64 = 1200.0;
 
@@ -418,13 +648,15 @@ struct InputNeuronHandleMapping {
     axial_acceleration: AxialAccelerationHandleMapping,
     lateral_acceleration: LateralAccelerationHandleMapping,
     vision: Vec<Handle>,
+    context: Vec<Handle>,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 struct OutputNeuronHandleMapping {
     axial_acceleration: AxialAccelerationHandleMapping,
     lateral_acceleration: LateralAccelerationHandleMapping,
     torque: TorqueHandleMapping,
+    context: Vec<Handle>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -459,7 +691,10 @@ fn map_handles(developed_neural_network: &DevelopedNeuralNetwork) -> NeuronHandl
                 left: get_neuron_handle(input_neurons, 2),
                 right: get_neuron_handle(input_neurons, 3),
             },
-            vision: (first_vision_index()..input_neuron_count().get())
+            vision: (first_vision_index()..first_context_input_index())
+                .map(|index| get_neuron_handle(input_neurons, index))
+                .collect(),
+            context: (first_context_input_index()..input_neuron_count().get())
                 .map(|index| get_neuron_handle(input_neurons, index))
                 .collect(),
         },
@@ -476,6 +711,9 @@ fn map_handles(developed_neural_network: &DevelopedNeuralNetwork) -> NeuronHandl
                 counterclockwise: get_neuron_handle(output_neurons, 4),
                 clockwise: get_neuron_handle(output_neurons, 5),
             },
+            context: (first_context_output_index()..output_neuron_count().get())
+                .map(|index| get_neuron_handle(output_neurons, index))
+                .collect(),
         },
     }
 }
@@ -521,6 +759,7 @@ mod tests {
     use nearly_eq::assert_nearly_eq;
     use std::f64::consts::PI;
     use std::iter;
+    use std::time::Duration;
 
     #[test]
     fn can_be_constructed_with_genome_generator() {
@@ -638,7 +877,10 @@ mod tests {
                 left: Handle(2),
                 right: Handle(3),
             },
-            vision: (first_vision_index()..input_neuron_count().get())
+            vision: (first_vision_index()..first_context_input_index())
+                .map(Handle)
+                .collect(),
+            context: (first_context_input_index()..input_neuron_count().get())
                 .map(Handle)
                 .collect(),
         };
@@ -828,6 +1070,245 @@ mod tests {
         }
     }
 
+    #[test]
+    fn neural_network_output_without_force_is_not_mapped_to_action() {
+        let developed_neural_network = mock_developed_neural_network();
+        let mapping = map_handles(&developed_neural_network);
+
+        let mut network = NeuralNetworkMock::new();
+        for &handle in &[
+            mapping.output.axial_acceleration.forward,
+            mapping.output.axial_acceleration.backward,
+            mapping.output.lateral_acceleration.left,
+            mapping.output.lateral_acceleration.right,
+            mapping.output.torque.counterclockwise,
+            mapping.output.torque.clockwise,
+        ] {
+            network
+                .expect_normalized_potential_of_neuron(|arg| arg.partial_eq(handle))
+                .returns(Ok(Some(0.0)));
+        }
+
+        let object_description = object_description().build().unwrap();
+
+        let action =
+            convert_neural_network_output_to_action(mapping, &network, &object_description);
+
+        assert!(action.is_none());
+    }
+
+    #[test]
+    fn context_output_is_fed_back_as_input_in_next_step() {
+        let developed_neural_network = mock_developed_neural_network();
+        let mapping = map_handles(&developed_neural_network);
+
+        let mut network = NeuralNetworkMock::new();
+        for (index, &handle) in mapping.output.context.iter().enumerate() {
+            let output = if index == 1 {
+                None
+            } else {
+                Some(index as f64 / 10.0)
+            };
+            network
+                .expect_normalized_potential_of_neuron(|arg| arg.partial_eq(handle))
+                .returns(Ok(output));
+        }
+
+        let context_outputs = get_context_outputs(&mapping.output, &network);
+
+        let mut inputs = HashMap::new();
+        add_context_inputs(&context_outputs, &mapping.input, |handle, value| {
+            inputs.insert(handle, value);
+        });
+
+        let expected_inputs: HashMap<_, _> = mapping
+            .input
+            .context
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| index != 1)
+            .map(|(index, &handle)| (handle, index as f64 / 10.0))
+            .collect();
+        assert_eq!(expected_inputs, inputs);
+    }
+
+    #[derive(Debug, Clone)]
+    struct TravelledDistance;
+
+    impl FitnessFunction for TravelledDistance {
+        fn score_step(
+            &self,
+            own_description: &ObjectDescription,
+            elapsed_time: Milliseconds,
+        ) -> f64 {
+            velocity(own_description).magnitude() * elapsed_time
+        }
+    }
+
+    fn organism_behavior() -> OrganismBehavior {
+        let mut neural_network_developer = NeuralNetworkDevelopmentOrchestratorMock::new();
+        neural_network_developer
+            .expect_develop_neural_network(|arg| arg.any())
+            .returns(mock_developed_neural_network());
+
+        OrganismBehavior::new(
+            GenomeOrigin::Genesis(Genome::default()),
+            box neural_network_developer,
+        )
+    }
+
+    #[test]
+    fn fitness_is_zero_without_fitness_function() {
+        let mut organism_behavior = organism_behavior();
+        let own_description = object_description()
+            .mobility(Mobility::Movable(Vector { x: 3.0, y: 4.0 }))
+            .build()
+            .unwrap();
+
+        organism_behavior.accumulate_fitness(&own_description, 10.0);
+
+        assert_nearly_eq!(0.0, organism_behavior.fitness());
+    }
+
+    #[test]
+    fn fitness_is_accumulated_over_steps() {
+        let mut organism_behavior =
+            organism_behavior().with_fitness_function(box TravelledDistance);
+
+        for &(velocity, elapsed_time) in &[
+            (Vector { x: 3.0, y: 4.0 }, 10.0),
+            (Vector { x: 0.0, y: 0.0 }, 10.0),
+            (Vector { x: -6.0, y: 8.0 }, 5.0),
+        ] {
+            let own_description = object_description()
+                .mobility(Mobility::Movable(velocity))
+                .build()
+                .unwrap();
+            organism_behavior.accumulate_fitness(&own_description, elapsed_time);
+        }
+
+        assert_nearly_eq!(100.0, organism_behavior.fitness());
+    }
+
+    fn max_force() -> Force {
+        Force {
+            linear: Vector {
+                x: MAX_ACCELERATION_FORCE,
+                y: 0.0,
+            },
+            torque: Torque(0.0),
+        }
+    }
+
+    #[test]
+    fn energy_is_none_without_energy_budget() {
+        let mut organism_behavior = organism_behavior();
+
+        for _ in 0..1000 {
+            let action =
+                organism_behavior.spend_energy(Some(Action::ApplyForce(max_force())), 16.0);
+            match action {
+                Some(Action::ApplyForce(_)) => {}
+                action => panic!("Expected Action::ApplyForce, got {:#?}", action),
+            }
+        }
+
+        assert_eq!(None, organism_behavior.energy());
+    }
+
+    #[test]
+    fn applying_max_force_continuously_leads_to_destruction() {
+        let mut organism_behavior = organism_behavior().with_energy(100.0, 0.001);
+
+        let steps_until_destruction = (0..1000).find(|_| {
+            let action =
+                organism_behavior.spend_energy(Some(Action::ApplyForce(max_force())), 16.0);
+
+            match action {
+                Some(Action::DestroySelf) => true,
+                Some(Action::ApplyForce(_)) => false,
+                action => panic!("Unexpected action {:#?}", action),
+            }
+        });
+
+        // 100 / (0.001 * 196 * 16) ≈ 31.9
+        assert_eq!(Some(31), steps_until_destruction);
+    }
+
+    #[test]
+    fn idle_organism_survives() {
+        let mut organism_behavior = organism_behavior().with_energy(100.0, 0.001);
+
+        for _ in 0..1000 {
+            assert!(organism_behavior.spend_energy(None, 16.0).is_none());
+        }
+
+        assert_nearly_eq!(100.0, organism_behavior.energy().unwrap());
+    }
+
+    #[test]
+    fn energy_regenerates_while_idle() {
+        let mut organism_behavior = organism_behavior().with_energy(100.0, 0.001);
+        organism_behavior.spend_energy(Some(Action::ApplyForce(max_force())), 10.0);
+
+        assert_nearly_eq!(98.04, organism_behavior.energy().unwrap());
+
+        organism_behavior.spend_energy(None, 100.0);
+
+        assert_nearly_eq!(99.04, organism_behavior.energy().unwrap());
+    }
+
+    #[test]
+    fn step_destroys_organism_when_energy_is_used_up() {
+        let developed_neural_network = mock_developed_neural_network();
+        let mapping = map_handles(&developed_neural_network);
+
+        let mut network = NeuralNetworkMock::new();
+        network.expect_step(|arg| arg.any(), |arg| arg.any());
+        network
+            .expect_normalized_potential_of_neuron(|arg| {
+                arg.partial_eq(mapping.output.axial_acceleration.forward)
+            })
+            .returns(Ok(Some(1.0)));
+        for &handle in mapping.output.context.iter().chain(&[
+            mapping.output.axial_acceleration.backward,
+            mapping.output.lateral_acceleration.left,
+            mapping.output.lateral_acceleration.right,
+            mapping.output.torque.counterclockwise,
+            mapping.output.torque.clockwise,
+        ]) {
+            network
+                .expect_normalized_potential_of_neuron(|arg| arg.partial_eq(handle))
+                .returns(Ok(None));
+        }
+
+        let mut organism_behavior = organism_behavior().with_energy(1.0, 1.0);
+        organism_behavior.developed_neural_network.neural_network = box network;
+
+        let object_behavior = ObjectBehaviorMock::new();
+        let mut world_interactor = WorldInteractorMock::new();
+        world_interactor
+            .expect_elapsed_time_in_update()
+            .returns(Duration::from_millis(16))
+            .times(2);
+        world_interactor.expect_own_object().returns(Object {
+            id: 0,
+            description: object_description().build().unwrap(),
+            behavior: &object_behavior,
+        });
+        world_interactor
+            .expect_find_objects_in_ray(|arg| arg.any(), |arg| arg.any())
+            .returns(Vec::new())
+            .times(RAYCAST_COUNT as u64);
+
+        let action = organism_behavior.step(box world_interactor);
+
+        match action {
+            Some(Action::DestroySelf) => {}
+            action => panic!("Expected Action::DestroySelf, got {:#?}", action),
+        }
+    }
+
     fn object_description() -> ObjectBuilder<AdditionalObjectDescription> {
         let mut builder = ObjectBuilder::default();
         builder
@@ -861,7 +1342,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg_attr(target_os = "macos", ignore)]
     fn objects_in_fov_is_empty_with_no_surrounding_objects() {
         test_objects_in_fov_are_as_expected(ExpectedFovObjects {
             first_objects_in_ray: Vec::new(),
@@ -879,7 +1359,6 @@ mod tests {
     }
 
     #[test]
-    #[cfg_attr(target_os = "macos", ignore)]
     fn objects_in_fov_are_filtered_correctly() {
         let mock_behavior = ObjectBehaviorMock::new();
         let mut counter = 0;
@@ -916,6 +1395,30 @@ mod tests {
         })
     }
 
+    #[test]
+    fn ray_directions_are_independent_of_rounding_noise_in_rotation() {
+        let rotation = Radians::try_new(PI).unwrap();
+        let noisy_rotation = Radians::try_new(PI + 0.000_000_000_001).unwrap();
+
+        let ray_directions_of_rotation: Vec<_> =
+            ray_directions(rotation, DEFAULT_RAY_ANGLE_QUANTIZATION_STEP).collect();
+        let ray_directions_of_noisy_rotation: Vec<_> =
+            ray_directions(noisy_rotation, DEFAULT_RAY_ANGLE_QUANTIZATION_STEP).collect();
+
+        assert_eq!(ray_directions_of_rotation, ray_directions_of_noisy_rotation);
+    }
+
+    #[test]
+    fn ray_angles_are_snapped_to_quantization_step() {
+        let rotation = Radians::try_from_degrees(10.0).unwrap();
+
+        let ray_directions: Vec<_> = ray_directions(rotation, 90.0).collect();
+
+        assert_eq!(Vector { x: 0.0, y: -1.0 }, ray_directions[0]);
+        assert_eq!(Vector { x: 1.0, y: 0.0 }, ray_directions[5]);
+        assert_eq!(Vector { x: 0.0, y: 1.0 }, ray_directions[9]);
+    }
+
     #[derive(Debug, Default)]
     struct ExpectedFovObjects<'a> {
         first_objects_in_ray: Snapshot<'a, AdditionalObjectDescription>,
@@ -945,68 +1448,68 @@ mod tests {
         };
 
         let first_ray = Vector {
-            x: 0.173_648_177_666_930_41,
-            y: 0.984_807_753_012_208,
+            x: 0.173_648_177_667,
+            y: 0.984_807_753_012,
         };
         connect_ray_to_expectation(first_ray, expected_fov_objects.first_objects_in_ray);
 
         let second_ray = Vector {
-            x: -0.173_648_177_666_930_25,
-            y: 0.984_807_753_012_208_1,
+            x: -0.173_648_177_667,
+            y: 0.984_807_753_012,
         };
         connect_ray_to_expectation(second_ray, expected_fov_objects.second_objects_in_ray);
 
         let third_ray = Vector {
-            x: -0.499_999_999_999_999_9,
-            /// On macOS, the actual value is for some reason 0.866_025_403_784_438_7
-            y: 0.866_025_403_784_438_6,
+            x: -0.5,
+            y: 0.866_025_403_784,
         };
         connect_ray_to_expectation(third_ray, expected_fov_objects.third_objects_in_ray);
 
         let fourth_ray = Vector {
-            x: -0.766_044_443_118_977_9,
-            y: 0.642_787_609_686_539_5,
+            x: -0.766_044_443_119,
+            y: 0.642_787_609_687,
         };
         connect_ray_to_expectation(fourth_ray, expected_fov_objects.fourth_objects_in_ray);
 
         let fifth_ray = Vector {
-            x: -0.939_692_620_785_908_3,
-            y: 0.342_020_143_325_668_8,
+            x: -0.939_692_620_786,
+            y: 0.342_020_143_326,
         };
         connect_ray_to_expectation(fifth_ray, expected_fov_objects.fifth_objects_in_ray);
 
-        let sixth_ray = Vector {
-            x: -0.999_999_999_999_999_9,
-            y: 0.000_000_000_000_000_083_266_726_846_886_74,
-        };
+        let sixth_ray = Vector { x: -1.0, y: 0.0 };
         connect_ray_to_expectation(sixth_ray, expected_fov_objects.sixth_objects_in_ray);
 
         let seventh_ray = Vector {
-            /// On macOS, the actual value is for some reason 0.939_692_620_785_908_4
-            x: -0.939_692_620_785_908_4,
-            y: -0.342_020_143_325_668_44,
+            x: -0.939_692_620_786,
+            y: -0.342_020_143_326,
         };
         connect_ray_to_expectation(seventh_ray, expected_fov_objects.seventh_objects_in_ray);
 
         let eight_ray = Vector {
-            x: -0.766_044_443_118_978_2,
-            y: -0.642_787_609_686_539,
+            x: -0.766_044_443_119,
+            y: -0.642_787_609_687,
         };
         connect_ray_to_expectation(eight_ray, expected_fov_objects.eight_objects_in_ray);
 
         let ninth_ray = Vector {
-            x: -0.500_000_000_000_000_2,
-            y: -0.866_025_403_784_438_5,
+            x: -0.5,
+            y: -0.866_025_403_784,
         };
         connect_ray_to_expectation(ninth_ray, expected_fov_objects.ninth_objects_in_ray);
 
         let tenth_ray = Vector {
-            x: -0.173_648_177_666_930_53,
-            y: -0.984_807_753_012_208,
+            x: -0.173_648_177_667,
+            y: -0.984_807_753_012,
         };
         connect_ray_to_expectation(tenth_ray, expected_fov_objects.tenth_objects_in_ray);
 
-        let objects_in_fov: Vec<_> = objects_in_fov(&own_description, &world_interactor).collect();
+        let objects_in_fov: Vec<_> = objects_in_fov(
+            &own_description,
+            &world_interactor,
+            DEFAULT_RAY_ANGLE_QUANTIZATION_STEP,
+        )
+        .collect();
         assert_eq!(
             expected_fov_objects.expected_objects.len(),
             objects_in_fov.len()
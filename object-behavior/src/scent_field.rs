@@ -0,0 +1,237 @@
+//! A scalar field that objects can leave scents in, which spread and fade over time
+
+use myelin_engine::prelude::*;
+
+/// A grid of scent concentrations covering an area of the world.
+/// Scents deposited in a cell diffuse into the neighbouring cells
+/// and decay on every [`step`].
+///
+/// [`step`]: ./struct.ScentField.html#method.step
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScentField {
+    bounds: Aabb,
+    cell_size: f64,
+    columns: usize,
+    rows: usize,
+    diffusion_rate: f64,
+    decay_rate: f64,
+    concentrations: Vec<f64>,
+}
+
+impl ScentField {
+    /// Creates an empty field covering `bounds` with square cells of `cell_size`.
+    /// On every step, a cell passes `diffusion_rate` of its concentration to its neighbours
+    /// and loses `decay_rate` of it.
+    ///
+    /// # Panics
+    /// Panics if `cell_size` is not positive or if either rate is not between 0 and 1.
+    pub fn new(bounds: Aabb, cell_size: f64, diffusion_rate: f64, decay_rate: f64) -> Self {
+        assert!(cell_size > 0.0, "cell_size must be positive");
+        assert!(
+            (0.0..=1.0).contains(&diffusion_rate),
+            "diffusion_rate must be between 0 and 1"
+        );
+        assert!(
+            (0.0..=1.0).contains(&decay_rate),
+            "decay_rate must be between 0 and 1"
+        );
+
+        let columns = cell_count(bounds.lower_right.x - bounds.upper_left.x, cell_size);
+        let rows = cell_count(bounds.lower_right.y - bounds.upper_left.y, cell_size);
+
+        Self {
+            bounds,
+            cell_size,
+            columns,
+            rows,
+            diffusion_rate,
+            decay_rate,
+            concentrations: vec![0.0; columns * rows],
+        }
+    }
+
+    /// Adds `amount` of scent to the cell containing `point`.
+    /// Deposits outside of the field's bounds are ignored.
+    pub fn deposit(&mut self, point: Point, amount: f64) {
+        if let Some(index) = self.cell_index(point) {
+            self.concentrations[index] += amount;
+        }
+    }
+
+    /// Returns the concentration of the cell containing `point`,
+    /// which is zero outside of the field's bounds.
+    pub fn sample(&self, point: Point) -> f64 {
+        self.cell_index(point)
+            .map(|index| self.concentrations[index])
+            .unwrap_or_default()
+    }
+
+    /// The sum of the concentrations of all cells
+    pub fn total_mass(&self) -> f64 {
+        self.concentrations.iter().sum()
+    }
+
+    /// Diffuses and decays the scents by one step
+    pub fn step(&mut self) {
+        let mut next_concentrations = vec![0.0; self.concentrations.len()];
+
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let index = row * self.columns + column;
+                let concentration = self.concentrations[index];
+                let neighbours = self.neighbours(column, row);
+
+                if neighbours.is_empty() {
+                    next_concentrations[index] += concentration;
+                    continue;
+                }
+
+                let diffused_concentration = concentration * self.diffusion_rate;
+                let share = diffused_concentration / neighbours.len() as f64;
+
+                next_concentrations[index] += concentration - diffused_concentration;
+                for neighbour in neighbours {
+                    next_concentrations[neighbour] += share;
+                }
+            }
+        }
+
+        let retained_fraction = 1.0 - self.decay_rate;
+        for concentration in &mut next_concentrations {
+            *concentration *= retained_fraction;
+        }
+
+        self.concentrations = next_concentrations;
+    }
+
+    fn cell_index(&self, point: Point) -> Option<usize> {
+        let column = self.cell_coordinate(point.x - self.bounds.upper_left.x, self.columns)?;
+        let row = self.cell_coordinate(point.y - self.bounds.upper_left.y, self.rows)?;
+        Some(row * self.columns + column)
+    }
+
+    /// The column or row that lies `offset` away from the field's upper left corner.
+    /// The range check happens before the cast, so that `NaN` and offsets
+    /// too large for a `usize` are rejected instead of being cast.
+    fn cell_coordinate(&self, offset: f64, cell_count: usize) -> Option<usize> {
+        let coordinate = offset / self.cell_size;
+
+        if (0.0..cell_count as f64).contains(&coordinate) {
+            Some(coordinate as usize)
+        } else {
+            None
+        }
+    }
+
+    /// The indices of the cells sharing an edge with the given cell
+    fn neighbours(&self, column: usize, row: usize) -> Vec<usize> {
+        let mut neighbours = Vec::with_capacity(4);
+
+        if column > 0 {
+            neighbours.push(row * self.columns + column - 1);
+        }
+        if column + 1 < self.columns {
+            neighbours.push(row * self.columns + column + 1);
+        }
+        if row > 0 {
+            neighbours.push((row - 1) * self.columns + column);
+        }
+        if row + 1 < self.rows {
+            neighbours.push((row + 1) * self.columns + column);
+        }
+
+        neighbours
+    }
+}
+
+fn cell_count(length: f64, cell_size: f64) -> usize {
+    (length / cell_size).ceil().max(1.0) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nearly_eq::assert_nearly_eq;
+
+    const CELL_SIZE: f64 = 10.0;
+
+    fn scent_field(diffusion_rate: f64, decay_rate: f64) -> ScentField {
+        let bounds = Aabb::try_new((0.0, 0.0), (100.0, 100.0)).unwrap();
+        ScentField::new(bounds, CELL_SIZE, diffusion_rate, decay_rate)
+    }
+
+    #[test]
+    fn deposit_raises_local_sample() {
+        let mut scent_field = scent_field(0.5, 0.1);
+        let point = Point { x: 45.0, y: 45.0 };
+
+        scent_field.deposit(point, 2.0);
+
+        assert_nearly_eq!(2.0, scent_field.sample(point));
+        assert_nearly_eq!(0.0, scent_field.sample(Point { x: 65.0, y: 45.0 }));
+    }
+
+    #[test]
+    fn deposit_outside_of_bounds_is_ignored() {
+        let mut scent_field = scent_field(0.5, 0.1);
+
+        scent_field.deposit(Point { x: -5.0, y: 45.0 }, 2.0);
+
+        assert_nearly_eq!(0.0, scent_field.total_mass());
+    }
+
+    #[test]
+    fn deposit_at_nan_is_ignored() {
+        let mut scent_field = scent_field(0.5, 0.1);
+        let point = Point {
+            x: std::f64::NAN,
+            y: 45.0,
+        };
+
+        scent_field.deposit(point, 2.0);
+
+        assert_nearly_eq!(0.0, scent_field.total_mass());
+        assert_nearly_eq!(0.0, scent_field.sample(point));
+    }
+
+    #[test]
+    fn deposit_at_huge_coordinate_is_ignored() {
+        let mut scent_field = scent_field(0.5, 0.1);
+        let point = Point { x: 45.0, y: 1e300 };
+
+        scent_field.deposit(point, 2.0);
+
+        assert_nearly_eq!(0.0, scent_field.total_mass());
+        assert_nearly_eq!(0.0, scent_field.sample(point));
+    }
+
+    #[test]
+    fn diffusion_spreads_scent_to_neighbours_over_steps() {
+        let mut scent_field = scent_field(0.5, 0.0);
+        let point = Point { x: 45.0, y: 45.0 };
+        let neighbour = Point { x: 55.0, y: 45.0 };
+        let neighbour_of_neighbour = Point { x: 65.0, y: 45.0 };
+
+        scent_field.deposit(point, 4.0);
+        scent_field.step();
+
+        assert_nearly_eq!(2.0, scent_field.sample(point));
+        assert_nearly_eq!(0.5, scent_field.sample(neighbour));
+        assert_nearly_eq!(0.0, scent_field.sample(neighbour_of_neighbour));
+
+        scent_field.step();
+
+        assert!(scent_field.sample(neighbour_of_neighbour) > 0.0);
+        assert_nearly_eq!(4.0, scent_field.total_mass());
+    }
+
+    #[test]
+    fn decay_reduces_total_mass() {
+        let mut scent_field = scent_field(0.5, 0.25);
+
+        scent_field.deposit(Point { x: 45.0, y: 45.0 }, 4.0);
+        scent_field.step();
+
+        assert_nearly_eq!(3.0, scent_field.total_mass());
+    }
+}
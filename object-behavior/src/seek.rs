@@ -0,0 +1,161 @@
+//! Contains the [`Seek`] behavior.
+
+use crate::nearest_object::find_nearest_object_of_kind;
+use myelin_engine::prelude::*;
+use myelin_object_data::{AdditionalObjectDescription, Kind};
+
+/// A behavior that moves towards the nearest object of a [`Kind`].
+/// Every step, a force of `max_force` is applied in the direction
+/// of the nearest such object within `search_radius`.
+/// No force is applied while the object is exactly at the location of its target.
+#[derive(Debug, Clone)]
+pub struct Seek {
+    target_kind: Kind,
+    search_radius: f64,
+    max_force: f64,
+}
+
+impl Seek {
+    /// Creates a behavior that seeks objects of `target_kind`
+    /// that are at most `search_radius` away.
+    ///
+    /// # Panics
+    /// Panics if `search_radius` is not positive.
+    pub fn new(target_kind: Kind, search_radius: f64, max_force: f64) -> Self {
+        assert!(search_radius > 0.0, "search_radius must be positive");
+
+        Self {
+            target_kind,
+            search_radius,
+            max_force,
+        }
+    }
+}
+
+impl ObjectBehavior<AdditionalObjectDescription> for Seek {
+    fn step(
+        &mut self,
+        world_interactor: Box<dyn WorldInteractor<AdditionalObjectDescription> + '_>,
+    ) -> Option<Action<AdditionalObjectDescription>> {
+        let own_object = world_interactor.own_object();
+        let target = find_nearest_object_of_kind(
+            &*world_interactor,
+            &own_object,
+            self.target_kind,
+            self.search_radius,
+        )?;
+
+        let offset = Vector::from(target.description.location - own_object.description.location);
+        if offset.magnitude() == 0.0 {
+            return None;
+        }
+
+        Some(Action::ApplyForce(Force {
+            linear: offset.unit() * self.max_force,
+            torque: Torque(0.0),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nearest_object::test_utils::{mock_behavior, object_description_at_location};
+    use myelin_object_data::Object;
+    use nearly_eq::assert_nearly_eq;
+
+    const SEARCH_RADIUS: f64 = 50.0;
+    const MAX_FORCE: f64 = 10.0;
+
+    #[test]
+    fn applies_force_towards_nearest_target() {
+        let behavior = mock_behavior();
+        let mut world_interactor = WorldInteractorMock::new();
+        world_interactor.expect_own_object().returns(Object {
+            id: 0,
+            description: object_description_at_location(Kind::Organism, 100.0, 100.0),
+            behavior: behavior.as_ref(),
+        });
+        world_interactor
+            .expect_find_objects_in_area(|arg| {
+                arg.partial_eq(Aabb::try_new((50.0, 50.0), (150.0, 150.0)).unwrap())
+            })
+            .returns(vec![
+                Object {
+                    id: 1,
+                    description: object_description_at_location(Kind::Plant, 100.0, 130.0),
+                    behavior: behavior.as_ref(),
+                },
+                Object {
+                    id: 2,
+                    description: object_description_at_location(Kind::Plant, 106.0, 92.0),
+                    behavior: behavior.as_ref(),
+                },
+                Object {
+                    id: 3,
+                    description: object_description_at_location(Kind::Water, 101.0, 100.0),
+                    behavior: behavior.as_ref(),
+                },
+            ]);
+
+        let mut seek = Seek::new(Kind::Plant, SEARCH_RADIUS, MAX_FORCE);
+        let action = seek.step(box world_interactor);
+
+        match action {
+            Some(Action::ApplyForce(force)) => {
+                assert_nearly_eq!(6.0, force.linear.x);
+                assert_nearly_eq!(-8.0, force.linear.y);
+                assert_nearly_eq!(0.0, force.torque.0);
+            }
+            action => panic!("Expected Action::ApplyForce, got {:#?}", action),
+        }
+    }
+
+    #[test]
+    fn does_nothing_without_target() {
+        let behavior = mock_behavior();
+        let mut world_interactor = WorldInteractorMock::new();
+        world_interactor.expect_own_object().returns(Object {
+            id: 0,
+            description: object_description_at_location(Kind::Organism, 100.0, 100.0),
+            behavior: behavior.as_ref(),
+        });
+        world_interactor
+            .expect_find_objects_in_area(|arg| arg.any())
+            .returns(Vec::new());
+
+        let mut seek = Seek::new(Kind::Plant, SEARCH_RADIUS, MAX_FORCE);
+        let action = seek.step(box world_interactor);
+
+        assert!(action.is_none());
+    }
+
+    #[test]
+    fn does_nothing_when_at_location_of_target() {
+        let behavior = mock_behavior();
+        let mut world_interactor = WorldInteractorMock::new();
+        world_interactor.expect_own_object().returns(Object {
+            id: 0,
+            description: object_description_at_location(Kind::Organism, 100.0, 100.0),
+            behavior: behavior.as_ref(),
+        });
+        world_interactor
+            .expect_find_objects_in_area(|arg| arg.any())
+            .returns(vec![Object {
+                id: 1,
+                description: object_description_at_location(Kind::Plant, 100.0, 100.0),
+                behavior: behavior.as_ref(),
+            }]);
+
+        let mut seek = Seek::new(Kind::Plant, SEARCH_RADIUS, MAX_FORCE);
+        let action = seek.step(box world_interactor);
+
+        assert!(action.is_none());
+    }
+
+    #[should_panic]
+    #[test]
+    fn panics_on_non_positive_search_radius() {
+        Seek::new(Kind::Plant, 0.0, MAX_FORCE);
+    }
+}
@@ -11,6 +11,7 @@ use myelin_random::Random;
 pub struct StochasticSpreading {
     random: Box<dyn Random>,
     spreading_probability: f64,
+    spreading_offsets: Option<Vec<Point>>,
     next_spreading_location: Option<Aabb>,
 }
 
@@ -19,6 +20,7 @@ impl Clone for StochasticSpreading {
         Self {
             random: self.random.clone_box(),
             spreading_probability: self.spreading_probability,
+            spreading_offsets: self.spreading_offsets.clone(),
             next_spreading_location: self.next_spreading_location,
         }
     }
@@ -32,10 +34,26 @@ impl StochasticSpreading {
         Self {
             spreading_probability,
             random,
+            spreading_offsets: None,
             next_spreading_location: None,
         }
     }
 
+    /// Tries to spread to the given offsets relative to the object's location
+    /// instead of the eight positions adjacent to the bounding box of its shape.
+    ///
+    /// # Panics
+    /// Panics if `spreading_offsets` is empty.
+    pub fn with_spreading_offsets(mut self, spreading_offsets: Vec<Point>) -> Self {
+        assert!(
+            !spreading_offsets.is_empty(),
+            "Expected at least one spreading offset"
+        );
+
+        self.spreading_offsets = Some(spreading_offsets);
+        self
+    }
+
     /// Returns the position, if any, where this behavior will spread to in the next step
     pub fn next_spreading_location(&self) -> Option<Aabb> {
         self.next_spreading_location
@@ -51,8 +69,10 @@ impl StochasticSpreading {
         world_interactor: &dyn WorldInteractor<AdditionalObjectDescription>,
     ) -> Option<Action<AdditionalObjectDescription>> {
         let own_object = world_interactor.own_object();
-        let possible_spreading_locations =
-            calculate_possible_spreading_locations(&own_object.description.shape);
+        let possible_spreading_locations = match &self.spreading_offsets {
+            Some(spreading_offsets) => spreading_offsets.clone(),
+            None => calculate_possible_spreading_locations(&own_object.description.shape),
+        };
 
         let first_try_index =
             self.random
@@ -92,10 +112,10 @@ impl StochasticSpreading {
 /// -----------------------------------------
 ///  Lower Left | Lower Middle | Lower Right
 /// ```
-fn calculate_possible_spreading_locations(polygon: &Polygon) -> [Point; 8] {
+fn calculate_possible_spreading_locations(polygon: &Polygon) -> Vec<Point> {
     let (width, height) = width_and_height_of_area(polygon.aabb());
 
-    [
+    vec![
         Point {
             x: -width,
             y: -height,
@@ -699,6 +719,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn spreads_at_custom_spreading_offset() {
+        let object_behavior = ObjectBehaviorMock::new();
+        let mut random = RandomMock::new();
+        random
+            .expect_flip_coin_with_probability(|arg| arg.partial_eq(SPREADING_CHANGE))
+            .returns(true);
+        random
+            .expect_i32_in_range(|arg| arg.partial_eq(0), |arg| arg.partial_eq(1))
+            .returns(0);
+        let mut object = StochasticSpreading::new(SPREADING_CHANGE, box random)
+            .with_spreading_offsets(vec![Point { x: 20.0, y: 0.0 }]);
+        let mut world_interactor = WorldInteractorMock::new();
+        world_interactor
+            .expect_find_objects_in_area(|arg| {
+                arg.partial_eq(Aabb::try_new((65.0, 45.0), (75.0, 55.0)).unwrap())
+            })
+            .returns(Vec::new());
+        world_interactor
+            .expect_find_objects_in_area(|arg| {
+                arg.partial_eq(Aabb::try_new((54.0, 34.0), (86.0, 66.0)).unwrap())
+            })
+            .returns(Vec::new());
+        world_interactor.expect_own_object().returns(Object {
+            id: 0,
+            description: object_description_at_location(50.0, 50.0),
+            behavior: &object_behavior,
+        });
+
+        let action = object.step(box world_interactor);
+        match action {
+            Some(Action::Spawn(object_description, _)) => {
+                assert_eq!(
+                    object_description_at_location(70.0, 50.0),
+                    object_description
+                );
+            }
+            action => panic!("Expected Action::Spawn, got {:#?}", action),
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn panics_on_empty_spreading_offsets() {
+        StochasticSpreading::new(SPREADING_CHANGE, box RandomMock::new())
+            .with_spreading_offsets(Vec::new());
+    }
+
     #[test]
     fn can_be_downcast_from_trait() {
         let object_behavior: Box<dyn ObjectBehavior<AdditionalObjectDescription>> =
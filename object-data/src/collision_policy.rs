@@ -0,0 +1,105 @@
+//! Rules about which kinds of objects collide with each other
+
+use crate::Kind;
+use std::collections::HashSet;
+
+/// Decides whether objects of two [`Kind`]s collide or pass through each other.
+/// Unless specified otherwise, all kinds collide.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct CollisionPolicy {
+    passable_pairs: HashSet<(Kind, Kind)>,
+}
+
+impl CollisionPolicy {
+    /// Creates a policy in which all kinds collide
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether objects of the two kinds collide. The order of the kinds is irrelevant.
+    pub fn collides(&self, first_kind: Kind, second_kind: Kind) -> bool {
+        !self.passable_pairs.contains(&(first_kind, second_kind))
+    }
+
+    /// Overrides whether objects of the two kinds collide. The order of the kinds is irrelevant.
+    pub fn set_collides(&mut self, first_kind: Kind, second_kind: Kind, collides: bool) {
+        if collides {
+            self.passable_pairs.remove(&(first_kind, second_kind));
+            self.passable_pairs.remove(&(second_kind, first_kind));
+        } else {
+            self.passable_pairs.insert((first_kind, second_kind));
+            self.passable_pairs.insert((second_kind, first_kind));
+        }
+    }
+}
+
+/// The collisions happening in a regular simulation:
+/// Organisms pass through water and plants, while everything collides with terrain.
+pub fn default_collision_policy() -> CollisionPolicy {
+    let mut collision_policy = CollisionPolicy::new();
+    collision_policy.set_collides(Kind::Organism, Kind::Water, false);
+    collision_policy.set_collides(Kind::Organism, Kind::Plant, false);
+    collision_policy
+}
+
+impl Kind {
+    /// Returns whether objects of `other_kind` pass through objects
+    /// of this kind according to the [`default_collision_policy`].
+    ///
+    /// [`default_collision_policy`]: ./fn.default_collision_policy.html
+    pub fn is_passable_by(self, other_kind: Kind) -> bool {
+        !default_collision_policy().collides(self, other_kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_KINDS: [Kind; 4] = [Kind::Organism, Kind::Plant, Kind::Water, Kind::Terrain];
+
+    #[test]
+    fn organisms_pass_water_by_default() {
+        let collision_policy = default_collision_policy();
+        assert!(!collision_policy.collides(Kind::Organism, Kind::Water));
+        assert!(!collision_policy.collides(Kind::Water, Kind::Organism));
+    }
+
+    #[test]
+    fn organisms_pass_plants_by_default() {
+        assert!(!default_collision_policy().collides(Kind::Organism, Kind::Plant));
+    }
+
+    #[test]
+    fn organisms_collide_with_each_other_by_default() {
+        assert!(default_collision_policy().collides(Kind::Organism, Kind::Organism));
+    }
+
+    #[test]
+    fn everything_collides_with_terrain_by_default() {
+        let collision_policy = default_collision_policy();
+
+        for &kind in &ALL_KINDS {
+            assert!(collision_policy.collides(kind, Kind::Terrain));
+            assert!(collision_policy.collides(Kind::Terrain, kind));
+        }
+    }
+
+    #[test]
+    fn custom_policy_overrides_default() {
+        let mut collision_policy = default_collision_policy();
+
+        collision_policy.set_collides(Kind::Water, Kind::Organism, true);
+        collision_policy.set_collides(Kind::Terrain, Kind::Organism, false);
+
+        assert!(collision_policy.collides(Kind::Organism, Kind::Water));
+        assert!(!collision_policy.collides(Kind::Organism, Kind::Terrain));
+        assert!(!collision_policy.collides(Kind::Organism, Kind::Plant));
+    }
+
+    #[test]
+    fn water_is_passable_by_organisms() {
+        assert!(Kind::Water.is_passable_by(Kind::Organism));
+        assert!(!Kind::Terrain.is_passable_by(Kind::Organism));
+    }
+}
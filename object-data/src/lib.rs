@@ -14,6 +14,10 @@
 )]
 
 use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+
+mod collision_policy;
+pub use self::collision_policy::{default_collision_policy, CollisionPolicy};
 
 /// The behaviourless description of an object that has
 /// been placed inside a [`Simulation`].
@@ -24,6 +28,27 @@ pub type ObjectDescription = myelin_engine::object::ObjectDescription<Additional
 /// An object that is stored in the simulation
 pub type Object<'a> = myelin_engine::object::Object<'a, AdditionalObjectDescription>;
 
+/// An [`Object`] that can be stored in a `HashSet` or used as a key of a `HashMap`.
+///
+/// Equality is identity-based, not structural:
+/// two objects are equal if they have the same id, even if their descriptions differ.
+#[derive(Debug)]
+pub struct ObjectRef<'a>(pub Object<'a>);
+
+impl PartialEq for ObjectRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.id == other.0.id
+    }
+}
+
+impl Eq for ObjectRef<'_> {}
+
+impl Hash for ObjectRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.id.hash(state);
+    }
+}
+
 /// The data associated with an object
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AdditionalObjectDescription {
@@ -50,3 +75,77 @@ pub enum Kind {
     /// Impassable terrain
     Terrain,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AdditionalObjectDescription, Kind, Object, ObjectDescription, ObjectRef};
+    use myelin_engine::prelude::*;
+    use std::collections::HashSet;
+
+    fn object_description(location: Point) -> ObjectDescription {
+        ObjectBuilder::default()
+            .shape(
+                PolygonBuilder::default()
+                    .vertex(-5.0, -5.0)
+                    .vertex(5.0, -5.0)
+                    .vertex(5.0, 5.0)
+                    .vertex(-5.0, 5.0)
+                    .build()
+                    .unwrap(),
+            )
+            .location(location.x, location.y)
+            .mobility(Mobility::Immovable)
+            .associated_data(AdditionalObjectDescription {
+                name: None,
+                kind: Kind::Plant,
+                height: 1.0,
+            })
+            .build()
+            .unwrap()
+    }
+
+    fn mock_behavior() -> Box<dyn ObjectBehavior<AdditionalObjectDescription>> {
+        Box::new(ObjectBehaviorMock::new())
+    }
+
+    #[test]
+    fn same_object_is_only_stored_once() {
+        let behavior = mock_behavior();
+        let description = object_description(Point { x: 10.0, y: 10.0 });
+
+        let mut objects = HashSet::new();
+        for _ in 0..2 {
+            objects.insert(ObjectRef(Object {
+                id: 1,
+                description: description.clone(),
+                behavior: behavior.as_ref(),
+            }));
+        }
+
+        assert_eq!(1, objects.len());
+    }
+
+    #[test]
+    fn objects_are_compared_by_id() {
+        let behavior = mock_behavior();
+
+        let object = ObjectRef(Object {
+            id: 1,
+            description: object_description(Point { x: 10.0, y: 10.0 }),
+            behavior: behavior.as_ref(),
+        });
+        let moved_object = ObjectRef(Object {
+            id: 1,
+            description: object_description(Point { x: 20.0, y: 10.0 }),
+            behavior: behavior.as_ref(),
+        });
+        let other_object = ObjectRef(Object {
+            id: 2,
+            description: object_description(Point { x: 10.0, y: 10.0 }),
+            behavior: behavior.as_ref(),
+        });
+
+        assert_eq!(object, moved_object);
+        assert_ne!(object, other_object);
+    }
+}
@@ -45,6 +45,12 @@ pub trait Random: Debug + RandomClone {
 
     /// Returns a random floating point number in the specified range [min; max)
     fn f64_in_range(&self, min: f64, max: f64) -> f64;
+
+    /// Returns a random floating point number drawn from a normal distribution
+    /// with the given mean and standard deviation.
+    /// # Panics
+    /// Panics if `standard_deviation` is negative.
+    fn random_gaussian(&self, mean: f64, standard_deviation: f64) -> f64;
 }
 
 clone_box!(Random, RandomClone);
@@ -55,3 +61,163 @@ pub trait Shuffler<T>: Debug {
     /// Shuffles a [`Vec`] randomly
     fn shuffle(&self, values: Vec<T>) -> Vec<T>;
 }
+
+/// Selection of elements at random, built on top of [`Random`].
+/// It is implemented for every [`Random`], including `dyn Random`.
+pub trait Chooser: Random {
+    /// Returns the index of a weight, picked with a probability proportional to the weight.
+    /// # Panics
+    /// Panics if `weights` is empty, if a weight is negative or not finite,
+    /// if the weights don't sum up to a finite value or if all weights are zero.
+    fn choose_weighted(&self, weights: &[f64]) -> usize {
+        if weights.is_empty() {
+            panic!("Expected at least one weight");
+        }
+
+        if let Some(weight) = weights
+            .iter()
+            .find(|&&weight| weight < 0.0 || !weight.is_finite())
+        {
+            panic!(
+                "Expected weights to be non-negative and finite but got {}",
+                weight
+            );
+        }
+
+        let total_weight: f64 = weights.iter().sum();
+        if !total_weight.is_finite() {
+            panic!("Expected the weights to sum up to a finite value");
+        }
+
+        if total_weight <= 0.0 {
+            panic!("Expected at least one weight to be positive");
+        }
+
+        let value = self.f64_in_range(0.0, total_weight);
+        let mut upper_bound = 0.0;
+
+        for (index, weight) in weights.iter().enumerate() {
+            upper_bound += weight;
+
+            if value < upper_bound {
+                return index;
+            }
+        }
+
+        // Rounding errors might push the value past the last bound
+        weights
+            .iter()
+            .rposition(|&weight| weight > 0.0)
+            .expect("At least one weight is positive")
+    }
+
+    /// Returns a random element of `items` or `None` if `items` is empty.
+    fn choose<'a, T>(&self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.usize_in_range(0, items.len()))
+        }
+    }
+}
+
+impl<R> Chooser for R where R: Random + ?Sized {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEIGHTS: [f64; 3] = [1.0, 2.0, 1.0];
+    const TOTAL_WEIGHT: f64 = 4.0;
+
+    fn random_returning(value: f64) -> RandomMock<'static> {
+        let mut random = RandomMock::new();
+        random
+            .expect_f64_in_range(
+                |arg| arg.partial_eq(0.0),
+                |arg| arg.partial_eq(TOTAL_WEIGHT),
+            )
+            .returns(value);
+        random
+    }
+
+    #[test]
+    fn choose_weighted_picks_first_index_below_its_upper_bound() {
+        assert_eq!(0, random_returning(0.0).choose_weighted(&WEIGHTS));
+        assert_eq!(0, random_returning(0.999).choose_weighted(&WEIGHTS));
+    }
+
+    #[test]
+    fn choose_weighted_picks_next_index_at_boundary() {
+        assert_eq!(1, random_returning(1.0).choose_weighted(&WEIGHTS));
+        assert_eq!(1, random_returning(2.999).choose_weighted(&WEIGHTS));
+        assert_eq!(2, random_returning(3.0).choose_weighted(&WEIGHTS));
+    }
+
+    #[test]
+    fn choose_weighted_never_picks_zero_weight() {
+        let weights = [1.0, 0.0, 3.0, 0.0];
+
+        assert_eq!(2, random_returning(1.0).choose_weighted(&weights));
+    }
+
+    #[test]
+    fn choose_weighted_works_on_trait_object() {
+        let random: Box<dyn Random> = box random_returning(3.5);
+
+        assert_eq!(2, random.choose_weighted(&WEIGHTS));
+    }
+
+    #[should_panic]
+    #[test]
+    fn choose_weighted_panics_on_empty_weights() {
+        RandomMock::new().choose_weighted(&[]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn choose_weighted_panics_on_negative_weight() {
+        RandomMock::new().choose_weighted(&[1.0, -1.0]);
+    }
+
+    #[should_panic(expected = "Expected weights to be non-negative and finite but got NaN")]
+    #[test]
+    fn choose_weighted_panics_on_nan_weight() {
+        RandomMock::new().choose_weighted(&[1.0, std::f64::NAN]);
+    }
+
+    #[should_panic(expected = "Expected weights to be non-negative and finite but got inf")]
+    #[test]
+    fn choose_weighted_panics_on_infinite_weight() {
+        RandomMock::new().choose_weighted(&[std::f64::INFINITY, 1.0]);
+    }
+
+    #[should_panic(expected = "Expected the weights to sum up to a finite value")]
+    #[test]
+    fn choose_weighted_panics_when_weights_sum_up_to_infinity() {
+        RandomMock::new().choose_weighted(&[std::f64::MAX, std::f64::MAX]);
+    }
+
+    #[should_panic]
+    #[test]
+    fn choose_weighted_panics_when_all_weights_are_zero() {
+        RandomMock::new().choose_weighted(&[0.0, 0.0]);
+    }
+
+    #[test]
+    fn choose_returns_element_at_random_index() {
+        let mut random = RandomMock::new();
+        random
+            .expect_usize_in_range(|arg| arg.partial_eq(0), |arg| arg.partial_eq(3))
+            .returns(1);
+
+        assert_eq!(Some(&20), random.choose(&[10, 20, 30]));
+    }
+
+    #[test]
+    fn choose_returns_none_for_empty_items() {
+        let items: [i32; 0] = [];
+
+        assert_eq!(None, RandomMock::new().choose(&items));
+    }
+}
@@ -5,6 +5,7 @@ use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng, RngCore, SeedableRng};
 use rand_hc::Hc128Rng;
 use std::cell::RefCell;
+use std::f64::consts::PI;
 use std::fmt::Debug;
 use std::rc::Rc;
 
@@ -30,11 +31,25 @@ impl RandomImpl {
     }
 
     /// Constructs a new [`RandomImpl`] with the given [`Seed`].
+    ///
+    /// Two generators constructed with the same seed produce identical sequences
+    /// across all methods of [`Random`] and [`Shuffler`], as long as the methods
+    /// are called in the same order with the same arguments.
+    /// Note that clones share their source of randomness with the original.
     pub fn with_seed(seed: Seed) -> Self {
         Self {
             rng: Rc::new(RefCell::new(Hc128Rng::from_seed(seed))),
         }
     }
+
+    /// Constructs a new [`RandomImpl`] from a seed given as a single number,
+    /// e.g. in order to reproduce a simulation.
+    /// The same guarantees as for [`RandomImpl::with_seed`] apply.
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            rng: Rc::new(RefCell::new(Hc128Rng::seed_from_u64(seed))),
+        }
+    }
 }
 
 impl Default for RandomImpl {
@@ -72,6 +87,25 @@ impl Random for RandomImpl {
     }
 
     generate_random_in_range_implementations!(i32, f64, usize);
+
+    fn random_gaussian(&self, mean: f64, standard_deviation: f64) -> f64 {
+        if standard_deviation < 0.0 {
+            panic!(
+                "Expected standard deviation to be non-negative but got {}",
+                standard_deviation
+            );
+        }
+
+        // Box–Muller transform.
+        // The first sample is drawn from (0; 1] so that its logarithm is finite.
+        let mut rng = self.rng.borrow_mut();
+        let first_sample = 1.0 - rng.gen::<f64>();
+        let second_sample = rng.gen::<f64>();
+        let standard_normal_value =
+            (-2.0 * first_sample.ln()).sqrt() * (2.0 * PI * second_sample).cos();
+
+        mean + standard_deviation * standard_normal_value
+    }
 }
 
 impl<T> Shuffler<T> for RandomImpl {
@@ -406,6 +440,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn random_numbers_are_the_same_when_constructed_from_the_same_seed() {
+        const SEED: u64 = 42;
+        let first_random = RandomImpl::from_seed(SEED);
+        let second_random = RandomImpl::from_seed(SEED);
+
+        for _ in 0..100 {
+            assert_eq!(
+                first_random.f64_in_range(-1.0, 1.0).to_bits(),
+                second_random.f64_in_range(-1.0, 1.0).to_bits()
+            );
+            assert_eq!(
+                first_random.usize_in_range(0, 100),
+                second_random.usize_in_range(0, 100)
+            );
+            assert_eq!(
+                first_random.flip_coin_with_probability(0.3),
+                second_random.flip_coin_with_probability(0.3)
+            );
+        }
+    }
+
+    #[test]
+    fn random_numbers_differ_when_constructed_from_different_seeds() {
+        let first_random = RandomImpl::from_seed(1);
+        let second_random = RandomImpl::from_seed(2);
+
+        let first_numbers: Vec<_> = (0..10)
+            .map(|_| first_random.usize_in_range(0, 1000))
+            .collect();
+        let second_numbers: Vec<_> = (0..10)
+            .map(|_| second_random.usize_in_range(0, 1000))
+            .collect();
+
+        assert_ne!(first_numbers, second_numbers);
+    }
+
+    #[should_panic]
+    #[test]
+    fn panics_on_negative_standard_deviation() {
+        test_panics_on_negative_standard_deviation(random());
+    }
+
+    #[should_panic]
+    #[test]
+    fn panics_on_negative_standard_deviation_with_seed() {
+        test_panics_on_negative_standard_deviation(seeded_random());
+    }
+
+    fn test_panics_on_negative_standard_deviation(random: RandomImpl) {
+        random.random_gaussian(0.0, -1.0);
+    }
+
+    #[test]
+    fn gaussian_sample_mean_is_close_to_mean() {
+        const MEAN: f64 = 5.0;
+        const STANDARD_DEVIATION: f64 = 2.0;
+        const SAMPLE_COUNT: usize = 10_000;
+        let random = seeded_random();
+
+        let sample_mean = (0..SAMPLE_COUNT)
+            .map(|_| random.random_gaussian(MEAN, STANDARD_DEVIATION))
+            .sum::<f64>()
+            / SAMPLE_COUNT as f64;
+
+        assert!(
+            (sample_mean - MEAN).abs() < 0.1,
+            "Sample mean was {}",
+            sample_mean
+        );
+    }
+
+    #[test]
+    fn gaussian_with_zero_standard_deviation_returns_mean() {
+        let random = seeded_random();
+
+        for _ in 0..10 {
+            assert_eq!(
+                1.5_f64.to_bits(),
+                random.random_gaussian(1.5, 0.0).to_bits()
+            );
+        }
+    }
+
     fn random() -> RandomImpl {
         RandomImpl::default()
     }
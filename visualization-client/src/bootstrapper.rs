@@ -1,10 +1,15 @@
 //! Entrypoint for the crate,
 //! used to setup the entire visualization
 
+use crate::camera::SharedCamera;
+use crate::clock::PerformanceClock;
 use crate::controller::ControllerImpl;
+use crate::delta_player::DeltaPlayer;
 use crate::input_handler::InputHandler;
-use crate::presenter::{CanvasPresenter, DeltaApplierImpl, GlobalPolygonTranslatorImpl};
-use crate::view::CanvasView;
+use crate::presenter::{
+    CanvasPresenter, ColorScalar, DeltaApplierImpl, GlobalPolygonTranslatorImpl,
+};
+use crate::view::{is_valid_grid_spacing, CanvasView};
 use myelin_visualization_core::serialization::BincodeDeserializer;
 use std::panic::{set_hook, PanicInfo};
 use wasm_bindgen::prelude::*;
@@ -18,18 +23,113 @@ fn panic_hook(info: &PanicInfo<'_>) {
 /// and returns a [`InputHandler`] that one can use to signal
 /// user interaction. This function is intended to be called from
 /// JavaScript or, preferably, TypeScript.
+/// If `grid_spacing` is set, a coordinate grid with the given spacing
+/// in world units is drawn behind the objects. It must be a positive number.
+/// If `color_scalar` is set to either `"speed"` or `"height"`, objects are tinted
+/// from red to green by that value instead of being colored by their kind.
 ///
 /// [`InputHandler`]: ./struct.InputHandler.html
 #[wasm_bindgen]
-pub fn init(canvas: &HtmlCanvasElement) -> InputHandler {
+pub fn init(
+    canvas: &HtmlCanvasElement,
+    grid_spacing: Option<f64>,
+    color_scalar: Option<String>,
+) -> InputHandler {
     set_hook(box panic_hook);
 
-    InputHandler::new(box ControllerImpl::new(
-        box CanvasPresenter::new(
-            box CanvasView::new(canvas),
-            box DeltaApplierImpl::new(),
-            box GlobalPolygonTranslatorImpl::new(),
-        ),
-        box BincodeDeserializer::default(),
+    InputHandler::new(box create_controller(
+        canvas,
+        validate_grid_spacing(grid_spacing),
+        color_scalar
+            .as_ref()
+            .map(|color_scalar| parse_color_scalar(color_scalar)),
     ))
 }
+
+/// Initializes all components like [`init()`], but instead of
+/// returning an [`InputHandler`] for live deltas, a [`DeltaPlayer`] is returned
+/// that replays the given recording made by the visualization server.
+///
+/// [`init()`]: ./fn.init.html
+/// [`InputHandler`]: ./struct.InputHandler.html
+/// [`DeltaPlayer`]: ./struct.DeltaPlayer.html
+#[wasm_bindgen]
+pub fn init_player(
+    canvas: &HtmlCanvasElement,
+    recording: &[u8],
+    grid_spacing: Option<f64>,
+    color_scalar: Option<String>,
+) -> DeltaPlayer {
+    set_hook(box panic_hook);
+
+    let controller = create_controller(
+        canvas,
+        validate_grid_spacing(grid_spacing),
+        color_scalar
+            .as_ref()
+            .map(|color_scalar| parse_color_scalar(color_scalar)),
+    );
+
+    DeltaPlayer::try_new(box controller, recording)
+        .unwrap_or_else(|err| wasm_bindgen::throw_str(&format!("{}", err)))
+}
+
+/// Arbitrary value
+const MAX_TINTED_SPEED: f64 = 50.0;
+
+/// The height of the highest objects placed by the world generator
+const MAX_TINTED_HEIGHT: f64 = 10.0;
+
+/// Objects smaller than this many pixels are drawn as rectangles
+const LEVEL_OF_DETAIL_THRESHOLD: f64 = 3.0;
+
+fn validate_grid_spacing(grid_spacing: Option<f64>) -> Option<f64> {
+    match grid_spacing {
+        Some(grid_spacing) if !is_valid_grid_spacing(grid_spacing) => {
+            wasm_bindgen::throw_str(&format!(
+                "Grid spacing must be a positive number, got {}",
+                grid_spacing
+            ))
+        }
+        grid_spacing => grid_spacing,
+    }
+}
+
+fn parse_color_scalar(color_scalar: &str) -> ColorScalar {
+    match color_scalar {
+        "speed" => ColorScalar::Speed {
+            max_speed: MAX_TINTED_SPEED,
+        },
+        "height" => ColorScalar::Height {
+            max_height: MAX_TINTED_HEIGHT,
+        },
+        _ => wasm_bindgen::throw_str(&format!("Unknown color scalar {:?}", color_scalar)),
+    }
+}
+
+fn create_controller(
+    canvas: &HtmlCanvasElement,
+    grid_spacing: Option<f64>,
+    color_scalar: Option<ColorScalar>,
+) -> ControllerImpl {
+    let camera = SharedCamera::default();
+    let view = CanvasView::new(canvas, grid_spacing, box PerformanceClock::new())
+        .with_camera(camera.clone());
+    let presenter = CanvasPresenter::new(
+        box view,
+        box DeltaApplierImpl::new(),
+        box GlobalPolygonTranslatorImpl::new(),
+    )
+    .with_camera(camera)
+    .with_level_of_detail_threshold(LEVEL_OF_DETAIL_THRESHOLD);
+    let presenter = match color_scalar {
+        Some(color_scalar) => presenter.with_color_scalar(color_scalar),
+        None => presenter,
+    };
+
+    ControllerImpl::new(
+        box presenter,
+        box BincodeDeserializer::default(),
+        box PerformanceClock::new(),
+    )
+}
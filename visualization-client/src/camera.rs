@@ -0,0 +1,81 @@
+//! Transformation between screen and world coordinates
+
+use myelin_engine::geometry::Point;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A [`Camera`] that is shared between the presenter and the view,
+/// so that hit-testing always uses the same pan and zoom as the drawing.
+pub(crate) type SharedCamera = Rc<RefCell<Camera>>;
+
+/// Describes which part of the world is currently visible on the canvas.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Camera {
+    /// The world coordinate displayed in the upper left corner of the canvas
+    pub(crate) offset: Point,
+
+    /// Screen pixels per world unit
+    pub(crate) zoom: f64,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            offset: Point { x: 0.0, y: 0.0 },
+            zoom: 1.0,
+        }
+    }
+}
+
+impl Camera {
+    /// Maps a point on the canvas to the world coordinate displayed at it
+    pub(crate) fn to_world(&self, screen_point: Point) -> Point {
+        Point {
+            x: screen_point.x / self.zoom + self.offset.x,
+            y: screen_point.y / self.zoom + self.offset.y,
+        }
+    }
+
+    /// Maps a world coordinate to the point on the canvas it is displayed at
+    pub(crate) fn to_screen(&self, world_point: Point) -> Point {
+        Point {
+            x: (world_point.x - self.offset.x) * self.zoom,
+            y: (world_point.y - self.offset.y) * self.zoom,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Camera {
+        Camera {
+            offset: Point { x: 10.0, y: 20.0 },
+            zoom: 2.0,
+        }
+    }
+
+    #[test]
+    fn default_camera_does_not_transform() {
+        let point = Point { x: 3.0, y: 4.0 };
+        assert_eq!(point, Camera::default().to_world(point));
+        assert_eq!(point, Camera::default().to_screen(point));
+    }
+
+    #[test]
+    fn to_world_applies_zoom_and_offset() {
+        let world_point = camera().to_world(Point { x: 4.0, y: 6.0 });
+        assert_eq!(Point { x: 12.0, y: 23.0 }, world_point);
+    }
+
+    #[test]
+    fn to_screen_is_inverse_of_to_world() {
+        let screen_point = Point { x: 4.0, y: 6.0 };
+        let camera = camera();
+        assert_eq!(
+            screen_point,
+            camera.to_screen(camera.to_world(screen_point))
+        );
+    }
+}
@@ -0,0 +1,29 @@
+//! Time measurement using the browser's high resolution timer
+
+use crate::controller::Clock;
+use std::time::Duration;
+use web_sys::Performance;
+
+/// A [`Clock`] backed by `window.performance`
+#[derive(Debug)]
+pub(crate) struct PerformanceClock {
+    performance: Performance,
+}
+
+impl PerformanceClock {
+    pub(crate) fn new() -> Self {
+        let performance = web_sys::window()
+            .expect("No window available")
+            .performance()
+            .expect("No performance available");
+
+        Self { performance }
+    }
+}
+
+impl Clock for PerformanceClock {
+    fn now(&self) -> Duration {
+        const MILLISECONDS_PER_SECOND: f64 = 1000.0;
+        Duration::from_secs_f64(self.performance.now() / MILLISECONDS_PER_SECOND)
+    }
+}
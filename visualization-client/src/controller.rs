@@ -1,33 +1,72 @@
+use self::frame_timer::FrameTimer;
 use crate::input_handler::Controller;
 use crate::presenter;
+use myelin_engine::prelude::*;
 use myelin_object_data::{AdditionalObjectDescription, ObjectDescription};
+use myelin_visualization_core::framing::{split_message, MessageKind};
 use myelin_visualization_core::serialization::ViewModelDeserializer;
+use myelin_visualization_core::simulation_stats::SimulationStats;
 use myelin_visualization_core::view_model_delta::{
     ObjectDelta, ObjectDescriptionDelta, ViewModelDelta,
 };
 use std::error::Error;
 use std::fmt;
+use std::time::Duration;
+
+mod frame_timer;
 
 pub(crate) trait Presenter: fmt::Debug {
     fn present_delta(&mut self, delta: presenter::ViewModelDelta) -> Result<(), Box<dyn Error>>;
+    fn present_fps(&mut self, fps: f64);
+    fn present_simulation_stats(&mut self, simulation_stats: SimulationStats);
+    fn present_animation_frame(&mut self);
+    /// Returns whether the presented state got out of sync and a full snapshot is needed.
+    /// Resets the request, so that it is only returned once.
+    fn take_resync_request(&mut self) -> bool;
+    fn select_object_at(&mut self, screen_location: Point) -> Option<Id>;
+}
+
+/// A monotonic time source
+pub(crate) trait Clock: fmt::Debug {
+    /// The time elapsed since an arbitrary, but fixed point in time
+    fn now(&self) -> Duration;
 }
 
 #[derive(Debug)]
 pub(crate) struct ControllerImpl {
     presenter: Box<dyn Presenter>,
     view_model_deserializer: Box<dyn ViewModelDeserializer>,
+    clock: Box<dyn Clock>,
+    frame_timer: FrameTimer,
+    last_frame_time: Option<Duration>,
 }
 
 impl Controller for ControllerImpl {
     fn on_message(&mut self, message: &[u8]) -> Result<(), Box<dyn Error>> {
-        let view_model_delta = self
-            .view_model_deserializer
-            .deserialize_view_model_delta(message)?;
+        let (message_kind, payload) = split_message(message)?;
 
-        self.presenter
-            .present_delta(translate_delta(view_model_delta))?;
+        match message_kind {
+            MessageKind::ViewModelDelta => self.on_view_model_delta(payload),
+            MessageKind::SimulationStats => self.on_simulation_stats(payload),
+        }
+    }
 
-        Ok(())
+    fn on_animation_frame(&mut self) {
+        self.record_frame();
+
+        if let Some(fps) = self.fps() {
+            self.presenter.present_fps(fps);
+        }
+
+        self.presenter.present_animation_frame();
+    }
+
+    fn on_click(&mut self, x: f64, y: f64) -> Option<Id> {
+        self.presenter.select_object_at(Point { x, y })
+    }
+
+    fn take_resync_request(&mut self) -> bool {
+        self.presenter.take_resync_request()
     }
 }
 
@@ -35,11 +74,51 @@ impl ControllerImpl {
     pub(crate) fn new(
         presenter: Box<dyn Presenter>,
         view_model_deserializer: Box<dyn ViewModelDeserializer>,
+        clock: Box<dyn Clock>,
     ) -> Self {
         Self {
             presenter,
             view_model_deserializer,
+            clock,
+            frame_timer: FrameTimer::new(),
+            last_frame_time: None,
+        }
+    }
+
+    /// The rolling average of rendered animation frames per second
+    pub(crate) fn fps(&self) -> Option<f64> {
+        self.frame_timer.fps()
+    }
+
+    fn on_view_model_delta(&mut self, payload: &[u8]) -> Result<(), Box<dyn Error>> {
+        let view_model_delta = self
+            .view_model_deserializer
+            .deserialize_view_model_delta(payload)?;
+
+        self.presenter
+            .present_delta(translate_delta(view_model_delta))?;
+
+        Ok(())
+    }
+
+    fn on_simulation_stats(&mut self, payload: &[u8]) -> Result<(), Box<dyn Error>> {
+        let simulation_stats = self
+            .view_model_deserializer
+            .deserialize_simulation_stats(payload)?;
+
+        self.presenter.present_simulation_stats(simulation_stats);
+
+        Ok(())
+    }
+
+    fn record_frame(&mut self) {
+        let now = self.clock.now();
+
+        if let Some(last_frame_time) = self.last_frame_time {
+            self.frame_timer.record_frame(now - last_frame_time);
         }
+
+        self.last_frame_time = Some(now);
     }
 }
 
@@ -125,22 +204,41 @@ mod tests {
     use maplit::hashmap;
     use myelin_engine::geometry::*;
     use myelin_object_data::Kind;
+    use myelin_visualization_core::framing::frame_message;
+    use myelin_visualization_core::serialization::BincodeDeserializer;
     use myelin_visualization_core::view_model_delta::{ObjectDelta, ObjectDescriptionDelta};
     use std::cell::RefCell;
+    use std::collections::VecDeque;
     use std::error::Error;
     use std::thread::panicking;
 
-    #[derive(Debug)]
+    #[derive(Debug, Default)]
     struct PresenterMock {
-        expected_view_model_delta: presenter::ViewModelDelta,
+        expected_view_model_delta: Option<presenter::ViewModelDelta>,
         present_delta_was_called: RefCell<bool>,
+        presented_fps: RefCell<Vec<f64>>,
+        expected_simulation_stats: Option<SimulationStats>,
+        present_simulation_stats_was_called: RefCell<bool>,
     }
 
     impl PresenterMock {
         fn new(expected_view_model_delta: presenter::ViewModelDelta) -> Self {
             Self {
                 present_delta_was_called: RefCell::new(false),
-                expected_view_model_delta,
+                expected_view_model_delta: Some(expected_view_model_delta),
+                presented_fps: RefCell::new(Vec::new()),
+                expected_simulation_stats: None,
+                present_simulation_stats_was_called: RefCell::new(false),
+            }
+        }
+
+        fn with_expected_simulation_stats(expected_simulation_stats: SimulationStats) -> Self {
+            Self {
+                present_delta_was_called: RefCell::new(false),
+                expected_view_model_delta: None,
+                presented_fps: RefCell::new(Vec::new()),
+                expected_simulation_stats: Some(expected_simulation_stats),
+                present_simulation_stats_was_called: RefCell::new(false),
             }
         }
     }
@@ -151,35 +249,116 @@ mod tests {
             delta: presenter::ViewModelDelta,
         ) -> Result<(), Box<dyn Error>> {
             *self.present_delta_was_called.borrow_mut() = true;
-            assert_eq!(self.expected_view_model_delta, delta);
+            let expected_view_model_delta = self
+                .expected_view_model_delta
+                .as_ref()
+                .expect("present_delta() was called unexpectedly");
+            assert_eq!(*expected_view_model_delta, delta);
             Ok(())
         }
+
+        fn present_fps(&mut self, fps: f64) {
+            self.presented_fps.borrow_mut().push(fps);
+        }
+
+        fn present_simulation_stats(&mut self, simulation_stats: SimulationStats) {
+            *self.present_simulation_stats_was_called.borrow_mut() = true;
+            let expected_simulation_stats = self
+                .expected_simulation_stats
+                .as_ref()
+                .expect("present_simulation_stats() was called unexpectedly");
+            assert_eq!(*expected_simulation_stats, simulation_stats);
+        }
+
+        fn present_animation_frame(&mut self) {}
+
+        fn take_resync_request(&mut self) -> bool {
+            panic!("take_resync_request() was called unexpectedly")
+        }
+
+        fn select_object_at(&mut self, _screen_location: Point) -> Option<Id> {
+            panic!("select_object_at() was called unexpectedly")
+        }
     }
 
     impl Drop for PresenterMock {
         fn drop(&mut self) {
-            if !panicking() {
+            if !panicking() && self.expected_view_model_delta.is_some() {
                 assert!(
                     *self.present_delta_was_called.borrow(),
                     "present_delta() was never called, but was expected"
                 );
             }
+
+            if !panicking() && self.expected_simulation_stats.is_some() {
+                assert!(
+                    *self.present_simulation_stats_was_called.borrow(),
+                    "present_simulation_stats() was never called, but was expected"
+                );
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct ClockMock {
+        times: RefCell<VecDeque<Duration>>,
+    }
+
+    impl ClockMock {
+        fn new(times: Vec<Duration>) -> Self {
+            Self {
+                times: RefCell::new(times.into()),
+            }
+        }
+    }
+
+    impl Clock for ClockMock {
+        fn now(&self) -> Duration {
+            self.times
+                .borrow_mut()
+                .pop_front()
+                .expect("Unexpected call to now()")
+        }
+    }
+
+    impl Drop for ClockMock {
+        fn drop(&mut self) {
+            if !panicking() {
+                assert!(
+                    self.times.borrow().is_empty(),
+                    "now() was called less often than expected"
+                );
+            }
         }
     }
 
     #[derive(Debug)]
     struct ViewModelDeserializerMock {
         expected_data: Vec<u8>,
-        view_model_delta: ViewModelDelta,
-        deserialize_view_model_was_called: RefCell<bool>,
+        view_model_delta: Option<ViewModelDelta>,
+        simulation_stats: Option<SimulationStats>,
+        deserialize_was_called: RefCell<bool>,
     }
 
     impl ViewModelDeserializerMock {
         fn new(expected_data: Vec<u8>, view_model_delta: ViewModelDelta) -> Self {
             Self {
                 expected_data,
-                view_model_delta,
-                deserialize_view_model_was_called: RefCell::new(false),
+                view_model_delta: Some(view_model_delta),
+                simulation_stats: None,
+                deserialize_was_called: RefCell::new(false),
+            }
+        }
+
+        fn with_simulation_stats(
+            expected_data: Vec<u8>,
+            simulation_stats: SimulationStats,
+        ) -> Self {
+            Self {
+                expected_data,
+                view_model_delta: None,
+                simulation_stats: Some(simulation_stats),
+                deserialize_was_called: RefCell::new(false),
             }
         }
     }
@@ -189,9 +368,24 @@ mod tests {
             &self,
             buf: &[u8],
         ) -> Result<ViewModelDelta, Box<dyn Error>> {
-            *self.deserialize_view_model_was_called.borrow_mut() = true;
+            *self.deserialize_was_called.borrow_mut() = true;
+            assert_eq!(self.expected_data, buf);
+            Ok(self
+                .view_model_delta
+                .clone()
+                .expect("deserialize_view_model_delta() was called unexpectedly"))
+        }
+
+        fn deserialize_simulation_stats(
+            &self,
+            buf: &[u8],
+        ) -> Result<SimulationStats, Box<dyn Error>> {
+            *self.deserialize_was_called.borrow_mut() = true;
             assert_eq!(self.expected_data, buf);
-            Ok(self.view_model_delta.clone())
+            Ok(self
+                .simulation_stats
+                .clone()
+                .expect("deserialize_simulation_stats() was called unexpectedly"))
         }
     }
 
@@ -199,8 +393,8 @@ mod tests {
         fn drop(&mut self) {
             if !panicking() {
                 assert!(
-                    *self.deserialize_view_model_was_called.borrow(),
-                    "deserialize_view_model() was never called, but was expected"
+                    *self.deserialize_was_called.borrow(),
+                    "The deserializer was never called, but was expected"
                 );
             }
         }
@@ -261,8 +455,56 @@ mod tests {
         let view_model_deserializer =
             ViewModelDeserializerMock::new(data.clone(), view_model_delta.clone());
         let presenter = PresenterMock::new(presenter_view_model_delta.clone());
-        let mut controller = ControllerImpl::new(box presenter, box view_model_deserializer);
+        let clock = ClockMock::new(Vec::new());
+        let mut controller =
+            ControllerImpl::new(box presenter, box view_model_deserializer, box clock);
+
+        controller
+            .on_message(&frame_message(MessageKind::ViewModelDelta, &data))
+            .unwrap();
+        assert_eq!(None, controller.fps());
+    }
+
+    #[test]
+    fn fps_is_calculated_from_time_between_animation_frames() {
+        let clock = ClockMock::new(vec![
+            Duration::from_millis(100),
+            Duration::from_millis(120),
+            Duration::from_millis(160),
+        ]);
+        let mut controller = ControllerImpl::new(
+            box PresenterMock::default(),
+            box BincodeDeserializer::default(),
+            box clock,
+        );
+
+        controller.on_animation_frame();
+        controller.on_animation_frame();
+        controller.on_animation_frame();
+
+        let fps = controller.fps().unwrap();
+        assert!((fps - 100.0 / 3.0).abs() < 1e-9, "got {} fps", fps);
+    }
+
+    #[test]
+    fn deserializes_and_presents_simulation_stats() {
+        let data = vec![7, 3, 12];
+        let simulation_stats = SimulationStats {
+            step: 42,
+            counts_by_kind: hashmap! { Kind::Plant => 5 },
+        };
+
+        let view_model_deserializer = ViewModelDeserializerMock::with_simulation_stats(
+            data.clone(),
+            simulation_stats.clone(),
+        );
+        let presenter = PresenterMock::with_expected_simulation_stats(simulation_stats);
+        let clock = ClockMock::new(Vec::new());
+        let mut controller =
+            ControllerImpl::new(box presenter, box view_model_deserializer, box clock);
 
-        controller.on_message(&data).unwrap();
+        controller
+            .on_message(&frame_message(MessageKind::SimulationStats, &data))
+            .unwrap();
     }
 }
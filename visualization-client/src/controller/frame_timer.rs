@@ -0,0 +1,90 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// The number of most recent frames the average is calculated over
+const FRAME_SAMPLE_COUNT: usize = 60;
+
+/// Keeps track of the durations of the most recent frames.
+#[derive(Debug, Default)]
+pub(crate) struct FrameTimer {
+    frame_durations: VecDeque<Duration>,
+}
+
+impl FrameTimer {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the duration of a frame, discarding the oldest
+    /// sample if more than [`FRAME_SAMPLE_COUNT`] have been recorded.
+    pub(crate) fn record_frame(&mut self, frame_duration: Duration) {
+        if self.frame_durations.len() == FRAME_SAMPLE_COUNT {
+            self.frame_durations.pop_front();
+        }
+
+        self.frame_durations.push_back(frame_duration);
+    }
+
+    /// The rolling average of frames per second.
+    /// Returns `None` if no frames have been recorded yet
+    /// or if the recorded frames took no time at all.
+    pub(crate) fn fps(&self) -> Option<f64> {
+        let total_duration: Duration = self.frame_durations.iter().sum();
+        let total_seconds = total_duration.as_secs_f64();
+
+        if total_seconds > 0.0 {
+            Some(self.frame_durations.len() as f64 / total_seconds)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_fps_eq(expected: f64, frame_timer: &FrameTimer) {
+        const EPSILON: f64 = 1e-9;
+        let actual = frame_timer.fps().expect("No fps available");
+        assert!(
+            (expected - actual).abs() < EPSILON,
+            "expected {} fps, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn fps_is_none_without_frames() {
+        assert_eq!(None, FrameTimer::new().fps());
+    }
+
+    #[test]
+    fn fps_is_calculated_from_single_frame() {
+        let mut frame_timer = FrameTimer::new();
+        frame_timer.record_frame(Duration::from_millis(20));
+        assert_fps_eq(50.0, &frame_timer);
+    }
+
+    #[test]
+    fn fps_is_averaged_over_frames() {
+        let mut frame_timer = FrameTimer::new();
+        frame_timer.record_frame(Duration::from_millis(10));
+        frame_timer.record_frame(Duration::from_millis(30));
+        frame_timer.record_frame(Duration::from_millis(20));
+        assert_fps_eq(50.0, &frame_timer);
+    }
+
+    #[test]
+    fn oldest_frames_are_discarded() {
+        let mut frame_timer = FrameTimer::new();
+        frame_timer.record_frame(Duration::from_secs(10));
+
+        for _ in 0..FRAME_SAMPLE_COUNT {
+            frame_timer.record_frame(Duration::from_millis(40));
+        }
+
+        assert_fps_eq(25.0, &frame_timer);
+    }
+}
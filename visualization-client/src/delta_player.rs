@@ -0,0 +1,324 @@
+//! Playback of delta streams recorded by the visualization server
+
+use crate::input_handler::Controller;
+use myelin_visualization_core::recording::{
+    read_recorded_deltas, RecordedDelta, TruncatedRecordingError,
+};
+use std::collections::VecDeque;
+use std::time::Duration;
+use wasm_bindgen::prelude::*;
+
+/// Feeds the deltas of a recording to the visualization at the recorded cadence.
+/// Created by [`init_player()`].
+///
+/// Resync requests of the presenter are ignored, as a recording cannot provide
+/// a full snapshot on demand. Recordings should therefore start with
+/// the first delta sent to a client, which contains every object.
+///
+/// [`init_player()`]: ./fn.init_player.html
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct DeltaPlayer {
+    controller: Box<dyn Controller>,
+    recorded_deltas: VecDeque<RecordedDelta>,
+}
+
+impl DeltaPlayer {
+    pub(crate) fn try_new(
+        controller: Box<dyn Controller>,
+        recording: &[u8],
+    ) -> Result<Self, TruncatedRecordingError> {
+        Ok(Self {
+            controller,
+            recorded_deltas: read_recorded_deltas(recording)?.into(),
+        })
+    }
+
+    fn play_until(&mut self, elapsed_time: Duration) {
+        while self
+            .recorded_deltas
+            .front()
+            .map(|recorded_delta| recorded_delta.timestamp <= elapsed_time)
+            .unwrap_or(false)
+        {
+            let recorded_delta = self.recorded_deltas.pop_front().unwrap();
+
+            if let Err(err) = self.controller.on_message(&recorded_delta.serialized_delta) {
+                wasm_bindgen::throw_str(&format!("{}", err));
+            }
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl DeltaPlayer {
+    /// Plays all deltas that were recorded within the given time
+    /// since the start of the recording and animates the objects in between them.
+    /// This should be called from JS on every animation frame.
+    /// Negative times are treated as the start of the recording,
+    /// times that are not finite only animate the objects.
+    ///
+    /// # Examples
+    ///
+    /// ```ts
+    /// const step = (timestamp: number) => {
+    ///     player.advance(timestamp - start)
+    ///     if (!player.is_finished()) {
+    ///         window.requestAnimationFrame(step)
+    ///     }
+    /// }
+    /// ```
+    pub fn advance(&mut self, elapsed_milliseconds: f64) {
+        if let Some(elapsed_time) = milliseconds_to_duration(elapsed_milliseconds) {
+            self.play_until(elapsed_time);
+        }
+        self.controller.on_animation_frame();
+    }
+
+    /// Returns `true` once all recorded deltas have been played.
+    pub fn is_finished(&self) -> bool {
+        self.recorded_deltas.is_empty()
+    }
+}
+
+/// Arbitrary value, far longer than any recording
+const MAX_ELAPSED_SECONDS: f64 = 1_000_000_000.0;
+
+/// Converts a time passed from JS into a [`Duration`], which can neither be negative nor `NaN`.
+/// Negative times are clamped to zero, times that are not finite are rejected.
+fn milliseconds_to_duration(milliseconds: f64) -> Option<Duration> {
+    const MILLISECONDS_PER_SECOND: f64 = 1000.0;
+
+    if milliseconds.is_finite() {
+        let seconds = (milliseconds / MILLISECONDS_PER_SECOND)
+            .max(0.0)
+            .min(MAX_ELAPSED_SECONDS);
+        Some(Duration::from_secs_f64(seconds))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::{Clock, ControllerImpl, Presenter};
+    use crate::presenter;
+    use maplit::hashmap;
+    use myelin_engine::prelude::*;
+    use myelin_visualization_core::framing::{frame_message, MessageKind};
+    use myelin_visualization_core::recording::write_recorded_delta;
+    use myelin_visualization_core::serialization::{
+        BincodeDeserializer, BincodeSerializer, ViewModelSerializer,
+    };
+    use myelin_visualization_core::simulation_stats::SimulationStats;
+    use myelin_visualization_core::view_model_delta::{self, ViewModelDelta};
+    use std::cell::RefCell;
+    use std::error::Error;
+    use std::rc::Rc;
+
+    #[derive(Debug, Default)]
+    struct ControllerMock {
+        received_messages: Rc<RefCell<Vec<Vec<u8>>>>,
+    }
+
+    impl Controller for ControllerMock {
+        fn on_message(&mut self, message: &[u8]) -> Result<(), Box<dyn Error>> {
+            self.received_messages.borrow_mut().push(message.to_vec());
+            Ok(())
+        }
+
+        fn on_animation_frame(&mut self) {}
+
+        fn on_click(&mut self, _x: f64, _y: f64) -> Option<Id> {
+            panic!("on_click() was called unexpectedly")
+        }
+
+        fn take_resync_request(&mut self) -> bool {
+            panic!("take_resync_request() was called unexpectedly")
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct PresenterMock {
+        presented_deltas: Rc<RefCell<Vec<presenter::ViewModelDelta>>>,
+    }
+
+    impl Presenter for PresenterMock {
+        fn present_delta(
+            &mut self,
+            delta: presenter::ViewModelDelta,
+        ) -> Result<(), Box<dyn Error>> {
+            self.presented_deltas.borrow_mut().push(delta);
+            Ok(())
+        }
+
+        fn present_fps(&mut self, _fps: f64) {}
+
+        fn present_simulation_stats(&mut self, _simulation_stats: SimulationStats) {
+            panic!("present_simulation_stats() was called unexpectedly")
+        }
+
+        fn present_animation_frame(&mut self) {}
+
+        fn take_resync_request(&mut self) -> bool {
+            panic!("take_resync_request() was called unexpectedly")
+        }
+
+        fn select_object_at(&mut self, _screen_location: Point) -> Option<Id> {
+            panic!("select_object_at() was called unexpectedly")
+        }
+    }
+
+    #[derive(Debug)]
+    struct ClockStub;
+
+    impl Clock for ClockStub {
+        fn now(&self) -> Duration {
+            Duration::default()
+        }
+    }
+
+    fn recording(recorded_deltas: &[RecordedDelta]) -> Vec<u8> {
+        let mut recording = Vec::new();
+
+        for recorded_delta in recorded_deltas {
+            write_recorded_delta(&mut recording, recorded_delta).unwrap();
+        }
+
+        recording
+    }
+
+    fn recorded_deltas() -> Vec<RecordedDelta> {
+        (0..5)
+            .map(|index| RecordedDelta {
+                timestamp: Duration::from_millis(index * 100),
+                serialized_delta: vec![index as u8; 3],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn replays_all_recorded_deltas_in_order() {
+        let controller = ControllerMock::default();
+        let received_messages = controller.received_messages.clone();
+        let mut player =
+            DeltaPlayer::try_new(box controller, &recording(&recorded_deltas())).unwrap();
+
+        player.advance(1000.0);
+
+        let expected_messages: Vec<_> = recorded_deltas()
+            .into_iter()
+            .map(|recorded_delta| recorded_delta.serialized_delta)
+            .collect();
+        assert_eq!(expected_messages, *received_messages.borrow());
+        assert!(player.is_finished());
+    }
+
+    #[test]
+    fn replays_deltas_at_recorded_cadence() {
+        let controller = ControllerMock::default();
+        let received_messages = controller.received_messages.clone();
+        let mut player =
+            DeltaPlayer::try_new(box controller, &recording(&recorded_deltas())).unwrap();
+
+        player.advance(0.0);
+        assert_eq!(1, received_messages.borrow().len());
+
+        player.advance(150.0);
+        assert_eq!(2, received_messages.borrow().len());
+
+        player.advance(300.0);
+        assert_eq!(4, received_messages.borrow().len());
+        assert!(!player.is_finished());
+    }
+
+    #[test]
+    fn negative_time_plays_deltas_recorded_at_start() {
+        let controller = ControllerMock::default();
+        let received_messages = controller.received_messages.clone();
+        let mut player =
+            DeltaPlayer::try_new(box controller, &recording(&recorded_deltas())).unwrap();
+
+        player.advance(-1.0);
+
+        assert_eq!(1, received_messages.borrow().len());
+    }
+
+    #[test]
+    fn time_that_is_not_finite_is_ignored() {
+        let controller = ControllerMock::default();
+        let received_messages = controller.received_messages.clone();
+        let mut player =
+            DeltaPlayer::try_new(box controller, &recording(&recorded_deltas())).unwrap();
+
+        player.advance(std::f64::NAN);
+        player.advance(std::f64::INFINITY);
+        assert!(received_messages.borrow().is_empty());
+
+        player.advance(150.0);
+        assert_eq!(2, received_messages.borrow().len());
+    }
+
+    #[test]
+    fn huge_time_plays_all_deltas() {
+        let controller = ControllerMock::default();
+        let received_messages = controller.received_messages.clone();
+        let mut player =
+            DeltaPlayer::try_new(box controller, &recording(&recorded_deltas())).unwrap();
+
+        player.advance(std::f64::MAX);
+
+        assert_eq!(5, received_messages.borrow().len());
+        assert!(player.is_finished());
+    }
+
+    #[test]
+    fn replaying_recorded_deltas_presents_them_in_order() {
+        let view_model_deltas: Vec<ViewModelDelta> = (0..5)
+            .map(|id| hashmap! { id => view_model_delta::ObjectDelta::Deleted })
+            .collect();
+        let serializer = BincodeSerializer::default();
+        let recorded_deltas: Vec<_> = view_model_deltas
+            .iter()
+            .enumerate()
+            .map(|(index, view_model_delta)| {
+                let payload = serializer
+                    .serialize_view_model_delta(view_model_delta)
+                    .unwrap();
+                RecordedDelta {
+                    timestamp: Duration::from_millis(index as u64 * 100),
+                    serialized_delta: frame_message(MessageKind::ViewModelDelta, &payload),
+                }
+            })
+            .collect();
+
+        let presenter = PresenterMock::default();
+        let presented_deltas = presenter.presented_deltas.clone();
+        let controller = ControllerImpl::new(
+            box presenter,
+            box BincodeDeserializer::default(),
+            box ClockStub,
+        );
+        let mut player =
+            DeltaPlayer::try_new(box controller, &recording(&recorded_deltas)).unwrap();
+
+        player.advance(1000.0);
+
+        let expected_deltas: Vec<presenter::ViewModelDelta> = (0..5)
+            .map(|id| hashmap! { id => presenter::ObjectDelta::Deleted })
+            .collect();
+        assert_eq!(expected_deltas, *presented_deltas.borrow());
+        assert!(player.is_finished());
+    }
+
+    #[test]
+    fn truncated_recording_is_rejected() {
+        let mut recording = recording(&recorded_deltas());
+        recording.pop();
+
+        let result = DeltaPlayer::try_new(box ControllerMock::default(), &recording);
+
+        assert_eq!(Some(TruncatedRecordingError), result.err());
+    }
+}
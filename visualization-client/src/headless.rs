@@ -0,0 +1,58 @@
+//! Rendering of recordings without a browser,
+//! e.g. for generating time-lapse videos
+
+use crate::controller::{Clock, ControllerImpl};
+use crate::input_handler::Controller;
+use crate::presenter::{CanvasPresenter, DeltaApplierImpl, GlobalPolygonTranslatorImpl};
+use crate::view::PngView;
+use myelin_visualization_core::recording::read_recorded_deltas;
+use myelin_visualization_core::serialization::BincodeDeserializer;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Replays a recording made by the visualization server and writes every
+/// frame as a PNG file of `width` x `height` pixels into `output_directory`.
+/// The frames are named `frame_000000.png`, `frame_000001.png` and so on.
+///
+/// # Errors
+/// Returns an error if the recording is invalid or if `output_directory` cannot be created.
+///
+/// # Panics
+/// Panics if a frame cannot be written.
+pub fn render_recording_to_png(
+    recording: &[u8],
+    width: u32,
+    height: u32,
+    output_directory: &Path,
+) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(output_directory)?;
+
+    let mut controller = ControllerImpl::new(
+        box CanvasPresenter::new(
+            box PngView::new(width, height, output_directory.to_owned()),
+            box DeltaApplierImpl::new(),
+            box GlobalPolygonTranslatorImpl::new(),
+        ),
+        box BincodeDeserializer::default(),
+        box StoppedClock,
+    );
+
+    for recorded_delta in read_recorded_deltas(recording)? {
+        controller.on_message(&recorded_delta.serialized_delta)?;
+    }
+
+    Ok(())
+}
+
+/// A [`Clock`] that never advances, as the frame rate
+/// is not of interest when rendering into files
+#[derive(Debug)]
+struct StoppedClock;
+
+impl Clock for StoppedClock {
+    fn now(&self) -> Duration {
+        Duration::default()
+    }
+}
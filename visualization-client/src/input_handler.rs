@@ -1,6 +1,7 @@
 //! Functionality to communicate with the controller
 //! once it's running.
 
+use myelin_engine::prelude::*;
 use std::error::Error;
 use std::fmt::Debug;
 use wasm_bindgen::prelude::*;
@@ -17,6 +18,9 @@ pub struct InputHandler {
 
 pub(crate) trait Controller: Debug {
     fn on_message(&mut self, message: &[u8]) -> Result<(), Box<dyn Error>>;
+    fn on_animation_frame(&mut self);
+    fn on_click(&mut self, x: f64, y: f64) -> Option<Id>;
+    fn take_resync_request(&mut self) -> bool;
 }
 
 #[wasm_bindgen]
@@ -38,6 +42,51 @@ impl InputHandler {
             wasm_bindgen::throw_str(&format!("{}", err));
         }
     }
+
+    /// Redraws the objects, moved to where they are expected to be
+    /// between the last and the next message.
+    /// This should be called from JS on every animation frame.
+    ///
+    /// # Examples
+    ///
+    /// ```ts
+    /// const step = () => {
+    ///     inputHandler.on_animation_frame()
+    ///     window.requestAnimationFrame(step)
+    /// }
+    /// ```
+    pub fn on_animation_frame(&mut self) {
+        self.controller.on_animation_frame();
+    }
+
+    /// Handles a click on the canvas at the given canvas coordinates by selecting
+    /// the topmost object that was clicked, which is outlined from the next frame on.
+    /// Returns the id of the selected object, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```ts
+    /// const selectedObject = inputHandler.on_click(event.offsetX, event.offsetY)
+    /// ```
+    pub fn on_click(&mut self, x: f64, y: f64) -> Option<usize> {
+        self.controller.on_click(x, y)
+    }
+
+    /// Returns whether a received message didn't match the current state of the visualization,
+    /// in which case a full snapshot is needed, e.g. by reconnecting to the server.
+    /// The request is only returned once.
+    ///
+    /// # Examples
+    ///
+    /// ```ts
+    /// inputHandler.on_message(new Uint8Array(event.data))
+    /// if (inputHandler.take_resync_request()) {
+    ///     reconnect()
+    /// }
+    /// ```
+    pub fn take_resync_request(&mut self) -> bool {
+        self.controller.take_resync_request()
+    }
 }
 
 #[cfg(test)]
@@ -67,6 +116,18 @@ mod tests {
             assert_eq!(self.expected_message, message);
             Ok(())
         }
+
+        fn on_animation_frame(&mut self) {
+            panic!("on_animation_frame() was called unexpectedly")
+        }
+
+        fn on_click(&mut self, _x: f64, _y: f64) -> Option<Id> {
+            panic!("on_click() was called unexpectedly")
+        }
+
+        fn take_resync_request(&mut self) -> bool {
+            panic!("take_resync_request() was called unexpectedly")
+        }
     }
 
     impl Drop for ControllerMock {
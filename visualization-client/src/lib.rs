@@ -15,10 +15,18 @@
 )]
 
 pub use self::bootstrapper::*;
+pub use self::delta_player::*;
+#[cfg(feature = "use-png")]
+pub use self::headless::*;
 pub use self::input_handler::*;
 
 mod bootstrapper;
+mod camera;
+mod clock;
 mod controller;
+mod delta_player;
+#[cfg(feature = "use-png")]
+mod headless;
 mod input_handler;
 mod presenter;
 mod view;
@@ -5,32 +5,64 @@ const websocketUrl = `ws://${window.location.hostname}:6956`
 Promise.all([
     createWebsocket(websocketUrl),
     import('../out/myelin_visualization_client'),
-]).then(([websocket, wasm]) => {
-    websocket.binaryType = 'arraybuffer'
-
+]).then(([initialWebsocket, wasm]) => {
     const canvas = document.getElementById('visualization') as HTMLCanvasElement
-    const inputHandler = wasm.init(canvas)
-
-    const onMessage = (event: MessageEvent) => {
-        try {
-            inputHandler.on_message(new Uint8Array(event.data))
-        } catch (e) {
-            console.error(e)
-            websocket.removeEventListener('message', onMessage)
-            websocket.removeEventListener('close', onClose)
-            websocket.close()
+    const searchParams = new URLSearchParams(window.location.search)
+    const gridSpacing = searchParams.get('grid')
+    const colorScalar = searchParams.get('color')
+    const inputHandler = wasm.init(
+        canvas,
+        gridSpacing === null ? undefined : Number(gridSpacing),
+        colorScalar === null ? undefined : colorScalar,
+    )
+
+    const listen = (websocket: WebSocket) => {
+        websocket.binaryType = 'arraybuffer'
+
+        const onClose = (event: CloseEvent) => {
+            console.error('Websocket closed')
         }
+
+        const onMessage = (event: MessageEvent) => {
+            try {
+                inputHandler.on_message(new Uint8Array(event.data))
+            } catch (e) {
+                console.error(e)
+                websocket.removeEventListener('message', onMessage)
+                websocket.removeEventListener('close', onClose)
+                websocket.close()
+                return
+            }
+
+            // The server sends a full snapshot to every new connection
+            if (inputHandler.take_resync_request()) {
+                console.warn('Visualization got out of sync, reconnecting')
+                websocket.removeEventListener('message', onMessage)
+                websocket.removeEventListener('close', onClose)
+                websocket.close()
+                createWebsocket(websocketUrl).then(listen).catch(console.error)
+            }
+        }
+
+        websocket.addEventListener('message', onMessage)
+        websocket.addEventListener('close', onClose)
+        // Temporary solution: the server waits for any message before
+        // it starts sending deltas, so that the client doesn't miss any of them
+        websocket.send(new ArrayBuffer(0))
+    }
+
+    const onClick = (event: MouseEvent) => {
+        inputHandler.on_click(event.offsetX, event.offsetY)
     }
 
-    const onClose = (event: CloseEvent) => {
-        console.error('Websocket closed')
+    const onAnimationFrame = () => {
+        inputHandler.on_animation_frame()
+        window.requestAnimationFrame(onAnimationFrame)
     }
 
-    websocket.addEventListener('message', onMessage)
-    websocket.addEventListener('close', onClose)
-    // Temporary solution: the server waits for any message before
-    // it starts sending deltas, so that the client doesn't miss any of them
-    websocket.send(new ArrayBuffer(0))
+    canvas.addEventListener('click', onClick)
+    listen(initialWebsocket)
+    window.requestAnimationFrame(onAnimationFrame)
 }).catch((reason) => {
     console.error(reason)
     document.body.appendChild(document.createTextNode('Failed to initialize visualization'))
@@ -2,15 +2,19 @@ pub(crate) use self::delta_applier::{DeltaApplier, DeltaApplierImpl};
 pub(crate) use self::global_polygon_translator::{
     GlobalPolygonTranslator, GlobalPolygonTranslatorImpl,
 };
+use crate::camera::{Camera, SharedCamera};
 use crate::controller::Presenter;
 use crate::view::constant;
-use crate::view_model;
+use crate::view_model::{self, compare_heights};
 use myelin_engine::prelude::*;
 use myelin_object_data::Kind;
+use myelin_visualization_core::simulation_stats::SimulationStats;
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
+use std::iter;
+use std::mem;
 
 mod delta_applier;
 mod global_polygon_translator;
@@ -21,6 +25,9 @@ use mockiato::mockable;
 #[cfg_attr(test, mockable)]
 pub(crate) trait View: fmt::Debug {
     fn draw_objects(&self, objects: Vec<view_model::Object>);
+    fn draw_fps(&self, fps: f64);
+    fn draw_stats(&self, lines: Vec<String>);
+    fn redraw(&self);
     fn flush(&self);
 }
 
@@ -30,17 +37,63 @@ pub(crate) struct CanvasPresenter {
     delta_applier: Box<dyn DeltaApplier>,
     global_polygon_translator: Box<dyn GlobalPolygonTranslator>,
     current_snapshot: Snapshot,
+    camera: SharedCamera,
+    resync_requested: bool,
+    color_scalar: Option<ColorScalar>,
+    level_of_detail_threshold: Option<f64>,
+    selected_object: Option<Id>,
+}
+
+/// A per-object value that objects can be tinted by instead of the color of their kind
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ColorScalar {
+    /// The magnitude of the object's velocity.
+    /// Objects at rest are tinted red, objects at `max_speed` or faster green.
+    Speed {
+        /// The speed at which the gradient ends
+        max_speed: f64,
+    },
+    /// The object's height.
+    /// Flat objects are tinted red, objects at `max_height` or higher green.
+    Height {
+        /// The height at which the gradient ends
+        max_height: f64,
+    },
 }
 
 impl Presenter for CanvasPresenter {
     fn present_delta(&mut self, delta: ViewModelDelta) -> Result<(), Box<dyn Error>> {
-        self.delta_applier
-            .apply_delta(&mut self.current_snapshot, delta)?;
+        let previous_locations = map_locations(&self.current_snapshot);
+
+        if self
+            .delta_applier
+            .apply_delta(&mut self.current_snapshot, delta)
+            .is_err()
+        {
+            // The delta doesn't match our snapshot, e.g. because a message got lost.
+            // Instead of drawing an inconsistent world, we start over with a full snapshot.
+            self.current_snapshot.clear();
+            self.resync_requested = true;
+            return Ok(());
+        }
+
+        if let Some(selected_object) = self.selected_object {
+            if !self.current_snapshot.contains_key(&selected_object) {
+                self.selected_object = None;
+            }
+        }
 
         let objects: Vec<_> = map_objects(
             &self.current_snapshot,
+            &previous_locations,
             self.global_polygon_translator.borrow(),
+            self.color_scalar,
+            self.selected_object,
         )
+        .map(|object| match self.level_of_detail_threshold {
+            Some(threshold) => simplify_tiny_object(object, &self.camera.borrow(), threshold),
+            None => object,
+        })
         .collect();
 
         self.view.flush();
@@ -48,6 +101,29 @@ impl Presenter for CanvasPresenter {
 
         Ok(())
     }
+
+    fn present_fps(&mut self, fps: f64) {
+        self.view.draw_fps(fps);
+    }
+
+    fn present_simulation_stats(&mut self, simulation_stats: SimulationStats) {
+        self.view
+            .draw_stats(format_simulation_stats(&simulation_stats));
+    }
+
+    fn present_animation_frame(&mut self) {
+        self.view.redraw();
+    }
+
+    fn take_resync_request(&mut self) -> bool {
+        mem::replace(&mut self.resync_requested, false)
+    }
+
+    fn select_object_at(&mut self, screen_location: Point) -> Option<Id> {
+        let world_location = self.camera.borrow().to_world(screen_location);
+        self.selected_object = find_topmost_object_at(&self.current_snapshot, world_location);
+        self.selected_object
+    }
 }
 
 pub(crate) type Snapshot = HashMap<Id, ObjectDescription>;
@@ -125,18 +201,71 @@ pub(crate) struct ObjectDescriptionDelta {
 
 fn map_objects<'a>(
     snapshot: &'a Snapshot,
+    previous_locations: &'a HashMap<Id, Point>,
     global_polygon_translator: &'a dyn GlobalPolygonTranslator,
+    color_scalar: Option<ColorScalar>,
+    selected_object: Option<Id>,
 ) -> impl Iterator<Item = view_model::Object> + 'a {
-    snapshot
-        .values()
-        .map(move |business_object| view_model::Object {
+    // The view draws equally high objects in the order it receives them,
+    // so passing them ordered by id keeps the drawing in sync with `find_topmost_object_at`
+    let mut business_objects: Vec<_> = snapshot.iter().collect();
+    business_objects.sort_by_key(|&(&id, _)| id);
+
+    business_objects
+        .into_iter()
+        .map(move |(id, business_object)| view_model::Object {
             shape: translate_shape_into_view_model(business_object, global_polygon_translator),
             kind: translate_kind_into_view_model(business_object.kind),
             height: business_object.height,
             name_label: translate_name_into_view_model(business_object),
+            movement: calculate_movement(business_object, previous_locations.get(id).cloned()),
+            color_scalar: color_scalar
+                .map(|color_scalar| calculate_color_scalar(business_object, color_scalar)),
+            is_selected: selected_object == Some(*id),
         })
 }
 
+fn map_locations(snapshot: &Snapshot) -> HashMap<Id, Point> {
+    snapshot
+        .iter()
+        .map(|(&id, business_object)| (id, business_object.location))
+        .collect()
+}
+
+/// Formats the stats as a line with the step, followed
+/// by a line for each kind, ordered alphabetically.
+fn format_simulation_stats(simulation_stats: &SimulationStats) -> Vec<String> {
+    let mut count_lines: Vec<_> = simulation_stats
+        .counts_by_kind
+        .iter()
+        .map(|(kind, count)| format!("{:?}: {}", kind, count))
+        .collect();
+    count_lines.sort();
+
+    iter::once(format!("Step {}", simulation_stats.step))
+        .chain(count_lines)
+        .collect()
+}
+
+/// Returns the object that contains the given location.
+/// If multiple objects overlap at that location, the one drawn on top wins,
+/// i.e. the highest one or, if they are equally high, the one with the greatest [`Id`].
+fn find_topmost_object_at(snapshot: &Snapshot, location: Point) -> Option<Id> {
+    snapshot
+        .iter()
+        .filter(|(_, business_object)| {
+            business_object
+                .shape
+                .translate(business_object.location)
+                .rotate_around_point(business_object.rotation, business_object.location)
+                .contains_point(location)
+        })
+        .max_by(|(id_one, object_one), (id_two, object_two)| {
+            compare_heights(object_one.height, object_two.height).then(id_one.cmp(id_two))
+        })
+        .map(|(&id, _)| id)
+}
+
 fn translate_shape_into_view_model(
     business_object: &ObjectDescription,
     global_polygon_translator: &dyn GlobalPolygonTranslator,
@@ -167,6 +296,107 @@ fn translate_name_into_view_model(
     })
 }
 
+/// Only movable objects that already existed before the last delta are animated,
+/// all other objects snap to their location.
+fn calculate_movement(
+    business_object: &ObjectDescription,
+    previous_location: Option<Point>,
+) -> Option<view_model::Point> {
+    match business_object.mobility {
+        Mobility::Immovable => None,
+        Mobility::Movable(_) => previous_location.map(|previous_location| view_model::Point {
+            x: business_object.location.x - previous_location.x,
+            y: business_object.location.y - previous_location.y,
+        }),
+    }
+}
+
+/// Replaces the shape of an object that is displayed smaller than `threshold` pixels
+/// by its bounding box, as the difference is not visible anyway.
+fn simplify_tiny_object(
+    object: view_model::Object,
+    camera: &Camera,
+    threshold: f64,
+) -> view_model::Object {
+    if calculate_screen_size(&object.shape, camera) >= threshold {
+        return object;
+    }
+
+    view_model::Object {
+        shape: bounding_box(&object.shape),
+        ..object
+    }
+}
+
+/// The longer side of the polygon's bounding box in screen pixels
+fn calculate_screen_size(polygon: &view_model::Polygon, camera: &Camera) -> f64 {
+    match calculate_bounds(polygon) {
+        Some((upper_left, lower_right)) => {
+            let width = lower_right.x - upper_left.x;
+            let height = lower_right.y - upper_left.y;
+            width.max(height) * camera.zoom
+        }
+        None => 0.0,
+    }
+}
+
+/// The rectangle enclosing all vertices of the polygon,
+/// starting at the upper left corner and continuing clockwise
+fn bounding_box(polygon: &view_model::Polygon) -> view_model::Polygon {
+    let vertices = match calculate_bounds(polygon) {
+        Some((upper_left, lower_right)) => vec![
+            upper_left.clone(),
+            view_model::Point {
+                x: lower_right.x,
+                y: upper_left.y,
+            },
+            lower_right.clone(),
+            view_model::Point {
+                x: upper_left.x,
+                y: lower_right.y,
+            },
+        ],
+        None => Vec::new(),
+    };
+
+    view_model::Polygon { vertices }
+}
+
+/// The upper left and lower right corner of the polygon's bounding box.
+/// `None` if the polygon has no vertices.
+fn calculate_bounds(
+    polygon: &view_model::Polygon,
+) -> Option<(view_model::Point, view_model::Point)> {
+    let first_vertex = polygon.vertices.first()?;
+    let mut upper_left = first_vertex.clone();
+    let mut lower_right = first_vertex.clone();
+
+    for vertex in &polygon.vertices[1..] {
+        upper_left.x = upper_left.x.min(vertex.x);
+        upper_left.y = upper_left.y.min(vertex.y);
+        lower_right.x = lower_right.x.max(vertex.x);
+        lower_right.y = lower_right.y.max(vertex.y);
+    }
+
+    Some((upper_left, lower_right))
+}
+
+/// Scales the value selected by `color_scalar` onto the range `[0.0; 1.0]`
+fn calculate_color_scalar(business_object: &ObjectDescription, color_scalar: ColorScalar) -> f64 {
+    let (value, max_value) = match color_scalar {
+        ColorScalar::Speed { max_speed } => {
+            let speed = match business_object.mobility {
+                Mobility::Immovable => 0.0,
+                Mobility::Movable(velocity) => velocity.magnitude(),
+            };
+            (speed, max_speed)
+        }
+        ColorScalar::Height { max_height } => (business_object.height, max_height),
+    };
+
+    (value / max_value).max(0.0).min(1.0)
+}
+
 fn calculate_name_position(business_object: &ObjectDescription) -> view_model::Point {
     let aabb = business_object.shape.aabb();
 
@@ -190,8 +420,32 @@ impl CanvasPresenter {
             global_polygon_translator,
             delta_applier,
             current_snapshot: Snapshot::new(),
+            camera: SharedCamera::default(),
+            resync_requested: false,
+            color_scalar: None,
+            level_of_detail_threshold: None,
+            selected_object: None,
         }
     }
+
+    /// Tints objects by `color_scalar` instead of the color of their kind
+    pub(crate) fn with_color_scalar(mut self, color_scalar: ColorScalar) -> Self {
+        self.color_scalar = Some(color_scalar);
+        self
+    }
+
+    /// Draws objects that are displayed smaller than `threshold` pixels as rectangles
+    pub(crate) fn with_level_of_detail_threshold(mut self, threshold: f64) -> Self {
+        self.level_of_detail_threshold = Some(threshold);
+        self
+    }
+
+    /// Uses the given `camera` instead of one only known to this presenter,
+    /// e.g. to share it with the view
+    pub(crate) fn with_camera(mut self, camera: SharedCamera) -> Self {
+        self.camera = camera;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -200,12 +454,14 @@ mod tests {
     use super::ObjectDescription;
     use super::*;
     use crate::presenter::global_polygon_translator::GlobalPolygonTranslatorMock;
+    use crate::view::project_object;
     use crate::view_model;
     use maplit::hashmap;
     use nameof::name_of;
     use std::cell::RefCell;
     use std::collections::VecDeque;
     use std::fmt::{self, Debug};
+    use std::rc::Rc;
     use std::thread::panicking;
 
     struct DeltaApplierMock<'mock> {
@@ -340,6 +596,9 @@ mod tests {
             kind: view_model::Kind::Plant,
             height: 1.6_f64,
             name_label: None,
+            movement: None,
+            color_scalar: None,
+            is_selected: false,
         }];
         let view_model_delta_1 = hashmap! {
             12 => ObjectDelta::Created(object_description_1.clone())
@@ -355,12 +614,18 @@ mod tests {
                 kind: view_model::Kind::Plant,
                 height: 1.6_f64,
                 name_label: None,
+                movement: None,
+                color_scalar: None,
+                is_selected: false,
             },
             view_model::Object {
                 shape: view_model_polygon_2.clone(),
                 kind: view_model::Kind::Plant,
                 height: 2_f64,
                 name_label: None,
+                movement: None,
+                color_scalar: None,
+                is_selected: false,
             },
         ];
         let view_model_delta_2 = hashmap! {
@@ -428,6 +693,495 @@ mod tests {
         presenter.present_delta(view_model_delta_2).unwrap();
     }
 
+    #[test]
+    fn invalid_delta_clears_snapshot_and_requests_resync() {
+        let mut presenter = CanvasPresenter::new(
+            box ViewMock::new(),
+            box DeltaApplierImpl::new(),
+            box GlobalPolygonTranslatorMock::new(),
+        );
+        presenter.current_snapshot.insert(12, object_description());
+
+        let invalid_delta = hashmap! {
+            45 => ObjectDelta::Updated(ObjectDescriptionDelta::default()),
+        };
+
+        assert!(!presenter.take_resync_request());
+        assert!(presenter.present_delta(invalid_delta).is_ok());
+        assert!(presenter.current_snapshot.is_empty());
+        assert!(presenter.take_resync_request());
+        assert!(!presenter.take_resync_request());
+    }
+
+    #[test]
+    fn color_scalar_is_passed_to_view() {
+        let object_description = ObjectDescription {
+            mobility: Mobility::Movable(Vector { x: 3.0, y: 4.0 }),
+            ..object_description()
+        };
+        let view_model_polygon = view_model::Polygon {
+            vertices: vec![view_model::Point { x: 1.0, y: 1.0 }],
+        };
+        let expected_view_model = vec![view_model::Object {
+            shape: view_model_polygon.clone(),
+            kind: view_model::Kind::Plant,
+            height: 1.6_f64,
+            name_label: None,
+            movement: None,
+            color_scalar: Some(0.5),
+            is_selected: false,
+        }];
+        let view_model_delta = hashmap! {
+            12 => ObjectDelta::Created(object_description.clone())
+        };
+
+        let mut view_mock = ViewMock::new();
+        view_mock.expect_draw_objects(|arg| arg.unordered_vec_eq(expected_view_model));
+        view_mock.expect_flush();
+
+        let mut global_polygon_translator = GlobalPolygonTranslatorMock::new();
+        global_polygon_translator
+            .expect_to_global_polygon(|arg| arg.any(), |arg| arg.any(), |arg| arg.any())
+            .returns(view_model_polygon);
+
+        let mut presenter = CanvasPresenter::new(
+            box view_mock,
+            box DeltaApplierImpl::new(),
+            box global_polygon_translator,
+        )
+        .with_color_scalar(ColorScalar::Speed { max_speed: 10.0 });
+
+        presenter.present_delta(view_model_delta).unwrap();
+    }
+
+    #[test]
+    fn color_scalar_is_clamped_to_max_value() {
+        let color_scalar = calculate_color_scalar(
+            &object_description(),
+            ColorScalar::Height { max_height: 0.8 },
+        );
+        assert_eq!(1.0, color_scalar);
+    }
+
+    #[test]
+    fn immovable_objects_have_no_speed() {
+        let color_scalar = calculate_color_scalar(
+            &object_description(),
+            ColorScalar::Speed { max_speed: 10.0 },
+        );
+        assert_eq!(0.0, color_scalar);
+    }
+
+    #[test]
+    fn tiny_objects_are_simplified_while_large_ones_keep_their_shape() {
+        let tiny_polygon = view_model::Polygon {
+            vertices: vec![
+                view_model::Point { x: 30.0, y: 40.0 },
+                view_model::Point { x: 31.0, y: 40.5 },
+                view_model::Point { x: 30.5, y: 41.0 },
+            ],
+        };
+        let large_polygon = view_model::Polygon {
+            vertices: vec![
+                view_model::Point { x: 10.0, y: 30.0 },
+                view_model::Point { x: 50.0, y: 50.0 },
+                view_model::Point { x: 10.0, y: 70.0 },
+            ],
+        };
+        let expected_view_model = vec![
+            view_model::Object {
+                shape: view_model::Polygon {
+                    vertices: vec![
+                        view_model::Point { x: 30.0, y: 40.0 },
+                        view_model::Point { x: 31.0, y: 40.0 },
+                        view_model::Point { x: 31.0, y: 41.0 },
+                        view_model::Point { x: 30.0, y: 41.0 },
+                    ],
+                },
+                kind: view_model::Kind::Plant,
+                height: 1.6_f64,
+                name_label: None,
+                movement: None,
+                color_scalar: None,
+                is_selected: false,
+            },
+            view_model::Object {
+                shape: large_polygon.clone(),
+                kind: view_model::Kind::Plant,
+                height: 2_f64,
+                name_label: None,
+                movement: None,
+                color_scalar: None,
+                is_selected: false,
+            },
+        ];
+        let object_description_1 = object_description();
+        let object_description_2 = object_description2();
+        let view_model_delta = hashmap! {
+            12 => ObjectDelta::Created(object_description_1.clone()),
+            45 => ObjectDelta::Created(object_description_2.clone()),
+        };
+
+        let mut view_mock = ViewMock::new();
+        view_mock.expect_draw_objects(|arg| arg.unordered_vec_eq(expected_view_model));
+        view_mock.expect_flush();
+
+        let mut global_polygon_translator = GlobalPolygonTranslatorMock::new();
+        global_polygon_translator
+            .expect_to_global_polygon(
+                |arg| arg.partial_eq_owned(object_description_1.shape.clone()),
+                |arg| arg.any(),
+                |arg| arg.any(),
+            )
+            .returns(tiny_polygon);
+        global_polygon_translator
+            .expect_to_global_polygon(
+                |arg| arg.partial_eq_owned(object_description_2.shape.clone()),
+                |arg| arg.any(),
+                |arg| arg.any(),
+            )
+            .returns(large_polygon);
+
+        let mut presenter = CanvasPresenter::new(
+            box view_mock,
+            box DeltaApplierImpl::new(),
+            box global_polygon_translator,
+        )
+        .with_level_of_detail_threshold(2.0);
+
+        presenter.present_delta(view_model_delta).unwrap();
+    }
+
+    #[test]
+    fn screen_size_is_longer_side_of_bounding_box() {
+        let polygon = view_model::Polygon {
+            vertices: vec![
+                view_model::Point { x: 10.0, y: 10.0 },
+                view_model::Point { x: 14.0, y: 12.0 },
+                view_model::Point { x: 11.0, y: 13.0 },
+            ],
+        };
+        assert_eq!(4.0, calculate_screen_size(&polygon, &Camera::default()));
+    }
+
+    #[test]
+    fn screen_size_respects_zoom() {
+        let polygon = view_model::Polygon {
+            vertices: vec![
+                view_model::Point { x: 10.0, y: 10.0 },
+                view_model::Point { x: 14.0, y: 12.0 },
+                view_model::Point { x: 11.0, y: 13.0 },
+            ],
+        };
+        let camera = Camera {
+            zoom: 0.25,
+            ..Camera::default()
+        };
+        assert_eq!(1.0, calculate_screen_size(&polygon, &camera));
+    }
+
+    #[test]
+    fn screen_size_of_empty_polygon_is_zero() {
+        let polygon = view_model::Polygon {
+            vertices: Vec::new(),
+        };
+        assert_eq!(0.0, calculate_screen_size(&polygon, &Camera::default()));
+    }
+
+    #[test]
+    fn presents_fps() {
+        let mut view_mock = ViewMock::new();
+        view_mock.expect_draw_fps(|arg| arg.partial_eq(60.0));
+        let mut presenter = CanvasPresenter::new(
+            box view_mock,
+            box DeltaApplierMock::new(VecDeque::new()),
+            box GlobalPolygonTranslatorMock::new(),
+        );
+        presenter.present_fps(60.0);
+    }
+
+    #[test]
+    fn presents_simulation_stats() {
+        let mut view_mock = ViewMock::new();
+        view_mock.expect_draw_stats(|arg| {
+            arg.partial_eq(vec![
+                String::from("Step 42"),
+                String::from("Organism: 3"),
+                String::from("Plant: 12"),
+            ])
+        });
+        let mut presenter = CanvasPresenter::new(
+            box view_mock,
+            box DeltaApplierMock::new(VecDeque::new()),
+            box GlobalPolygonTranslatorMock::new(),
+        );
+        presenter.present_simulation_stats(SimulationStats {
+            step: 42,
+            counts_by_kind: hashmap! {
+                Kind::Plant => 12,
+                Kind::Organism => 3,
+            },
+        });
+    }
+
+    #[test]
+    fn click_inside_polygon_selects_object() {
+        let snapshot = hashmap! { 12 => object_description() };
+        assert_eq!(
+            Some(12),
+            find_topmost_object_at(&snapshot, Point { x: 35.0, y: 45.0 })
+        );
+    }
+
+    #[test]
+    fn click_in_empty_space_selects_nothing() {
+        let snapshot = hashmap! { 12 => object_description() };
+        assert_eq!(
+            None,
+            find_topmost_object_at(&snapshot, Point { x: 100.0, y: 100.0 })
+        );
+    }
+
+    #[test]
+    fn click_on_overlapping_objects_selects_topmost() {
+        let snapshot = hashmap! {
+            12 => object_description(),
+            45 => object_description2(),
+        };
+        assert_eq!(
+            Some(45),
+            find_topmost_object_at(&snapshot, Point { x: 30.0, y: 45.0 })
+        );
+    }
+
+    #[test]
+    fn click_on_equally_high_objects_selects_the_one_drawn_last() {
+        let snapshot = hashmap! {
+            45 => object_description(),
+            12 => object_description(),
+            30 => object_description(),
+        };
+        assert_eq!(
+            Some(45),
+            find_topmost_object_at(&snapshot, Point { x: 30.0, y: 40.0 })
+        );
+    }
+
+    #[test]
+    fn click_on_object_with_nan_height_does_not_panic() {
+        let snapshot = hashmap! {
+            12 => object_description(),
+            45 => ObjectDescription {
+                height: std::f64::NAN,
+                ..object_description()
+            },
+        };
+        assert_eq!(
+            Some(45),
+            find_topmost_object_at(&snapshot, Point { x: 30.0, y: 40.0 })
+        );
+    }
+
+    #[test]
+    fn equally_high_objects_are_passed_to_view_ordered_by_id() {
+        let view_model_polygon = view_model::Polygon {
+            vertices: vec![view_model::Point { x: 1.0, y: 1.0 }],
+        };
+        let view_model_object = |kind| view_model::Object {
+            shape: view_model_polygon.clone(),
+            kind,
+            height: 1.6_f64,
+            name_label: None,
+            movement: None,
+            color_scalar: None,
+            is_selected: false,
+        };
+        let expected_view_model = vec![
+            view_model_object(view_model::Kind::Water),
+            view_model_object(view_model::Kind::Plant),
+            view_model_object(view_model::Kind::Terrain),
+        ];
+        let view_model_delta = hashmap! {
+            45 => ObjectDelta::Created(ObjectDescription {
+                kind: Kind::Terrain,
+                ..object_description()
+            }),
+            12 => ObjectDelta::Created(ObjectDescription {
+                kind: Kind::Water,
+                ..object_description()
+            }),
+            30 => ObjectDelta::Created(object_description()),
+        };
+
+        let mut view_mock = ViewMock::new();
+        view_mock.expect_draw_objects(|arg| arg.partial_eq(expected_view_model));
+        view_mock.expect_flush();
+
+        let mut global_polygon_translator = GlobalPolygonTranslatorMock::new();
+        global_polygon_translator
+            .expect_to_global_polygon(|arg| arg.any(), |arg| arg.any(), |arg| arg.any())
+            .returns(view_model_polygon.clone())
+            .times(3);
+
+        let mut presenter = CanvasPresenter::new(
+            box view_mock,
+            box DeltaApplierImpl::new(),
+            box global_polygon_translator,
+        );
+
+        presenter.present_delta(view_model_delta).unwrap();
+    }
+
+    #[test]
+    fn shared_camera_is_used_for_selection() {
+        let camera = SharedCamera::default();
+        let mut presenter = CanvasPresenter::new(
+            box ViewMock::new(),
+            box DeltaApplierMock::new(VecDeque::new()),
+            box GlobalPolygonTranslatorMock::new(),
+        )
+        .with_camera(camera.clone());
+        presenter.current_snapshot.insert(12, object_description());
+
+        assert_eq!(
+            None,
+            presenter.select_object_at(Point { x: 130.0, y: 140.0 })
+        );
+
+        camera.borrow_mut().offset = Point {
+            x: -100.0,
+            y: -100.0,
+        };
+        assert_eq!(
+            Some(12),
+            presenter.select_object_at(Point { x: 130.0, y: 140.0 })
+        );
+    }
+
+    #[test]
+    fn objects_are_selected_where_they_are_drawn() {
+        let camera = Rc::new(RefCell::new(Camera {
+            offset: Point { x: 25.0, y: 35.0 },
+            zoom: 4.0,
+        }));
+        let mut presenter = CanvasPresenter::new(
+            box ViewMock::new(),
+            box DeltaApplierMock::new(VecDeque::new()),
+            box GlobalPolygonTranslatorMock::new(),
+        )
+        .with_camera(camera.clone());
+        let object_description = object_description();
+        presenter
+            .current_snapshot
+            .insert(12, object_description.clone());
+
+        let object = view_model::Object {
+            shape: GlobalPolygonTranslatorImpl::new().to_global_polygon(
+                &object_description.shape,
+                object_description.location,
+                object_description.rotation,
+            ),
+            kind: view_model::Kind::Plant,
+            height: object_description.height,
+            name_label: None,
+            movement: None,
+            color_scalar: None,
+            is_selected: false,
+        };
+        let drawn_object = project_object(&object, &camera.borrow());
+        let vertex_count = drawn_object.shape.vertices.len() as f64;
+        let drawn_center = Point {
+            x: drawn_object
+                .shape
+                .vertices
+                .iter()
+                .map(|vertex| vertex.x)
+                .sum::<f64>()
+                / vertex_count,
+            y: drawn_object
+                .shape
+                .vertices
+                .iter()
+                .map(|vertex| vertex.y)
+                .sum::<f64>()
+                / vertex_count,
+        };
+
+        assert_eq!(Point { x: 20.0, y: 20.0 }, drawn_center);
+        assert_eq!(Some(12), presenter.select_object_at(drawn_center));
+        assert_eq!(None, presenter.select_object_at(Point { x: 70.0, y: 70.0 }));
+    }
+
+    #[test]
+    fn selected_object_is_drawn_as_selected() {
+        let view_model_polygon = view_model::Polygon {
+            vertices: vec![view_model::Point { x: 1.0, y: 1.0 }],
+        };
+        let view_model_object = |is_selected| view_model::Object {
+            shape: view_model_polygon.clone(),
+            kind: view_model::Kind::Plant,
+            height: 1.6_f64,
+            name_label: None,
+            movement: None,
+            color_scalar: None,
+            is_selected,
+        };
+        let expected_view_model = vec![view_model_object(true), view_model_object(false)];
+
+        let mut view_mock = ViewMock::new();
+        view_mock.expect_draw_objects(|arg| arg.partial_eq(expected_view_model));
+        view_mock.expect_flush();
+
+        let mut global_polygon_translator = GlobalPolygonTranslatorMock::new();
+        global_polygon_translator
+            .expect_to_global_polygon(|arg| arg.any(), |arg| arg.any(), |arg| arg.any())
+            .returns(view_model_polygon.clone())
+            .times(2);
+
+        let mut presenter = CanvasPresenter::new(
+            box view_mock,
+            box DeltaApplierImpl::new(),
+            box global_polygon_translator,
+        );
+        presenter.current_snapshot.insert(12, object_description());
+        presenter.current_snapshot.insert(
+            30,
+            ObjectDescription {
+                location: Point { x: 100.0, y: 100.0 },
+                ..object_description()
+            },
+        );
+
+        assert_eq!(
+            Some(12),
+            presenter.select_object_at(Point { x: 30.0, y: 40.0 })
+        );
+        presenter.present_delta(ViewModelDelta::new()).unwrap();
+    }
+
+    #[test]
+    fn selection_is_cleared_when_selected_object_is_deleted() {
+        let mut view_mock = ViewMock::new();
+        view_mock.expect_draw_objects(|arg| arg.partial_eq(Vec::new()));
+        view_mock.expect_flush();
+
+        let mut presenter = CanvasPresenter::new(
+            box view_mock,
+            box DeltaApplierImpl::new(),
+            box GlobalPolygonTranslatorMock::new(),
+        );
+        presenter.current_snapshot.insert(12, object_description());
+
+        assert_eq!(
+            Some(12),
+            presenter.select_object_at(Point { x: 30.0, y: 40.0 })
+        );
+        presenter
+            .present_delta(hashmap! { 12 => ObjectDelta::Deleted })
+            .unwrap();
+        assert_eq!(None, presenter.selected_object);
+    }
+
     #[test]
     fn calculate_name_position_works() {
         let position = calculate_name_position(&object_description());
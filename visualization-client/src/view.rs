@@ -1,10 +1,20 @@
 //! Internal module containing the DOM manipulation.
 pub(crate) mod constant;
+#[cfg(feature = "use-png")]
+mod png_view;
 
+#[cfg(feature = "use-png")]
+pub(crate) use self::png_view::PngView;
+
+use crate::camera::{Camera, SharedCamera};
+use crate::controller::Clock;
 use crate::presenter::View;
 use crate::view_model::*;
+use myelin_engine::geometry;
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::fmt;
+use std::time::Duration;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlElement};
 
@@ -12,15 +22,70 @@ use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlElement};
 #[derive(Debug)]
 pub(crate) struct CanvasView {
     context: CanvasRenderingContext2d,
+    camera: SharedCamera,
+    grid_spacing: Option<f64>,
+    clock: Box<dyn Clock>,
+    frame: RefCell<Frame>,
+}
+
+/// Everything that was drawn since the last delta,
+/// so that it can be redrawn on every animation frame
+#[derive(Debug, Default)]
+struct Frame {
+    objects: Vec<Object>,
+    fps: Option<f64>,
+    stats_lines: Vec<String>,
+    last_delta_time: Option<Duration>,
+    delta_interval: Option<Duration>,
 }
 
 impl View for CanvasView {
     fn draw_objects(&self, mut objects: Vec<Object>) {
         objects.sort_by(compare_objects);
 
-        for object in objects {
-            self.draw_object(&object);
+        let now = self.clock.now();
+        let mut frame = self.frame.borrow_mut();
+
+        frame.delta_interval = frame
+            .last_delta_time
+            .map(|last_delta_time| now - last_delta_time);
+        frame.last_delta_time = Some(now);
+        frame.objects = objects;
+
+        self.draw_interpolated_objects(&frame.objects, 0.0);
+    }
+
+    fn draw_fps(&self, fps: f64) {
+        self.frame.borrow_mut().fps = Some(fps);
+        self.draw_fps_label(fps);
+    }
+
+    fn draw_stats(&self, lines: Vec<String>) {
+        self.draw_stats_lines(&lines);
+        self.frame.borrow_mut().stats_lines = lines;
+    }
+
+    fn redraw(&self) {
+        let frame = self.frame.borrow();
+
+        let last_delta_time = match frame.last_delta_time {
+            Some(last_delta_time) => last_delta_time,
+            None => return,
+        };
+
+        let interpolation_factor = calculate_interpolation_factor(
+            self.clock.now() - last_delta_time,
+            frame.delta_interval,
+        );
+
+        self.flush();
+        self.draw_interpolated_objects(&frame.objects, interpolation_factor);
+
+        if let Some(fps) = frame.fps {
+            self.draw_fps_label(fps);
         }
+
+        self.draw_stats_lines(&frame.stats_lines);
     }
 
     fn flush(&self) {
@@ -34,55 +99,418 @@ impl View for CanvasView {
 }
 
 impl CanvasView {
-    pub(crate) fn new(canvas: &HtmlCanvasElement) -> Self {
+    /// Creates a new [`CanvasView`].
+    /// A grid with lines every `grid_spacing` world units
+    /// is drawn behind the objects if `grid_spacing` is set.
+    /// The `clock` is used to animate the objects between deltas.
+    pub(crate) fn new(
+        canvas: &HtmlCanvasElement,
+        grid_spacing: Option<f64>,
+        clock: Box<dyn Clock>,
+    ) -> Self {
         let context = get_2d_context(canvas);
 
         adjust_canvas_to_device_pixel_ratio(canvas, &context);
 
-        Self { context }
+        Self {
+            context,
+            camera: SharedCamera::default(),
+            grid_spacing,
+            clock,
+            frame: RefCell::new(Frame::default()),
+        }
+    }
+
+    /// Uses the given `camera` instead of one only known to this view,
+    /// e.g. to share it with the presenter
+    pub(crate) fn with_camera(mut self, camera: SharedCamera) -> Self {
+        self.camera = camera;
+        self
     }
 
-    fn draw_object(&self, object: &Object) {
-        self.context.begin_path();
+    fn draw_interpolated_objects(&self, objects: &[Object], interpolation_factor: f64) {
+        if let Some(grid_spacing) = self.grid_spacing {
+            self.draw_grid(grid_spacing);
+        }
 
-        let first_vertex = &object.shape.vertices[0];
-        self.context.move_to(first_vertex.x, first_vertex.y);
+        let screen_objects = calculate_visible_screen_objects(
+            objects,
+            interpolation_factor,
+            &self.camera.borrow(),
+            self.viewport().as_ref(),
+        );
 
-        for vertex in &object.shape.vertices[1..] {
-            self.context.line_to(vertex.x, vertex.y);
+        for batch in batch_objects_by_color(&screen_objects) {
+            self.draw_batch(&batch);
         }
+    }
 
-        self.context.close_path();
+    fn draw_fps_label(&self, fps: f64) {
+        let label = format!("{:.0} FPS", fps);
 
-        let color = map_kind_to_color(&object.kind);
-        self.context.set_fill_style(&JsValue::from_str(color));
-        self.context.fill();
+        self.context
+            .set_fill_style(&JsValue::from_str(constant::color::LABEL));
+        self.context.set_text_align(constant::alignment::LEFT);
+        self.context
+            .fill_text(
+                &label,
+                constant::offset::FPS_LABEL.x,
+                constant::offset::FPS_LABEL.y,
+            )
+            .unwrap_or_else(|error| panic!("Unable to display fps. Error: {:?}", error));
+    }
 
-        if let Some(ref name_label) = object.name_label {
-            self.context
-                .set_fill_style(&JsValue::from_str(&name_label.font_color));
-            self.context.set_text_align(constant::alignment::CENTER);
+    fn draw_stats_lines(&self, lines: &[String]) {
+        self.context
+            .set_fill_style(&JsValue::from_str(constant::color::LABEL));
+        self.context.set_text_align(constant::alignment::LEFT);
+
+        for (index, line) in lines.iter().enumerate() {
             self.context
                 .fill_text(
-                    &name_label.text,
-                    name_label.location.x,
-                    name_label.location.y,
+                    line,
+                    constant::offset::STATS_LABEL.x,
+                    constant::offset::STATS_LABEL.y + index as f64 * constant::line_height::LABEL,
+                )
+                .unwrap_or_else(|error| panic!("Unable to display stats. Error: {:?}", error));
+        }
+    }
+
+    fn draw_grid(&self, spacing: f64) {
+        let canvas = self
+            .context
+            .canvas()
+            .expect("No association with a <canvas> element");
+        let width = f64::from(canvas.width());
+        let height = f64::from(canvas.height());
+
+        let camera = self.camera.borrow();
+        let upper_left = camera.to_world(geometry::Point { x: 0.0, y: 0.0 });
+        let lower_right = camera.to_world(geometry::Point {
+            x: width,
+            y: height,
+        });
+
+        self.context.begin_path();
+
+        for x in calculate_gridline_positions(upper_left.x, lower_right.x, spacing) {
+            let screen_x = camera.to_screen(geometry::Point { x, y: 0.0 }).x;
+            self.context.move_to(screen_x, 0.0);
+            self.context.line_to(screen_x, height);
+        }
+
+        for y in calculate_gridline_positions(upper_left.y, lower_right.y, spacing) {
+            let screen_y = camera.to_screen(geometry::Point { x: 0.0, y }).y;
+            self.context.move_to(0.0, screen_y);
+            self.context.line_to(width, screen_y);
+        }
+
+        self.context
+            .set_stroke_style(&JsValue::from_str(constant::color::GRID));
+        self.context.set_line_width(constant::line_width::GRID);
+        self.context.stroke();
+    }
+
+    /// The area of the canvas in screen coordinates.
+    /// `None` if the canvas has no area, e.g. because it has not been laid out yet.
+    fn viewport(&self) -> Option<geometry::Aabb> {
+        let canvas = self
+            .context
+            .canvas()
+            .expect("No association with a <canvas> element");
+
+        geometry::Aabb::try_new(
+            (0.0, 0.0),
+            (f64::from(canvas.width()), f64::from(canvas.height())),
+        )
+        .ok()
+    }
+
+    fn draw_batch(&self, batch: &Batch<'_>) {
+        self.context.begin_path();
+
+        for object in &batch.objects {
+            self.trace_polygon(&object.shape);
+        }
+
+        self.context
+            .set_fill_style(&JsValue::from_str(&batch.color));
+        self.context.fill();
+
+        for object in batch.objects.iter().filter(|object| object.is_selected) {
+            self.draw_selection_outline(&object.shape);
+        }
+
+        for object in &batch.objects {
+            if let Some(ref name_label) = object.name_label {
+                self.draw_name_label(name_label);
+            }
+        }
+    }
+
+    /// Adds the polygon, given in screen coordinates, as a sub-path to the current path.
+    /// All polygons are traced in the same direction, so that overlapping polygons
+    /// of the same path are filled like individually drawn ones.
+    fn trace_polygon(&self, polygon: &Polygon) {
+        let mut vertices: Vec<_> = polygon.vertices.iter().collect();
+
+        if is_counterclockwise(&polygon.vertices) {
+            vertices.reverse();
+        }
+
+        if let Some((first_vertex, remaining_vertices)) = vertices.split_first() {
+            self.context.move_to(first_vertex.x, first_vertex.y);
+
+            for vertex in remaining_vertices {
+                self.context.line_to(vertex.x, vertex.y);
+            }
+
+            self.context.close_path();
+        }
+    }
+
+    fn draw_selection_outline(&self, polygon: &Polygon) {
+        self.context.begin_path();
+        self.trace_polygon(polygon);
+        self.context
+            .set_stroke_style(&JsValue::from_str(constant::color::SELECTION));
+        self.context.set_line_width(constant::line_width::SELECTION);
+        self.context.stroke();
+    }
+
+    fn draw_name_label(&self, name_label: &Label) {
+        self.context
+            .set_fill_style(&JsValue::from_str(&name_label.font_color));
+        self.context.set_text_align(constant::alignment::CENTER);
+        self.context
+            .fill_text(
+                &name_label.text,
+                name_label.location.x,
+                name_label.location.y,
+            )
+            .unwrap_or_else(|error| {
+                panic!(
+                    "Unable to display name {:?}. Error: {:?}",
+                    name_label.text, error
                 )
-                .unwrap_or_else(|error| {
-                    panic!(
-                        "Unable to display name {:?}. Error: {:?}",
-                        name_label.text, error
-                    )
-                });
+            });
+    }
+}
+
+/// Neighbouring objects that share a fill color and can therefore be filled as a single path
+#[derive(Debug, PartialEq)]
+struct Batch<'a> {
+    color: String,
+    objects: Vec<&'a Object>,
+}
+
+/// Groups objects that are ordered by height into [`Batch`]es, keeping their order.
+/// Only neighbouring objects are grouped, so that objects of different colors
+/// still overlap each other in the same way. An object with a name label or a selection
+/// outline ends its batch, as these need to be drawn before any following object.
+fn batch_objects_by_color(objects: &[Object]) -> Vec<Batch<'_>> {
+    let mut batches: Vec<Batch<'_>> = Vec::new();
+    let mut previous_object_ends_batch = false;
+
+    for object in objects {
+        let color = map_object_to_color(object);
+
+        match batches.last_mut() {
+            Some(batch) if batch.color == color && !previous_object_ends_batch => {
+                batch.objects.push(object)
+            }
+            _ => batches.push(Batch {
+                color,
+                objects: vec![object],
+            }),
         }
+
+        previous_object_ends_batch = object.name_label.is_some() || object.is_selected;
+    }
+
+    batches
+}
+
+/// Maps the shape and name label of an object from world coordinates
+/// to the screen coordinates they are displayed at through the `camera`.
+/// The presenter maps clicks back through the same camera, so objects are selected where they are drawn.
+pub(crate) fn project_object(object: &Object, camera: &Camera) -> Object {
+    let project_point = |point: &Point| {
+        let screen_point = camera.to_screen(geometry::Point {
+            x: point.x,
+            y: point.y,
+        });
+        Point {
+            x: screen_point.x,
+            y: screen_point.y,
+        }
+    };
+
+    Object {
+        shape: Polygon {
+            vertices: object.shape.vertices.iter().map(project_point).collect(),
+        },
+        name_label: object.name_label.as_ref().map(|name_label| Label {
+            location: project_point(&name_label.location),
+            ..name_label.clone()
+        }),
+        ..object.clone()
+    }
+}
+
+/// Interpolates the objects and maps them onto the screen through the `camera`.
+/// Objects outside of the `viewport` are culled after the mapping,
+/// so that culling and drawing always agree on where an object is.
+/// Nothing is culled without a `viewport`.
+fn calculate_visible_screen_objects(
+    objects: &[Object],
+    interpolation_factor: f64,
+    camera: &Camera,
+    viewport: Option<&geometry::Aabb>,
+) -> Vec<Object> {
+    objects
+        .iter()
+        .map(|object| project_object(&interpolate_object(object, interpolation_factor), camera))
+        .filter(|object| match viewport {
+            Some(viewport) => is_within_viewport(&object.shape, viewport),
+            None => true,
+        })
+        .collect()
+}
+
+/// Whether the bounding box of the polygon, given in screen coordinates,
+/// intersects the `viewport`. Polygons without any area are never visible.
+fn is_within_viewport(polygon: &Polygon, viewport: &geometry::Aabb) -> bool {
+    let mut screen_vertices = polygon.vertices.iter().map(|vertex| geometry::Point {
+        x: vertex.x,
+        y: vertex.y,
+    });
+
+    let mut upper_left = match screen_vertices.next() {
+        Some(first_vertex) => first_vertex,
+        None => return false,
+    };
+    let mut lower_right = upper_left;
+
+    for vertex in screen_vertices {
+        upper_left.x = upper_left.x.min(vertex.x);
+        upper_left.y = upper_left.y.min(vertex.y);
+        lower_right.x = lower_right.x.max(vertex.x);
+        lower_right.y = lower_right.y.max(vertex.y);
+    }
+
+    geometry::Aabb::try_new(upper_left, lower_right)
+        .map(|aabb| aabb.intersects(viewport))
+        .unwrap_or(false)
+}
+
+/// Uses the shoelace formula to determine the orientation of the polygon
+/// in a coordinate system whose y axis points down
+fn is_counterclockwise(vertices: &[Point]) -> bool {
+    let following_vertices = vertices.iter().cycle().skip(1);
+
+    let doubled_signed_area: f64 = vertices
+        .iter()
+        .zip(following_vertices)
+        .map(|(vertex, following_vertex)| {
+            vertex.x * following_vertex.y - following_vertex.x * vertex.y
+        })
+        .sum();
+
+    doubled_signed_area < 0.0
+}
+
+/// Whether gridlines can be drawn `spacing` world units apart
+pub(crate) fn is_valid_grid_spacing(spacing: f64) -> bool {
+    spacing.is_finite() && spacing > 0.0
+}
+
+/// Arbitrary value, far more than fit onto any screen
+const MAX_GRIDLINE_COUNT: f64 = 10_000.0;
+
+/// Calculates the world coordinates of all gridlines along one axis
+/// that lie within the visible range `[world_start; world_end]`.
+/// No gridlines are calculated for an invalid `spacing`
+/// or if there would be more of them than could possibly be distinguished.
+fn calculate_gridline_positions(world_start: f64, world_end: f64, spacing: f64) -> Vec<f64> {
+    if !is_valid_grid_spacing(spacing) {
+        return Vec::new();
+    }
+
+    let first_gridline = (world_start / spacing).ceil() * spacing;
+
+    if first_gridline > world_end {
+        return Vec::new();
+    }
+
+    let gridline_count = ((world_end - first_gridline) / spacing).floor() + 1.0;
+
+    if gridline_count.is_nan() || gridline_count > MAX_GRIDLINE_COUNT {
+        return Vec::new();
+    }
+
+    let gridline_count = gridline_count as usize;
+
+    (0..gridline_count)
+        .map(|index| first_gridline + index as f64 * spacing)
+        .collect()
+}
+
+/// The fraction of the interval between the last two deltas that has passed since the last one.
+/// Objects snap to their location as long as the interval is unknown.
+fn calculate_interpolation_factor(
+    elapsed_since_last_delta: Duration,
+    delta_interval: Option<Duration>,
+) -> f64 {
+    match delta_interval {
+        Some(delta_interval) if delta_interval > Duration::default() => {
+            let interpolation_factor =
+                elapsed_since_last_delta.as_secs_f64() / delta_interval.as_secs_f64();
+            interpolation_factor.min(1.0)
+        }
+        _ => 1.0,
+    }
+}
+
+/// Moves the object back along its movement, so that it is located at its previous
+/// location for an `interpolation_factor` of `0.0` and at its current one for `1.0`.
+fn interpolate_object(object: &Object, interpolation_factor: f64) -> Object {
+    let movement = match object.movement {
+        Some(ref movement) => movement,
+        None => return object.clone(),
+    };
+
+    let offset = Point {
+        x: movement.x * (interpolation_factor - 1.0),
+        y: movement.y * (interpolation_factor - 1.0),
+    };
+
+    Object {
+        shape: Polygon {
+            vertices: object
+                .shape
+                .vertices
+                .iter()
+                .map(|vertex| translate_point(vertex, &offset))
+                .collect(),
+        },
+        name_label: object.name_label.as_ref().map(|name_label| Label {
+            location: translate_point(&name_label.location, &offset),
+            ..name_label.clone()
+        }),
+        ..object.clone()
+    }
+}
+
+fn translate_point(point: &Point, offset: &Point) -> Point {
+    Point {
+        x: point.x + offset.x,
+        y: point.y + offset.y,
     }
 }
 
 fn compare_objects(object_one: &Object, object_two: &Object) -> Ordering {
-    object_one
-        .height
-        .partial_cmp(&object_two.height)
-        .expect("Tried to compare heights with non-comparable values")
+    compare_heights(object_one.height, object_two.height)
 }
 
 fn get_2d_context(canvas: &HtmlCanvasElement) -> CanvasRenderingContext2d {
@@ -137,6 +565,25 @@ impl fmt::Display for Pixels {
     }
 }
 
+fn map_object_to_color(object: &Object) -> String {
+    match object.color_scalar {
+        Some(color_scalar) => {
+            let [red, green, blue] = map_scalar_to_rgb(color_scalar);
+            format!("rgb({}, {}, {})", red, green, blue)
+        }
+        None => String::from(map_kind_to_color(&object.kind)),
+    }
+}
+
+/// Maps a scalar onto a gradient from red at `0.0` to green at `1.0`.
+/// Values outside of that range are clamped.
+fn map_scalar_to_rgb(scalar: f64) -> [u8; 3] {
+    let scalar = scalar.max(0.0).min(1.0);
+    let red = ((1.0 - scalar) * 255.0).round() as u8;
+    let green = (scalar * 255.0).round() as u8;
+    [red, green, 0]
+}
+
 fn map_kind_to_color(kind: &Kind) -> &'static str {
     match kind {
         Kind::Organism => constant::color::ORGANISM,
@@ -148,9 +595,17 @@ fn map_kind_to_color(kind: &Kind) -> &'static str {
 
 #[cfg(test)]
 mod tests {
-    use crate::view::compare_objects;
-    use crate::view_model::{Kind, Object, Polygon};
+    use crate::camera::Camera;
+    use crate::view::{
+        batch_objects_by_color, calculate_gridline_positions, calculate_interpolation_factor,
+        calculate_visible_screen_objects, compare_objects, constant, interpolate_object,
+        is_counterclockwise, is_valid_grid_spacing, is_within_viewport, map_object_to_color,
+        map_scalar_to_rgb, project_object,
+    };
+    use crate::view_model::{Kind, Label, Object, Point, Polygon};
+    use myelin_engine::geometry::{self, Aabb};
     use std::cmp::Ordering;
+    use std::time::Duration;
 
     #[test]
     fn objects_are_ordered_by_height() {
@@ -159,6 +614,9 @@ mod tests {
             kind: Kind::Organism,
             height: 20.0,
             name_label: None,
+            movement: None,
+            color_scalar: None,
+            is_selected: false,
         };
 
         let object_two = Object {
@@ -166,9 +624,419 @@ mod tests {
             kind: Kind::Organism,
             height: 10.0,
             name_label: None,
+            movement: None,
+            color_scalar: None,
+            is_selected: false,
         };
 
         assert_eq!(Ordering::Greater, compare_objects(&object_one, &object_two));
         assert_eq!(Ordering::Less, compare_objects(&object_two, &object_one));
     }
+
+    #[test]
+    fn gridlines_start_at_origin() {
+        let positions = calculate_gridline_positions(0.0, 100.0, 25.0);
+        assert_eq!(vec![0.0, 25.0, 50.0, 75.0, 100.0], positions);
+    }
+
+    #[test]
+    fn gridlines_start_at_first_multiple_of_spacing() {
+        let positions = calculate_gridline_positions(10.0, 60.0, 25.0);
+        assert_eq!(vec![25.0, 50.0], positions);
+    }
+
+    #[test]
+    fn gridlines_work_with_negative_coordinates() {
+        let positions = calculate_gridline_positions(-30.0, 10.0, 25.0);
+        assert_eq!(vec![-25.0, 0.0], positions);
+    }
+
+    #[test]
+    fn no_gridlines_are_calculated_when_spacing_exceeds_visible_range() {
+        let positions = calculate_gridline_positions(1.0, 11.0, 25.0);
+        assert!(positions.is_empty());
+    }
+
+    #[test]
+    fn no_gridlines_are_calculated_for_zero_spacing() {
+        assert!(calculate_gridline_positions(0.0, 100.0, 0.0).is_empty());
+    }
+
+    #[test]
+    fn no_gridlines_are_calculated_for_negative_spacing() {
+        assert!(calculate_gridline_positions(0.0, 100.0, -25.0).is_empty());
+    }
+
+    #[test]
+    fn no_gridlines_are_calculated_for_nan_spacing() {
+        assert!(calculate_gridline_positions(0.0, 100.0, std::f64::NAN).is_empty());
+    }
+
+    #[test]
+    fn no_gridlines_are_calculated_for_indistinguishably_small_spacing() {
+        assert!(calculate_gridline_positions(0.0, 100.0, 1e-300).is_empty());
+    }
+
+    #[test]
+    fn only_finite_positive_grid_spacing_is_valid() {
+        assert!(is_valid_grid_spacing(25.0));
+        assert!(!is_valid_grid_spacing(0.0));
+        assert!(!is_valid_grid_spacing(-25.0));
+        assert!(!is_valid_grid_spacing(std::f64::NAN));
+        assert!(!is_valid_grid_spacing(std::f64::INFINITY));
+    }
+
+    #[test]
+    fn interpolation_factor_is_fraction_of_delta_interval() {
+        let interpolation_factor = calculate_interpolation_factor(
+            Duration::from_millis(25),
+            Some(Duration::from_millis(100)),
+        );
+        assert_eq!(0.25, interpolation_factor);
+    }
+
+    #[test]
+    fn interpolation_factor_does_not_exceed_one() {
+        let interpolation_factor = calculate_interpolation_factor(
+            Duration::from_millis(150),
+            Some(Duration::from_millis(100)),
+        );
+        assert_eq!(1.0, interpolation_factor);
+    }
+
+    #[test]
+    fn objects_snap_while_delta_interval_is_unknown() {
+        let interpolation_factor = calculate_interpolation_factor(Duration::from_millis(25), None);
+        assert_eq!(1.0, interpolation_factor);
+    }
+
+    fn object(movement: Option<Point>) -> Object {
+        Object {
+            shape: Polygon {
+                vertices: vec![
+                    Point { x: 10.0, y: 10.0 },
+                    Point { x: 20.0, y: 10.0 },
+                    Point { x: 20.0, y: 20.0 },
+                ],
+            },
+            kind: Kind::Organism,
+            height: 1.0,
+            name_label: None,
+            movement,
+            color_scalar: None,
+            is_selected: false,
+        }
+    }
+
+    #[test]
+    fn moved_object_is_halfway_at_half_interpolation_factor() {
+        let object = object(Some(Point { x: 10.0, y: -4.0 }));
+
+        let interpolated_object = interpolate_object(&object, 0.5);
+
+        let expected_vertices = vec![
+            Point { x: 5.0, y: 12.0 },
+            Point { x: 15.0, y: 12.0 },
+            Point { x: 15.0, y: 22.0 },
+        ];
+        assert_eq!(expected_vertices, interpolated_object.shape.vertices);
+    }
+
+    #[test]
+    fn object_without_movement_is_not_interpolated() {
+        let object = object(None);
+        assert_eq!(object, interpolate_object(&object, 0.5));
+    }
+
+    #[test]
+    fn gradient_is_red_at_zero() {
+        assert_eq!([255, 0, 0], map_scalar_to_rgb(0.0));
+    }
+
+    #[test]
+    fn gradient_is_green_at_one() {
+        assert_eq!([0, 255, 0], map_scalar_to_rgb(1.0));
+    }
+
+    #[test]
+    fn gradient_is_mixed_at_midpoint() {
+        assert_eq!([128, 128, 0], map_scalar_to_rgb(0.5));
+    }
+
+    #[test]
+    fn gradient_clamps_values_out_of_range() {
+        assert_eq!([255, 0, 0], map_scalar_to_rgb(-2.0));
+        assert_eq!([0, 255, 0], map_scalar_to_rgb(3.0));
+    }
+
+    #[test]
+    fn object_with_color_scalar_is_tinted() {
+        let object = Object {
+            color_scalar: Some(1.0),
+            is_selected: false,
+            ..object(None)
+        };
+        assert_eq!("rgb(0, 255, 0)", map_object_to_color(&object));
+    }
+
+    #[test]
+    fn object_without_color_scalar_uses_color_of_kind() {
+        assert_eq!(
+            constant::color::ORGANISM,
+            map_object_to_color(&object(None))
+        );
+    }
+
+    fn object_of_kind(kind: Kind, height: f64) -> Object {
+        Object {
+            kind,
+            height,
+            ..object(None)
+        }
+    }
+
+    #[test]
+    fn neighbouring_objects_of_same_color_are_batched() {
+        let objects = vec![
+            object_of_kind(Kind::Water, 1.0),
+            object_of_kind(Kind::Plant, 2.0),
+            object_of_kind(Kind::Plant, 3.0),
+            object_of_kind(Kind::Organism, 4.0),
+        ];
+
+        let batches = batch_objects_by_color(&objects);
+
+        let batched_objects: Vec<Vec<_>> =
+            batches.iter().map(|batch| batch.objects.clone()).collect();
+        assert_eq!(
+            vec![
+                vec![&objects[0]],
+                vec![&objects[1], &objects[2]],
+                vec![&objects[3]],
+            ],
+            batched_objects
+        );
+        let colors: Vec<_> = batches.iter().map(|batch| batch.color.as_str()).collect();
+        assert_eq!(
+            vec![
+                constant::color::WATER,
+                constant::color::PLANT,
+                constant::color::ORGANISM,
+            ],
+            colors
+        );
+    }
+
+    #[test]
+    fn batches_keep_order_of_objects() {
+        let objects = vec![
+            object_of_kind(Kind::Plant, 1.0),
+            object_of_kind(Kind::Plant, 2.0),
+            object_of_kind(Kind::Plant, 3.0),
+        ];
+
+        let batches = batch_objects_by_color(&objects);
+
+        assert_eq!(1, batches.len());
+        let heights: Vec<_> = batches[0]
+            .objects
+            .iter()
+            .map(|object| object.height)
+            .collect();
+        assert_eq!(vec![1.0, 2.0, 3.0], heights);
+    }
+
+    #[test]
+    fn objects_of_same_color_separated_by_other_color_are_not_batched() {
+        let objects = vec![
+            object_of_kind(Kind::Plant, 1.0),
+            object_of_kind(Kind::Water, 2.0),
+            object_of_kind(Kind::Plant, 3.0),
+        ];
+
+        assert_eq!(3, batch_objects_by_color(&objects).len());
+    }
+
+    #[test]
+    fn object_with_name_label_ends_batch() {
+        let labelled_object = Object {
+            name_label: Some(Label {
+                text: String::from("Bob"),
+                location: Point { x: 15.0, y: 5.0 },
+                font_color: String::from(constant::color::LABEL),
+            }),
+            ..object_of_kind(Kind::Organism, 1.0)
+        };
+        let objects = vec![
+            object_of_kind(Kind::Organism, 0.5),
+            labelled_object,
+            object_of_kind(Kind::Organism, 2.0),
+        ];
+
+        let batches = batch_objects_by_color(&objects);
+
+        assert_eq!(2, batches.len());
+        assert_eq!(vec![&objects[0], &objects[1]], batches[0].objects);
+        assert_eq!(vec![&objects[2]], batches[1].objects);
+    }
+
+    #[test]
+    fn selected_object_ends_batch() {
+        let selected_object = Object {
+            is_selected: true,
+            ..object_of_kind(Kind::Plant, 1.0)
+        };
+        let objects = vec![
+            object_of_kind(Kind::Plant, 0.5),
+            selected_object,
+            object_of_kind(Kind::Plant, 2.0),
+        ];
+
+        let batches = batch_objects_by_color(&objects);
+
+        assert_eq!(2, batches.len());
+        assert_eq!(vec![&objects[0], &objects[1]], batches[0].objects);
+        assert_eq!(vec![&objects[2]], batches[1].objects);
+    }
+
+    #[test]
+    fn orientation_of_polygon_is_detected() {
+        let clockwise_vertices = object(None).shape.vertices;
+        let mut counterclockwise_vertices = clockwise_vertices.clone();
+        counterclockwise_vertices.reverse();
+
+        assert!(!is_counterclockwise(&clockwise_vertices));
+        assert!(is_counterclockwise(&counterclockwise_vertices));
+    }
+
+    fn viewport() -> Aabb {
+        Aabb::try_new((0.0, 0.0), (100.0, 100.0)).unwrap()
+    }
+
+    fn square(x: f64, y: f64, size: f64) -> Polygon {
+        Polygon {
+            vertices: vec![
+                Point { x, y },
+                Point { x: x + size, y },
+                Point {
+                    x: x + size,
+                    y: y + size,
+                },
+                Point { x, y: y + size },
+            ],
+        }
+    }
+
+    #[test]
+    fn object_fully_outside_of_viewport_is_culled() {
+        let polygon = square(120.0, 40.0, 10.0);
+        assert!(!is_within_viewport(&polygon, &viewport()));
+    }
+
+    #[test]
+    fn object_partially_inside_of_viewport_is_kept() {
+        let polygon = square(95.0, -5.0, 10.0);
+        assert!(is_within_viewport(&polygon, &viewport()));
+    }
+
+    #[test]
+    fn object_fully_inside_of_viewport_is_kept() {
+        let polygon = square(40.0, 40.0, 10.0);
+        assert!(is_within_viewport(&polygon, &viewport()));
+    }
+
+    #[test]
+    fn culling_and_drawing_respect_camera() {
+        let object_left_of_offset = Object {
+            shape: square(40.0, 40.0, 10.0),
+            ..object(None)
+        };
+        let object_right_of_offset = Object {
+            shape: square(140.0, 40.0, 10.0),
+            ..object(None)
+        };
+        let camera = Camera {
+            offset: geometry::Point { x: 60.0, y: 0.0 },
+            zoom: 2.0,
+        };
+
+        let screen_objects = calculate_visible_screen_objects(
+            &[object_left_of_offset, object_right_of_offset],
+            1.0,
+            &camera,
+            Some(&Aabb::try_new((0.0, 0.0), (200.0, 200.0)).unwrap()),
+        );
+
+        assert_eq!(1, screen_objects.len());
+        assert_eq!(
+            square(160.0, 80.0, 20.0).vertices,
+            screen_objects[0].shape.vertices
+        );
+    }
+
+    #[test]
+    fn interpolated_objects_are_culled_where_they_are_drawn() {
+        let moving_into_viewport = Object {
+            shape: square(100.0, 40.0, 10.0),
+            movement: Some(Point { x: 70.0, y: 0.0 }),
+            ..object(None)
+        };
+        let camera = Camera {
+            offset: geometry::Point { x: 50.0, y: 0.0 },
+            zoom: 1.0,
+        };
+
+        let screen_objects = calculate_visible_screen_objects(
+            &[moving_into_viewport],
+            0.0,
+            &camera,
+            Some(&viewport()),
+        );
+
+        assert!(screen_objects.is_empty());
+    }
+
+    #[test]
+    fn nothing_is_culled_without_viewport() {
+        let camera = Camera {
+            offset: geometry::Point {
+                x: 1000.0,
+                y: 1000.0,
+            },
+            zoom: 1.0,
+        };
+
+        let screen_objects = calculate_visible_screen_objects(&[object(None)], 1.0, &camera, None);
+
+        assert_eq!(1, screen_objects.len());
+    }
+
+    #[test]
+    fn projected_object_is_at_its_screen_location() {
+        let object = Object {
+            name_label: Some(Label {
+                text: String::from("Bob"),
+                location: Point { x: 15.0, y: 5.0 },
+                font_color: String::from(constant::color::LABEL),
+            }),
+            ..object(None)
+        };
+        let camera = Camera {
+            offset: geometry::Point { x: 10.0, y: 5.0 },
+            zoom: 2.0,
+        };
+
+        let screen_object = project_object(&object, &camera);
+
+        let expected_vertices = vec![
+            Point { x: 0.0, y: 10.0 },
+            Point { x: 20.0, y: 10.0 },
+            Point { x: 20.0, y: 30.0 },
+        ];
+        assert_eq!(expected_vertices, screen_object.shape.vertices);
+        assert_eq!(
+            Point { x: 10.0, y: 0.0 },
+            screen_object.name_label.unwrap().location
+        );
+    }
 }
@@ -4,14 +4,28 @@ pub(crate) mod color {
     pub(crate) const PLANT: &str = "green";
     pub(crate) const TERRAIN: &str = "brown";
     pub(crate) const LABEL: &str = "black";
+    pub(crate) const GRID: &str = "lightgray";
+    pub(crate) const SELECTION: &str = "red";
 }
 
 pub(crate) mod offset {
     use myelin_engine::geometry::Point;
 
     pub(crate) const NAME_OFFSET: Point = Point { x: 0.0, y: -10.0 };
+    pub(crate) const FPS_LABEL: Point = Point { x: 10.0, y: 20.0 };
+    pub(crate) const STATS_LABEL: Point = Point { x: 10.0, y: 40.0 };
 }
 
 pub(crate) mod alignment {
     pub(crate) const CENTER: &str = "center";
+    pub(crate) const LEFT: &str = "left";
+}
+
+pub(crate) mod line_width {
+    pub(crate) const GRID: f64 = 0.5;
+    pub(crate) const SELECTION: f64 = 2.0;
+}
+
+pub(crate) mod line_height {
+    pub(crate) const LABEL: f64 = 16.0;
 }
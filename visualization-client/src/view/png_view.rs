@@ -0,0 +1,266 @@
+//! A view that renders into PNG files instead of a canvas
+
+use super::{compare_objects, map_scalar_to_rgb};
+use crate::presenter::View;
+use crate::view_model::*;
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+type Color = [u8; 4];
+
+const BACKGROUND_COLOR: Color = [255, 255, 255, 255];
+const ORGANISM_COLOR: Color = [255, 165, 0, 255];
+const PLANT_COLOR: Color = [0, 128, 0, 255];
+const WATER_COLOR: Color = [0, 0, 255, 255];
+const TERRAIN_COLOR: Color = [165, 42, 42, 255];
+
+/// A [`View`] that rasterizes the objects of every frame and writes them
+/// as consecutively numbered PNG files into a directory.
+/// Text, such as names or the FPS counter, is not rendered.
+#[derive(Debug)]
+pub(crate) struct PngView {
+    frame_buffer: RefCell<FrameBuffer>,
+    output_directory: PathBuf,
+    next_frame_number: Cell<usize>,
+}
+
+impl View for PngView {
+    fn draw_objects(&self, mut objects: Vec<Object>) {
+        objects.sort_by(compare_objects);
+
+        let mut frame_buffer = self.frame_buffer.borrow_mut();
+
+        for object in &objects {
+            frame_buffer.fill_polygon(&object.shape.vertices, map_object_to_color(object));
+        }
+
+        let frame_number = self.next_frame_number.get();
+        self.next_frame_number.set(frame_number + 1);
+
+        let path = self
+            .output_directory
+            .join(format!("frame_{:06}.png", frame_number));
+
+        frame_buffer.write_png(&path).unwrap_or_else(|error| {
+            panic!(
+                "Unable to write frame to {}. Error: {}",
+                path.display(),
+                error
+            )
+        });
+    }
+
+    fn draw_fps(&self, _fps: f64) {}
+
+    fn draw_stats(&self, _lines: Vec<String>) {}
+
+    fn redraw(&self) {}
+
+    fn flush(&self) {
+        self.frame_buffer.borrow_mut().clear();
+    }
+}
+
+impl PngView {
+    /// Creates a new [`PngView`] that writes frames of
+    /// `width` x `height` pixels into `output_directory`.
+    pub(crate) fn new(width: u32, height: u32, output_directory: PathBuf) -> Self {
+        Self {
+            frame_buffer: RefCell::new(FrameBuffer::new(width, height)),
+            output_directory,
+            next_frame_number: Cell::new(0),
+        }
+    }
+}
+
+/// RGBA pixels, stored row by row
+#[derive(Debug)]
+struct FrameBuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl FrameBuffer {
+    fn new(width: u32, height: u32) -> Self {
+        let mut frame_buffer = Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * BACKGROUND_COLOR.len()],
+        };
+        frame_buffer.clear();
+        frame_buffer
+    }
+
+    fn clear(&mut self) {
+        for pixel in self.pixels.chunks_mut(BACKGROUND_COLOR.len()) {
+            pixel.copy_from_slice(&BACKGROUND_COLOR);
+        }
+    }
+
+    /// Fills every pixel whose center lies inside of the polygon,
+    /// using the even-odd rule.
+    fn fill_polygon(&mut self, vertices: &[Point], color: Color) {
+        for row in 0..self.height {
+            let scanline = f64::from(row) + 0.5;
+            let mut intersections = scanline_intersections(vertices, scanline);
+            intersections.sort_by(|a, b| a.partial_cmp(b).expect("Vertex is not a number"));
+
+            for span in intersections.chunks_exact(2) {
+                let first_column = self.clamp_column(span[0]);
+                let last_column = self.clamp_column(span[1]);
+
+                for column in first_column..last_column {
+                    self.set_pixel(column, row, color);
+                }
+            }
+        }
+    }
+
+    /// The first column whose center lies right of `x`
+    fn clamp_column(&self, x: f64) -> u32 {
+        (x - 0.5).ceil().max(0.0).min(f64::from(self.width)) as u32
+    }
+
+    fn set_pixel(&mut self, column: u32, row: u32, color: Color) {
+        let index = (row as usize * self.width as usize + column as usize) * color.len();
+        self.pixels[index..index + color.len()].copy_from_slice(&color);
+    }
+
+    fn write_png(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let writer = BufWriter::new(File::create(path)?);
+
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::RGBA);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+
+        Ok(())
+    }
+}
+
+/// The x coordinates at which the edges of the polygon cross the horizontal line at `y`
+fn scanline_intersections(vertices: &[Point], y: f64) -> Vec<f64> {
+    let following_vertices = vertices.iter().cycle().skip(1);
+
+    vertices
+        .iter()
+        .zip(following_vertices)
+        .filter(|(start, end)| (start.y <= y) != (end.y <= y))
+        .map(|(start, end)| start.x + (y - start.y) * (end.x - start.x) / (end.y - start.y))
+        .collect()
+}
+
+fn map_object_to_color(object: &Object) -> Color {
+    match object.color_scalar {
+        Some(color_scalar) => {
+            let [red, green, blue] = map_scalar_to_rgb(color_scalar);
+            [red, green, blue, 255]
+        }
+        None => map_kind_to_color(&object.kind),
+    }
+}
+
+fn map_kind_to_color(kind: &Kind) -> Color {
+    match kind {
+        Kind::Organism => ORGANISM_COLOR,
+        Kind::Plant => PLANT_COLOR,
+        Kind::Water => WATER_COLOR,
+        Kind::Terrain => TERRAIN_COLOR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    const FRAME_SIZE: u32 = 20;
+
+    fn output_directory(test_name: &str) -> PathBuf {
+        let output_directory =
+            env::temp_dir().join(format!("myelin-png-view-{}-{}", test_name, process::id()));
+        fs::create_dir_all(&output_directory).unwrap();
+        output_directory
+    }
+
+    fn square_object(kind: Kind) -> Object {
+        Object {
+            shape: Polygon {
+                vertices: vec![
+                    Point { x: 5.0, y: 5.0 },
+                    Point { x: 15.0, y: 5.0 },
+                    Point { x: 15.0, y: 15.0 },
+                    Point { x: 5.0, y: 15.0 },
+                ],
+            },
+            kind,
+            height: 1.0,
+            name_label: None,
+            movement: None,
+            color_scalar: None,
+            is_selected: false,
+        }
+    }
+
+    fn read_png(path: &Path) -> Vec<u8> {
+        let decoder = png::Decoder::new(File::open(path).unwrap());
+        let (info, mut reader) = decoder.read_info().unwrap();
+        assert_eq!((FRAME_SIZE, FRAME_SIZE), (info.width, info.height));
+
+        let mut pixels = vec![0; info.buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        pixels
+    }
+
+    fn pixel_at(pixels: &[u8], column: usize, row: usize) -> Color {
+        let index = (row * FRAME_SIZE as usize + column) * 4;
+        let mut color = Color::default();
+        color.copy_from_slice(&pixels[index..index + 4]);
+        color
+    }
+
+    #[test]
+    fn renders_object_at_its_location() {
+        let output_directory = output_directory("renders-object");
+        let view = PngView::new(FRAME_SIZE, FRAME_SIZE, output_directory.clone());
+
+        view.flush();
+        view.draw_objects(vec![square_object(Kind::Plant)]);
+
+        let pixels = read_png(&output_directory.join("frame_000000.png"));
+        fs::remove_dir_all(&output_directory).unwrap();
+
+        assert_eq!(PLANT_COLOR, pixel_at(&pixels, 5, 5));
+        assert_eq!(PLANT_COLOR, pixel_at(&pixels, 10, 10));
+        assert_eq!(PLANT_COLOR, pixel_at(&pixels, 14, 14));
+        assert_eq!(BACKGROUND_COLOR, pixel_at(&pixels, 4, 10));
+        assert_eq!(BACKGROUND_COLOR, pixel_at(&pixels, 15, 10));
+        assert_eq!(BACKGROUND_COLOR, pixel_at(&pixels, 10, 15));
+    }
+
+    #[test]
+    fn frames_are_numbered_consecutively() {
+        let output_directory = output_directory("numbers-frames");
+        let view = PngView::new(FRAME_SIZE, FRAME_SIZE, output_directory.clone());
+
+        for _ in 0..2 {
+            view.flush();
+            view.draw_objects(vec![square_object(Kind::Water)]);
+        }
+
+        let first_frame_exists = output_directory.join("frame_000000.png").exists();
+        let second_frame_exists = output_directory.join("frame_000001.png").exists();
+        fs::remove_dir_all(&output_directory).unwrap();
+
+        assert!(first_frame_exists);
+        assert!(second_frame_exists);
+    }
+}
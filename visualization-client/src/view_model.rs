@@ -1,9 +1,22 @@
+use std::cmp::Ordering;
+
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct Object {
     pub(crate) shape: Polygon,
     pub(crate) kind: Kind,
     pub(crate) height: f64,
     pub(crate) name_label: Option<Label>,
+
+    /// The distance the object has moved since the previous delta.
+    /// `None` if the object is not animated, e.g. because it is immovable or new.
+    pub(crate) movement: Option<Point>,
+
+    /// A value between `0.0` and `1.0` that the object is tinted by
+    /// instead of the color of its kind. `None` if the object uses its kind's color.
+    pub(crate) color_scalar: Option<f64>,
+
+    /// Whether the user selected this object by clicking on it
+    pub(crate) is_selected: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -37,3 +50,11 @@ pub(crate) struct Label {
     /// The font color to use
     pub(crate) font_color: String,
 }
+
+/// Orders objects by their height, which is also the order in which they are drawn.
+/// Unlike [`f64::partial_cmp`], this never fails: `NaN` heights are ordered above all others.
+pub(crate) fn compare_heights(height_one: f64, height_two: f64) -> Ordering {
+    height_one
+        .partial_cmp(&height_two)
+        .unwrap_or_else(|| height_one.is_nan().cmp(&height_two.is_nan()))
+}
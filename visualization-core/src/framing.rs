@@ -0,0 +1,117 @@
+//! Framing of the messages sent from the server to the client.
+//!
+//! Every message consists of a single byte identifying its [`MessageKind`],
+//! followed by the payload as serialized by a [`ViewModelSerializer`].
+//!
+//! [`ViewModelSerializer`]: ../serialization/trait.ViewModelSerializer.html
+
+use std::error::Error;
+use std::fmt;
+
+/// Identifies what a message contains
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MessageKind {
+    /// A serialized [`ViewModelDelta`]
+    ///
+    /// [`ViewModelDelta`]: ../view_model_delta/type.ViewModelDelta.html
+    ViewModelDelta,
+    /// Serialized [`SimulationStats`]
+    ///
+    /// [`SimulationStats`]: ../simulation_stats/struct.SimulationStats.html
+    SimulationStats,
+}
+
+impl MessageKind {
+    fn tag(self) -> u8 {
+        match self {
+            MessageKind::ViewModelDelta => 0,
+            MessageKind::SimulationStats => 1,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(MessageKind::ViewModelDelta),
+            1 => Some(MessageKind::SimulationStats),
+            _ => None,
+        }
+    }
+}
+
+/// Prefixes a serialized payload with its [`MessageKind`].
+pub fn frame_message(kind: MessageKind, payload: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(payload.len() + 1);
+    message.push(kind.tag());
+    message.extend_from_slice(payload);
+    message
+}
+
+/// Splits a message created by [`frame_message`] into its [`MessageKind`] and payload.
+///
+/// # Errors
+/// Returns an [`InvalidMessageError`] if the message is empty or of an unknown kind.
+pub fn split_message(message: &[u8]) -> Result<(MessageKind, &[u8]), InvalidMessageError> {
+    let (&tag, payload) = message.split_first().ok_or(InvalidMessageError::Empty)?;
+    let kind = MessageKind::from_tag(tag).ok_or(InvalidMessageError::UnknownKind(tag))?;
+    Ok((kind, payload))
+}
+
+/// The reason why a message could not be split by [`split_message`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InvalidMessageError {
+    /// The message did not contain any bytes
+    Empty,
+    /// The message started with a tag that does not belong to any [`MessageKind`]
+    UnknownKind(u8),
+}
+
+impl Error for InvalidMessageError {}
+
+impl fmt::Display for InvalidMessageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidMessageError::Empty => write!(f, "The message is empty"),
+            InvalidMessageError::UnknownKind(tag) => {
+                write!(f, "The message is of an unknown kind ({})", tag)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAYLOAD: [u8; 3] = [4, 2, 42];
+
+    #[test]
+    fn framed_view_model_delta_can_be_split() {
+        let message = frame_message(MessageKind::ViewModelDelta, &PAYLOAD);
+        assert_eq!(
+            Ok((MessageKind::ViewModelDelta, &PAYLOAD[..])),
+            split_message(&message)
+        );
+    }
+
+    #[test]
+    fn framed_simulation_stats_can_be_split() {
+        let message = frame_message(MessageKind::SimulationStats, &PAYLOAD);
+        assert_eq!(
+            Ok((MessageKind::SimulationStats, &PAYLOAD[..])),
+            split_message(&message)
+        );
+    }
+
+    #[test]
+    fn empty_message_is_rejected() {
+        assert_eq!(Err(InvalidMessageError::Empty), split_message(&[]));
+    }
+
+    #[test]
+    fn message_of_unknown_kind_is_rejected() {
+        assert_eq!(
+            Err(InvalidMessageError::UnknownKind(200)),
+            split_message(&[200, 1, 2])
+        );
+    }
+}
@@ -16,5 +16,8 @@
     clippy::explicit_into_iter_loop
 )]
 
+pub mod framing;
+pub mod recording;
 pub mod serialization;
+pub mod simulation_stats;
 pub mod view_model_delta;
@@ -0,0 +1,151 @@
+//! A simple file format for recorded streams of serialized [`ViewModelDelta`]s.
+//!
+//! A recording is a sequence of entries, each consisting of
+//! the timestamp in milliseconds (`u64`, little endian),
+//! the length of the serialized delta in bytes (`u32`, little endian)
+//! and the serialized delta itself.
+//!
+//! [`ViewModelDelta`]: ../view_model_delta/type.ViewModelDelta.html
+
+use std::convert::TryInto;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+const TIMESTAMP_SIZE: usize = 8;
+const LENGTH_SIZE: usize = 4;
+
+/// A serialized [`ViewModelDelta`] together with the time it was sent at.
+///
+/// [`ViewModelDelta`]: ../view_model_delta/type.ViewModelDelta.html
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RecordedDelta {
+    /// The time since the start of the recording
+    pub timestamp: Duration,
+    /// The delta as serialized by a [`ViewModelSerializer`]
+    ///
+    /// [`ViewModelSerializer`]: ../serialization/trait.ViewModelSerializer.html
+    pub serialized_delta: Vec<u8>,
+}
+
+/// Appends a [`RecordedDelta`] to a recording.
+///
+/// # Errors
+/// Returns an error if writing fails or if the serialized delta does not fit into an entry.
+pub fn write_recorded_delta(
+    writer: &mut dyn Write,
+    recorded_delta: &RecordedDelta,
+) -> io::Result<()> {
+    let timestamp = recorded_delta.timestamp.as_millis() as u64;
+    let length: u32 = recorded_delta
+        .serialized_delta
+        .len()
+        .try_into()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Delta is too large"))?;
+
+    writer.write_all(&timestamp.to_le_bytes())?;
+    writer.write_all(&length.to_le_bytes())?;
+    writer.write_all(&recorded_delta.serialized_delta)?;
+
+    Ok(())
+}
+
+/// Reads all [`RecordedDelta`]s from a recording.
+///
+/// # Errors
+/// Returns a [`TruncatedRecordingError`] if the recording ends in the middle of an entry.
+pub fn read_recorded_deltas(
+    mut recording: &[u8],
+) -> Result<Vec<RecordedDelta>, TruncatedRecordingError> {
+    let mut recorded_deltas = Vec::new();
+
+    while !recording.is_empty() {
+        let (timestamp, rest) = split_entry_part(recording, TIMESTAMP_SIZE)?;
+        let (length, rest) = split_entry_part(rest, LENGTH_SIZE)?;
+
+        let timestamp = u64::from_le_bytes(timestamp.try_into().unwrap());
+        let length = u32::from_le_bytes(length.try_into().unwrap()) as usize;
+
+        let (serialized_delta, rest) = split_entry_part(rest, length)?;
+
+        recorded_deltas.push(RecordedDelta {
+            timestamp: Duration::from_millis(timestamp),
+            serialized_delta: serialized_delta.to_vec(),
+        });
+
+        recording = rest;
+    }
+
+    Ok(recorded_deltas)
+}
+
+fn split_entry_part(buf: &[u8], size: usize) -> Result<(&[u8], &[u8]), TruncatedRecordingError> {
+    if buf.len() < size {
+        Err(TruncatedRecordingError)
+    } else {
+        Ok(buf.split_at(size))
+    }
+}
+
+/// The recording ended in the middle of an entry.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TruncatedRecordingError;
+
+impl Error for TruncatedRecordingError {}
+
+impl fmt::Display for TruncatedRecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The recording ended in the middle of an entry")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded_deltas() -> Vec<RecordedDelta> {
+        vec![
+            RecordedDelta {
+                timestamp: Duration::from_millis(0),
+                serialized_delta: vec![1, 2, 3],
+            },
+            RecordedDelta {
+                timestamp: Duration::from_millis(16),
+                serialized_delta: Vec::new(),
+            },
+            RecordedDelta {
+                timestamp: Duration::from_millis(33),
+                serialized_delta: vec![4, 5],
+            },
+        ]
+    }
+
+    #[test]
+    fn recorded_deltas_round_trip() {
+        let mut recording = Vec::new();
+
+        for recorded_delta in &recorded_deltas() {
+            write_recorded_delta(&mut recording, recorded_delta).unwrap();
+        }
+
+        assert_eq!(Ok(recorded_deltas()), read_recorded_deltas(&recording));
+    }
+
+    #[test]
+    fn empty_recording_contains_no_deltas() {
+        assert_eq!(Ok(Vec::new()), read_recorded_deltas(&[]));
+    }
+
+    #[test]
+    fn truncated_recording_is_rejected() {
+        let mut recording = Vec::new();
+        write_recorded_delta(&mut recording, &recorded_deltas()[0]).unwrap();
+        recording.pop();
+
+        assert_eq!(
+            Err(TruncatedRecordingError),
+            read_recorded_deltas(&recording)
+        );
+    }
+}
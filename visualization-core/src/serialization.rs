@@ -4,9 +4,10 @@
 pub use self::bincode::*;
 #[cfg(feature = "use-json")]
 pub use self::json::*;
-use crate::view_model_delta::ViewModelDelta;
+use crate::simulation_stats::SimulationStats;
+use crate::view_model_delta::{DeltaEncoding, ViewModelDelta};
 use std::error::Error;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 #[cfg(feature = "use-bincode")]
 mod bincode;
@@ -25,6 +26,16 @@ pub trait ViewModelSerializer: Debug {
         &self,
         view_model_delta: &ViewModelDelta,
     ) -> Result<Vec<u8>, Box<dyn Error>>;
+
+    /// Serializes [`SimulationStats`] into a binary representation which can
+    /// be deserialized using [`ViewModelDeserializer::deserialize_simulation_stats`].
+    ///
+    /// [`SimulationStats`]: ../simulation_stats/struct.SimulationStats.html
+    /// [`ViewModelDeserializer::deserialize_simulation_stats`]: ./trait.ViewModelDeserializer.html#tymethod.deserialize_simulation_stats
+    fn serialize_simulation_stats(
+        &self,
+        simulation_stats: &SimulationStats,
+    ) -> Result<Vec<u8>, Box<dyn Error>>;
 }
 
 /// Deserializes [`ViewModelDelta`]s which were previously serialized with a [`ViewModelSerializer`].
@@ -33,4 +44,97 @@ pub trait ViewModelDeserializer: Debug {
     ///
     /// [`ViewModelDelta`]: ../view_model_delta/type.ViewModelDelta.html
     fn deserialize_view_model_delta(&self, buf: &[u8]) -> Result<ViewModelDelta, Box<dyn Error>>;
+
+    /// Deserializes [`SimulationStats`] from their binary representation.
+    ///
+    /// [`SimulationStats`]: ../simulation_stats/struct.SimulationStats.html
+    fn deserialize_simulation_stats(&self, buf: &[u8]) -> Result<SimulationStats, Box<dyn Error>>;
+}
+
+/// Prefixes a serialized [`ViewModelDelta`] with the [`DeltaEncoding`] it was serialized in.
+fn tag_encoding(encoding: DeltaEncoding, payload: &[u8]) -> Vec<u8> {
+    let mut tagged = Vec::with_capacity(payload.len() + 1);
+    tagged.push(encoding.tag());
+    tagged.extend_from_slice(payload);
+    tagged
+}
+
+/// Splits a delta created by [`tag_encoding`] into its [`DeltaEncoding`] and payload.
+fn split_encoding(buf: &[u8]) -> Result<(DeltaEncoding, &[u8]), InvalidDeltaEncodingError> {
+    let (&tag, payload) = buf.split_first().ok_or(InvalidDeltaEncodingError::Empty)?;
+    let encoding =
+        DeltaEncoding::from_tag(tag).ok_or(InvalidDeltaEncodingError::UnknownEncoding(tag))?;
+    Ok((encoding, payload))
+}
+
+/// The reason why a serialized [`ViewModelDelta`] could not be deserialized
+/// before even looking at its payload
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InvalidDeltaEncodingError {
+    /// The serialized delta did not contain any bytes
+    Empty,
+    /// The serialized delta started with a tag that does not belong to any [`DeltaEncoding`]
+    UnknownEncoding(u8),
+}
+
+impl Error for InvalidDeltaEncodingError {}
+
+impl fmt::Display for InvalidDeltaEncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidDeltaEncodingError::Empty => write!(f, "The serialized delta is empty"),
+            InvalidDeltaEncodingError::UnknownEncoding(tag) => {
+                write!(f, "The serialized delta has an unknown encoding ({})", tag)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_utils {
+    use crate::view_model_delta::*;
+    use myelin_engine::prelude::*;
+
+    pub(crate) fn moved_objects_view_model_delta(object_count: usize) -> ViewModelDelta {
+        (0..object_count)
+            .map(|id| {
+                let object_description_delta = ObjectDescriptionDelta {
+                    location: Some(Point {
+                        x: id as f64 * 1.5,
+                        y: id as f64 * 0.5,
+                    }),
+                    rotation: Some(Radians::try_new(0.25).unwrap()),
+                    ..ObjectDescriptionDelta::default()
+                };
+                (id, ObjectDelta::Updated(object_description_delta))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tagged_delta_can_be_split() {
+        let tagged = tag_encoding(DeltaEncoding::Compact, &[4, 2]);
+        assert_eq!(
+            Ok((DeltaEncoding::Compact, &[4, 2][..])),
+            split_encoding(&tagged)
+        );
+    }
+
+    #[test]
+    fn empty_delta_is_rejected() {
+        assert_eq!(Err(InvalidDeltaEncodingError::Empty), split_encoding(&[]));
+    }
+
+    #[test]
+    fn delta_with_unknown_encoding_is_rejected() {
+        assert_eq!(
+            Err(InvalidDeltaEncodingError::UnknownEncoding(200)),
+            split_encoding(&[200, 1, 2])
+        );
+    }
 }
@@ -1,8 +1,11 @@
 //! Implementation of [`ViewModelSerializer`] and [`ViewModelDeserializer`] using
 //! [`bincode`], a compact binary encoding format.
 
-use crate::serialization::{ViewModelDeserializer, ViewModelSerializer};
-use crate::view_model_delta::ViewModelDelta;
+use crate::serialization::{
+    split_encoding, tag_encoding, ViewModelDeserializer, ViewModelSerializer,
+};
+use crate::simulation_stats::SimulationStats;
+use crate::view_model_delta::{CompactViewModelDelta, DeltaEncoding, ViewModelDelta};
 use std::error::Error;
 use std::marker::PhantomData;
 
@@ -21,13 +24,20 @@ use std::marker::PhantomData;
 ///
 /// [`bincode`]: https://github.com/TyOverby/bincode
 #[derive(Debug, Default)]
-pub struct BincodeSerializer(PhantomData<()>);
+pub struct BincodeSerializer {
+    encoding: DeltaEncoding,
+}
 
 impl BincodeSerializer {
     /// Returns a new [`BincodeSerializer`]
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Returns a new [`BincodeSerializer`] that uses the given [`DeltaEncoding`]
+    pub fn with_encoding(encoding: DeltaEncoding) -> Self {
+        Self { encoding }
+    }
 }
 
 impl ViewModelSerializer for BincodeSerializer {
@@ -35,7 +45,21 @@ impl ViewModelSerializer for BincodeSerializer {
         &self,
         view_model_delta: &ViewModelDelta,
     ) -> Result<Vec<u8>, Box<dyn Error>> {
-        Ok(bincode::serialize(view_model_delta)?)
+        let serialized = match self.encoding {
+            DeltaEncoding::Verbose => bincode::serialize(view_model_delta)?,
+            DeltaEncoding::Compact => {
+                bincode::serialize(&CompactViewModelDelta::from(view_model_delta))?
+            }
+        };
+
+        Ok(tag_encoding(self.encoding, &serialized))
+    }
+
+    fn serialize_simulation_stats(
+        &self,
+        simulation_stats: &SimulationStats,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(bincode::serialize(simulation_stats)?)
     }
 }
 
@@ -66,6 +90,18 @@ impl BincodeDeserializer {
 
 impl ViewModelDeserializer for BincodeDeserializer {
     fn deserialize_view_model_delta(&self, buf: &[u8]) -> Result<ViewModelDelta, Box<dyn Error>> {
+        let (encoding, payload) = split_encoding(buf)?;
+        match encoding {
+            DeltaEncoding::Verbose => Ok(bincode::deserialize(payload)?),
+            DeltaEncoding::Compact => {
+                let compact_view_model_delta: CompactViewModelDelta =
+                    bincode::deserialize(payload)?;
+                Ok(compact_view_model_delta.into_view_model_delta()?)
+            }
+        }
+    }
+
+    fn deserialize_simulation_stats(&self, buf: &[u8]) -> Result<SimulationStats, Box<dyn Error>> {
         Ok(bincode::deserialize(buf)?)
     }
 }
@@ -73,6 +109,7 @@ impl ViewModelDeserializer for BincodeDeserializer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::serialization::test_utils::moved_objects_view_model_delta;
     use crate::view_model_delta::*;
     use maplit::hashmap;
     use myelin_engine::geometry::*;
@@ -169,6 +206,101 @@ mod tests {
         assert_eq!(expected, deserialized);
     }
 
+    #[test]
+    fn compact_encoding_round_trips() {
+        let view_model_delta = moved_objects_view_model_delta(100);
+
+        let serialized = BincodeSerializer::with_encoding(DeltaEncoding::Compact)
+            .serialize_view_model_delta(&view_model_delta)
+            .unwrap();
+        let deserialized = BincodeDeserializer::default()
+            .deserialize_view_model_delta(&serialized)
+            .unwrap();
+
+        assert_eq!(view_model_delta, deserialized);
+    }
+
+    #[test]
+    fn compact_encoding_round_trips_rotation_update() {
+        let view_model_delta = hashmap! {
+            7 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                rotation: Some(Radians::try_new(0.5).unwrap()),
+                ..ObjectDescriptionDelta::default()
+            }),
+        };
+
+        let serialized = BincodeSerializer::with_encoding(DeltaEncoding::Compact)
+            .serialize_view_model_delta(&view_model_delta)
+            .unwrap();
+        let deserialized = BincodeDeserializer::default()
+            .deserialize_view_model_delta(&serialized)
+            .unwrap();
+
+        assert_eq!(view_model_delta, deserialized);
+    }
+
+    #[test]
+    fn deserializer_follows_encoding_of_each_delta() {
+        let view_model_delta = moved_objects_view_model_delta(3);
+        let deserializer = BincodeDeserializer::default();
+
+        for &encoding in &[DeltaEncoding::Verbose, DeltaEncoding::Compact] {
+            let serialized = BincodeSerializer::with_encoding(encoding)
+                .serialize_view_model_delta(&view_model_delta)
+                .unwrap();
+            let deserialized = deserializer
+                .deserialize_view_model_delta(&serialized)
+                .unwrap();
+
+            assert_eq!(view_model_delta, deserialized);
+        }
+    }
+
+    #[test]
+    fn deserializing_delta_with_unknown_encoding_fails() {
+        let deserializer = BincodeDeserializer::default();
+        assert!(deserializer.deserialize_view_model_delta(&[200]).is_err());
+    }
+
+    #[test]
+    fn compact_encoding_is_smaller_than_verbose_encoding_for_moved_objects() {
+        let view_model_delta = moved_objects_view_model_delta(100);
+
+        let verbose = BincodeSerializer::with_encoding(DeltaEncoding::Verbose)
+            .serialize_view_model_delta(&view_model_delta)
+            .unwrap();
+        let compact = BincodeSerializer::with_encoding(DeltaEncoding::Compact)
+            .serialize_view_model_delta(&view_model_delta)
+            .unwrap();
+
+        assert!(
+            compact.len() < verbose.len(),
+            "compact: {} bytes, verbose: {} bytes",
+            compact.len(),
+            verbose.len()
+        );
+    }
+
+    #[test]
+    fn simulation_stats_round_trip() {
+        let simulation_stats = SimulationStats {
+            step: 1234,
+            counts_by_kind: hashmap! {
+                Kind::Organism => 3,
+                Kind::Plant => 12,
+            },
+        };
+
+        let serialized = BincodeSerializer::default()
+            .serialize_simulation_stats(&simulation_stats)
+            .unwrap();
+        let deserialized = BincodeDeserializer::default()
+            .deserialize_simulation_stats(&serialized)
+            .unwrap();
+
+        assert_eq!(simulation_stats, deserialized);
+    }
+
     fn associated_data() -> AdditionalObjectDescription {
         AdditionalObjectDescription {
             name: Some(String::from("Cat")),
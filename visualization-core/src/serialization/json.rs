@@ -1,9 +1,21 @@
-use crate::serialization::{ViewModelDeserializer, ViewModelSerializer};
-use crate::view_model_delta::ViewModelDelta;
+use crate::serialization::{
+    split_encoding, tag_encoding, ViewModelDeserializer, ViewModelSerializer,
+};
+use crate::simulation_stats::SimulationStats;
+use crate::view_model_delta::{CompactViewModelDelta, DeltaEncoding, ViewModelDelta};
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::marker::PhantomData;
 
 /// Provides methods for JSON serialization.
+///
+/// Serialized [`ViewModelDelta`]s start with a single byte
+/// identifying their [`DeltaEncoding`], followed by the JSON itself.
+///
+/// Objects are always emitted in ascending order of their [`Id`],
+/// so serializing the same [`ViewModelDelta`] twice yields identical bytes.
+///
+/// [`Id`]: ../../myelin_engine/object/type.Id.html
 /// # Examples
 /// ```
 /// use myelin_visualization_core::serialization::{JsonSerializer, ViewModelSerializer};
@@ -14,7 +26,16 @@ use std::marker::PhantomData;
 /// let serialized = serializer.serialize_view_model_delta(&view_model_delta);
 /// ```
 #[derive(Debug, Default)]
-pub struct JsonSerializer(PhantomData<()>);
+pub struct JsonSerializer {
+    encoding: DeltaEncoding,
+}
+
+impl JsonSerializer {
+    /// Returns a new [`JsonSerializer`] that uses the given [`DeltaEncoding`]
+    pub fn with_encoding(encoding: DeltaEncoding) -> Self {
+        Self { encoding }
+    }
+}
 
 /// Provides methods for JSON deserialization
 /// # Examples
@@ -22,8 +43,8 @@ pub struct JsonSerializer(PhantomData<()>);
 /// use myelin_visualization_core::serialization::{JsonDeserializer, ViewModelDeserializer};
 /// use myelin_visualization_core::view_model_delta::ViewModelDelta;
 ///
-/// // Replace with a string that represents a ViewModelDelta
-/// let source: Vec<u8> = r#"{}"#.into();
+/// // Replace with a tagged string that represents a ViewModelDelta
+/// let source: Vec<u8> = "\u{0}{}".into();
 ///
 /// let deserializer = JsonDeserializer::default();
 /// let deserialized = deserializer.deserialize_view_model_delta(&source);
@@ -36,24 +57,53 @@ impl ViewModelSerializer for JsonSerializer {
         &self,
         view_model_delta: &ViewModelDelta,
     ) -> Result<Vec<u8>, Box<dyn Error>> {
-        let serialized = serde_json::to_string(view_model_delta)?;
+        let serialized = match self.encoding {
+            DeltaEncoding::Verbose => {
+                let ordered_view_model_delta: BTreeMap<_, _> = view_model_delta.iter().collect();
+                serde_json::to_string(&ordered_view_model_delta)?
+            }
+            DeltaEncoding::Compact => {
+                serde_json::to_string(&CompactViewModelDelta::from(view_model_delta))?
+            }
+        };
 
-        Ok(serialized.into())
+        Ok(tag_encoding(self.encoding, serialized.as_bytes()))
+    }
+
+    fn serialize_simulation_stats(
+        &self,
+        simulation_stats: &SimulationStats,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        Ok(serde_json::to_string(simulation_stats)?.into())
     }
 }
 
 impl ViewModelDeserializer for JsonDeserializer {
     fn deserialize_view_model_delta(&self, buf: &[u8]) -> Result<ViewModelDelta, Box<dyn Error>> {
-        let json_string = String::from_utf8(buf.to_vec())?;
-        let deserialized: ViewModelDelta = serde_json::from_str(&json_string)?;
+        let (encoding, payload) = split_encoding(buf)?;
+        let json_string = String::from_utf8(payload.to_vec())?;
+        let deserialized: ViewModelDelta = match encoding {
+            DeltaEncoding::Verbose => serde_json::from_str(&json_string)?,
+            DeltaEncoding::Compact => {
+                let compact_view_model_delta: CompactViewModelDelta =
+                    serde_json::from_str(&json_string)?;
+                compact_view_model_delta.into_view_model_delta()?
+            }
+        };
 
         Ok(deserialized)
     }
+
+    fn deserialize_simulation_stats(&self, buf: &[u8]) -> Result<SimulationStats, Box<dyn Error>> {
+        let json_string = String::from_utf8(buf.to_vec())?;
+        Ok(serde_json::from_str(&json_string)?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::serialization::test_utils::moved_objects_view_model_delta;
     use crate::view_model_delta::*;
     use maplit::hashmap;
     use myelin_engine::geometry::*;
@@ -64,7 +114,7 @@ mod tests {
 
     #[test]
     fn serializes_full_delta() {
-        let expected: Vec<u8> = EXPECTED_JSON.into();
+        let expected = tag_encoding(DeltaEncoding::Verbose, EXPECTED_JSON.as_bytes());
 
         let object_description_delta = ObjectDescriptionDelta {
             shape: Some(
@@ -103,9 +153,134 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    const EXPECTED_MULTI_OBJECT_JSON: &str = r#"{"1":{"Updated":{"shape":null,"location":{"x":1.0,"y":2.0},"rotation":null,"mobility":null,"associated_data":null}},"2":"Deleted","10":{"Updated":{"shape":null,"location":null,"rotation":{"value":0.5},"mobility":{"Movable":{"x":0.0,"y":-1.0}},"associated_data":null}}}"#;
+
+    #[test]
+    fn serializes_multiple_objects_ordered_by_id() {
+        let expected = tag_encoding(
+            DeltaEncoding::Verbose,
+            EXPECTED_MULTI_OBJECT_JSON.as_bytes(),
+        );
+
+        let serializer = JsonSerializer::default();
+        let serialized = serializer
+            .serialize_view_model_delta(&multi_object_view_model_delta())
+            .unwrap();
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn serializing_the_same_delta_twice_yields_identical_bytes() {
+        let view_model_delta = multi_object_view_model_delta();
+
+        let serializer = JsonSerializer::default();
+        let first_serialized = serializer
+            .serialize_view_model_delta(&view_model_delta)
+            .unwrap();
+        let second_serialized = serializer
+            .serialize_view_model_delta(&view_model_delta.clone())
+            .unwrap();
+
+        assert_eq!(first_serialized, second_serialized);
+    }
+
+    #[test]
+    fn deserializes_multiple_objects() {
+        let source = tag_encoding(
+            DeltaEncoding::Verbose,
+            EXPECTED_MULTI_OBJECT_JSON.as_bytes(),
+        );
+
+        let deserializer = JsonDeserializer::default();
+        let deserialized = deserializer.deserialize_view_model_delta(&source).unwrap();
+
+        assert_eq!(multi_object_view_model_delta(), deserialized);
+    }
+
+    const EXPECTED_COMPACT_JSON: &str =
+        r#"{"positions":[[1,[1.0,2.0],null],[3,[4.0,5.0],0.5]],"others":{"2":"Deleted"}}"#;
+
+    #[test]
+    fn serializes_position_updates_compactly() {
+        let expected = tag_encoding(DeltaEncoding::Compact, EXPECTED_COMPACT_JSON.as_bytes());
+
+        let serializer = JsonSerializer::with_encoding(DeltaEncoding::Compact);
+        let serialized = serializer
+            .serialize_view_model_delta(&compact_view_model_delta())
+            .unwrap();
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn deserializes_compact_delta() {
+        let source = tag_encoding(DeltaEncoding::Compact, EXPECTED_COMPACT_JSON.as_bytes());
+
+        let deserializer = JsonDeserializer::default();
+        let deserialized = deserializer.deserialize_view_model_delta(&source).unwrap();
+
+        assert_eq!(compact_view_model_delta(), deserialized);
+    }
+
+    #[test]
+    fn serializes_rotation_update_without_shape() {
+        let expected = tag_encoding(
+            DeltaEncoding::Compact,
+            br#"{"positions":[[7,null,0.5]],"others":{}}"#,
+        );
+
+        let view_model_delta = hashmap! {
+            7 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                rotation: Some(Radians::try_new(0.5).unwrap()),
+                ..ObjectDescriptionDelta::default()
+            }),
+        };
+
+        let serializer = JsonSerializer::with_encoding(DeltaEncoding::Compact);
+        let serialized = serializer
+            .serialize_view_model_delta(&view_model_delta)
+            .unwrap();
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn compact_encoding_round_trips() {
+        let view_model_delta = moved_objects_view_model_delta(100);
+
+        let serialized = JsonSerializer::with_encoding(DeltaEncoding::Compact)
+            .serialize_view_model_delta(&view_model_delta)
+            .unwrap();
+        let deserialized = JsonDeserializer::default()
+            .deserialize_view_model_delta(&serialized)
+            .unwrap();
+
+        assert_eq!(view_model_delta, deserialized);
+    }
+
+    #[test]
+    fn compact_encoding_is_smaller_than_verbose_encoding_for_moved_objects() {
+        let view_model_delta = moved_objects_view_model_delta(100);
+
+        let verbose = JsonSerializer::with_encoding(DeltaEncoding::Verbose)
+            .serialize_view_model_delta(&view_model_delta)
+            .unwrap();
+        let compact = JsonSerializer::with_encoding(DeltaEncoding::Compact)
+            .serialize_view_model_delta(&view_model_delta)
+            .unwrap();
+
+        assert!(
+            compact.len() * 2 < verbose.len(),
+            "compact: {} bytes, verbose: {} bytes",
+            compact.len(),
+            verbose.len()
+        );
+    }
+
     #[test]
     fn serialize_works_with_empty_view_model() {
-        let expected: Vec<u8> = r#"{}"#.into();
+        let expected = tag_encoding(DeltaEncoding::Verbose, b"{}");
 
         let view_model_delta = ViewModelDelta::default();
 
@@ -137,7 +312,7 @@ mod tests {
 
         let expected = hashmap! { 12 => ObjectDelta::Updated(object_description_delta) };
 
-        let source: Vec<u8> = EXPECTED_JSON.into();
+        let source = tag_encoding(DeltaEncoding::Verbose, EXPECTED_JSON.as_bytes());
 
         println!(
             "got {}\n expected {}",
@@ -160,7 +335,7 @@ mod tests {
     fn deserialize_works_with_empty_view_model() {
         let expected = ViewModelDelta::default();
 
-        let source: Vec<u8> = r#"{}"#.into();
+        let source = tag_encoding(DeltaEncoding::Verbose, b"{}");
 
         let deserializer = JsonDeserializer::default();
         let deserialized = deserializer.deserialize_view_model_delta(&source).unwrap();
@@ -168,6 +343,74 @@ mod tests {
         assert_eq!(expected, deserialized);
     }
 
+    #[test]
+    fn serializes_simulation_stats() {
+        let simulation_stats = SimulationStats {
+            step: 42,
+            counts_by_kind: hashmap! { Kind::Water => 2 },
+        };
+
+        let serialized = JsonSerializer::default()
+            .serialize_simulation_stats(&simulation_stats)
+            .unwrap();
+
+        assert_eq!(
+            String::from(r#"{"step":42,"counts_by_kind":{"Water":2}}"#),
+            String::from_utf8(serialized).unwrap()
+        );
+    }
+
+    #[test]
+    fn simulation_stats_round_trip() {
+        let simulation_stats = SimulationStats {
+            step: 1234,
+            counts_by_kind: hashmap! {
+                Kind::Organism => 3,
+                Kind::Plant => 12,
+                Kind::Terrain => 1,
+            },
+        };
+
+        let serialized = JsonSerializer::default()
+            .serialize_simulation_stats(&simulation_stats)
+            .unwrap();
+        let deserialized = JsonDeserializer::default()
+            .deserialize_simulation_stats(&serialized)
+            .unwrap();
+
+        assert_eq!(simulation_stats, deserialized);
+    }
+
+    fn compact_view_model_delta() -> ViewModelDelta {
+        hashmap! {
+            3 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                location: Some(Point { x: 4.0, y: 5.0 }),
+                rotation: Some(Radians::try_new(0.5).unwrap()),
+                ..ObjectDescriptionDelta::default()
+            }),
+            2 => ObjectDelta::Deleted,
+            1 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                location: Some(Point { x: 1.0, y: 2.0 }),
+                ..ObjectDescriptionDelta::default()
+            }),
+        }
+    }
+
+    fn multi_object_view_model_delta() -> ViewModelDelta {
+        hashmap! {
+            10 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                rotation: Some(Radians::try_new(0.5).unwrap()),
+                mobility: Some(Mobility::Movable(Vector { x: 0.0, y: -1.0 })),
+                ..ObjectDescriptionDelta::default()
+            }),
+            2 => ObjectDelta::Deleted,
+            1 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                location: Some(Point { x: 1.0, y: 2.0 }),
+                ..ObjectDescriptionDelta::default()
+            }),
+        }
+    }
+
     fn associated_data() -> AdditionalObjectDescription {
         AdditionalObjectDescription {
             name: Some(String::from("Cat")),
@@ -0,0 +1,19 @@
+//! Aggregated information about the state of a simulation
+
+use myelin_object_data::Kind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Lightweight statistics about a simulation, which can be displayed
+/// without having to reconstruct the whole snapshot.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SimulationStats {
+    /// The number of steps that have been simulated so far
+    pub step: u64,
+
+    /// The number of objects of each [`Kind`].
+    /// Kinds without any objects are omitted.
+    ///
+    /// [`Kind`]: ../../myelin_object_data/enum.Kind.html
+    pub counts_by_kind: HashMap<Kind, usize>,
+}
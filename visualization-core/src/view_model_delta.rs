@@ -4,7 +4,9 @@
 use myelin_engine::prelude::*;
 use myelin_object_data::{AdditionalObjectDescription, ObjectDescription};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
 
 /// This step's object deltas
 pub type ViewModelDelta = HashMap<Id, ObjectDelta>;
@@ -45,3 +47,412 @@ pub struct ObjectDescriptionDelta {
     /// Arbitrary data associated with this object
     pub associated_data: Option<AdditionalObjectDescription>,
 }
+
+/// Combines two consecutive deltas into one.
+pub trait Compose {
+    /// Returns a single delta that has the same effect as applying
+    /// `self` followed by `newer`.
+    fn compose(self, newer: Self) -> Self;
+}
+
+impl Compose for ViewModelDelta {
+    fn compose(mut self, newer: Self) -> Self {
+        for (id, newer_object_delta) in newer {
+            match self.remove(&id) {
+                Some(older_object_delta) => {
+                    if let Some(object_delta) = older_object_delta.compose(newer_object_delta) {
+                        self.insert(id, object_delta);
+                    }
+                }
+                None => {
+                    self.insert(id, newer_object_delta);
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl ObjectDelta {
+    /// Returns `None` if the two deltas cancel each other out,
+    /// i.e. the object was created and deleted again.
+    fn compose(self, newer: ObjectDelta) -> Option<ObjectDelta> {
+        match (self, newer) {
+            (ObjectDelta::Created(_), ObjectDelta::Deleted) => None,
+            (ObjectDelta::Created(mut object_description), ObjectDelta::Updated(delta)) => {
+                apply_object_description_delta(&mut object_description, delta);
+                Some(ObjectDelta::Created(object_description))
+            }
+            (ObjectDelta::Updated(older_delta), ObjectDelta::Updated(newer_delta)) => {
+                Some(ObjectDelta::Updated(older_delta.compose(newer_delta)))
+            }
+            (_, newer) => Some(newer),
+        }
+    }
+}
+
+impl Compose for ObjectDescriptionDelta {
+    fn compose(self, newer: Self) -> Self {
+        ObjectDescriptionDelta {
+            shape: newer.shape.or(self.shape),
+            location: newer.location.or(self.location),
+            rotation: newer.rotation.or(self.rotation),
+            mobility: newer.mobility.or(self.mobility),
+            associated_data: newer.associated_data.or(self.associated_data),
+        }
+    }
+}
+
+fn apply_object_description_delta(
+    object_description: &mut ObjectDescription,
+    delta: ObjectDescriptionDelta,
+) {
+    let ObjectDescriptionDelta {
+        shape,
+        location,
+        rotation,
+        mobility,
+        associated_data,
+    } = delta;
+
+    if let Some(shape) = shape {
+        object_description.shape = shape;
+    }
+
+    if let Some(location) = location {
+        object_description.location = location;
+    }
+
+    if let Some(rotation) = rotation {
+        object_description.rotation = rotation;
+    }
+
+    if let Some(mobility) = mobility {
+        object_description.mobility = mobility;
+    }
+
+    if let Some(associated_data) = associated_data {
+        object_description.associated_data = associated_data;
+    }
+}
+
+/// The wire format used when serializing a [`ViewModelDelta`].
+/// Serializers prefix every delta with the encoding they used,
+/// so deserializers accept either encoding without being configured.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum DeltaEncoding {
+    /// Every [`ObjectDelta`] is encoded with all of its field names
+    Verbose,
+    /// Updates that only touch the location and / or rotation
+    /// of an object are packed into a [`PositionDelta`] tuple
+    Compact,
+}
+
+impl DeltaEncoding {
+    pub(crate) fn tag(self) -> u8 {
+        match self {
+            DeltaEncoding::Verbose => 0,
+            DeltaEncoding::Compact => 1,
+        }
+    }
+
+    pub(crate) fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(DeltaEncoding::Verbose),
+            1 => Some(DeltaEncoding::Compact),
+            _ => None,
+        }
+    }
+}
+
+impl Default for DeltaEncoding {
+    fn default() -> Self {
+        DeltaEncoding::Verbose
+    }
+}
+
+/// A [`ViewModelDelta`] in its [`DeltaEncoding::Compact`] representation.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompactViewModelDelta {
+    /// Updates that only changed the location and / or rotation of an object,
+    /// ordered by [`Id`]
+    pub positions: Vec<PositionDelta>,
+
+    /// All remaining object deltas
+    pub others: BTreeMap<Id, ObjectDelta>,
+}
+
+/// An update that only touches the location and / or rotation of an object.
+/// Encoded as `[id, [x, y], rotation]`, where unchanged values are left empty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionDelta(pub Id, pub Option<(f64, f64)>, pub Option<f64>);
+
+/// The error returned when a [`CompactViewModelDelta`]
+/// contains a rotation that is not a valid [`Radians`] value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidRotationError {
+    /// The object with the invalid rotation
+    pub id: Id,
+    /// The offending rotation
+    pub rotation: f64,
+}
+
+impl Error for InvalidRotationError {}
+
+impl fmt::Display for InvalidRotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Object with id {} has an invalid rotation of {}",
+            self.id, self.rotation
+        )
+    }
+}
+
+impl From<&ViewModelDelta> for CompactViewModelDelta {
+    fn from(view_model_delta: &ViewModelDelta) -> Self {
+        let mut compact_view_model_delta = CompactViewModelDelta::default();
+
+        for (&id, object_delta) in view_model_delta {
+            match to_position_delta(id, object_delta) {
+                Some(position_delta) => compact_view_model_delta.positions.push(position_delta),
+                None => {
+                    compact_view_model_delta
+                        .others
+                        .insert(id, object_delta.clone());
+                }
+            }
+        }
+
+        compact_view_model_delta
+            .positions
+            .sort_by_key(|PositionDelta(id, ..)| *id);
+
+        compact_view_model_delta
+    }
+}
+
+impl CompactViewModelDelta {
+    /// Expands the compact representation back into a [`ViewModelDelta`].
+    ///
+    /// # Errors
+    /// Returns an [`InvalidRotationError`] if a [`PositionDelta`] contains an invalid rotation.
+    pub fn into_view_model_delta(self) -> Result<ViewModelDelta, InvalidRotationError> {
+        let mut view_model_delta: ViewModelDelta = self.others.into_iter().collect();
+
+        for PositionDelta(id, location, rotation) in self.positions {
+            let rotation = rotation
+                .map(|rotation| {
+                    Radians::try_new(rotation).map_err(|_| InvalidRotationError { id, rotation })
+                })
+                .transpose()?;
+
+            let object_description_delta = ObjectDescriptionDelta {
+                location: location.map(|(x, y)| Point { x, y }),
+                rotation,
+                ..ObjectDescriptionDelta::default()
+            };
+
+            view_model_delta.insert(id, ObjectDelta::Updated(object_description_delta));
+        }
+
+        Ok(view_model_delta)
+    }
+}
+
+fn to_position_delta(id: Id, object_delta: &ObjectDelta) -> Option<PositionDelta> {
+    match object_delta {
+        ObjectDelta::Updated(ObjectDescriptionDelta {
+            shape: None,
+            mobility: None,
+            associated_data: None,
+            location,
+            rotation,
+        }) if location.is_some() || rotation.is_some() => Some(PositionDelta(
+            id,
+            location.as_ref().map(|location| (location.x, location.y)),
+            rotation.as_ref().map(|rotation| rotation.value()),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maplit::hashmap;
+    use myelin_object_data::Kind;
+
+    fn object_description() -> ObjectDescription {
+        ObjectBuilder::default()
+            .shape(
+                PolygonBuilder::default()
+                    .vertex(-5.0, -5.0)
+                    .vertex(5.0, -5.0)
+                    .vertex(5.0, 5.0)
+                    .vertex(-5.0, 5.0)
+                    .build()
+                    .unwrap(),
+            )
+            .associated_data(AdditionalObjectDescription {
+                name: None,
+                kind: Kind::Plant,
+                height: 1.0,
+            })
+            .location(50.0, 50.0)
+            .mobility(Mobility::Immovable)
+            .build()
+            .unwrap()
+    }
+
+    fn location_update(x: f64, y: f64) -> ObjectDelta {
+        ObjectDelta::Updated(ObjectDescriptionDelta {
+            location: Some(Point { x, y }),
+            ..ObjectDescriptionDelta::default()
+        })
+    }
+
+    #[test]
+    fn compose_keeps_deltas_of_distinct_objects() {
+        let first = hashmap! { 1 => ObjectDelta::Deleted };
+        let second = hashmap! { 2 => location_update(1.0, 2.0) };
+
+        let expected = hashmap! {
+            1 => ObjectDelta::Deleted,
+            2 => location_update(1.0, 2.0),
+        };
+
+        assert_eq!(expected, first.compose(second));
+    }
+
+    #[test]
+    fn compose_merges_updates_with_newer_values_taking_precedence() {
+        let first = hashmap! {
+            1 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                location: Some(Point { x: 1.0, y: 1.0 }),
+                rotation: Some(Radians::try_new(1.0).unwrap()),
+                ..ObjectDescriptionDelta::default()
+            })
+        };
+        let second = hashmap! { 1 => location_update(2.0, 2.0) };
+
+        let expected = hashmap! {
+            1 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                location: Some(Point { x: 2.0, y: 2.0 }),
+                rotation: Some(Radians::try_new(1.0).unwrap()),
+                ..ObjectDescriptionDelta::default()
+            })
+        };
+
+        assert_eq!(expected, first.compose(second));
+    }
+
+    #[test]
+    fn compose_applies_updates_to_created_objects() {
+        let first = hashmap! { 1 => ObjectDelta::Created(object_description()) };
+        let second = hashmap! { 1 => location_update(3.0, 4.0) };
+
+        let mut expected_object_description = object_description();
+        expected_object_description.location = Point { x: 3.0, y: 4.0 };
+        let expected = hashmap! { 1 => ObjectDelta::Created(expected_object_description) };
+
+        assert_eq!(expected, first.compose(second));
+    }
+
+    #[test]
+    fn compose_drops_objects_that_were_created_and_deleted() {
+        let first = hashmap! { 1 => ObjectDelta::Created(object_description()) };
+        let second = hashmap! { 1 => ObjectDelta::Deleted };
+
+        assert_eq!(ViewModelDelta::new(), first.compose(second));
+    }
+
+    #[test]
+    fn compose_keeps_deletion_of_updated_objects() {
+        let first = hashmap! { 1 => location_update(3.0, 4.0) };
+        let second = hashmap! { 1 => ObjectDelta::Deleted };
+
+        assert_eq!(
+            hashmap! { 1 => ObjectDelta::Deleted },
+            first.compose(second)
+        );
+    }
+
+    #[test]
+    fn position_only_updates_are_packed() {
+        let view_model_delta = hashmap! {
+            2 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                rotation: Some(Radians::try_new(1.0).unwrap()),
+                ..ObjectDescriptionDelta::default()
+            }),
+            1 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                location: Some(Point { x: 3.0, y: 4.0 }),
+                ..ObjectDescriptionDelta::default()
+            }),
+        };
+
+        let expected = CompactViewModelDelta {
+            positions: vec![
+                PositionDelta(1, Some((3.0, 4.0)), None),
+                PositionDelta(2, None, Some(1.0)),
+            ],
+            others: BTreeMap::new(),
+        };
+
+        assert_eq!(expected, CompactViewModelDelta::from(&view_model_delta));
+    }
+
+    #[test]
+    fn other_deltas_are_not_packed() {
+        let view_model_delta = hashmap! {
+            1 => ObjectDelta::Deleted,
+            2 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                location: Some(Point { x: 3.0, y: 4.0 }),
+                mobility: Some(Mobility::Movable(Vector { x: 1.0, y: 1.0 })),
+                ..ObjectDescriptionDelta::default()
+            }),
+            3 => ObjectDelta::Updated(ObjectDescriptionDelta::default()),
+        };
+
+        let compact_view_model_delta = CompactViewModelDelta::from(&view_model_delta);
+
+        assert!(compact_view_model_delta.positions.is_empty());
+        assert_eq!(3, compact_view_model_delta.others.len());
+    }
+
+    #[test]
+    fn compact_view_model_delta_round_trips() {
+        let view_model_delta = hashmap! {
+            1 => ObjectDelta::Deleted,
+            2 => ObjectDelta::Updated(ObjectDescriptionDelta {
+                location: Some(Point { x: 3.0, y: 4.0 }),
+                rotation: Some(Radians::try_new(2.0).unwrap()),
+                ..ObjectDescriptionDelta::default()
+            }),
+        };
+
+        let compact_view_model_delta = CompactViewModelDelta::from(&view_model_delta);
+
+        assert_eq!(
+            Ok(view_model_delta),
+            compact_view_model_delta.into_view_model_delta()
+        );
+    }
+
+    #[test]
+    fn invalid_rotation_is_rejected() {
+        let compact_view_model_delta = CompactViewModelDelta {
+            positions: vec![PositionDelta(4, None, Some(-1.0))],
+            others: BTreeMap::new(),
+        };
+
+        assert_eq!(
+            Err(InvalidRotationError {
+                id: 4,
+                rotation: -1.0
+            }),
+            compact_view_model_delta.into_view_model_delta()
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use clap::{App, Arg};
-use myelin_visualization_server::start_server;
+use myelin_visualization_server::{start_server, start_server_with_recording};
 use std::net::{IpAddr, Ipv6Addr};
+use std::path::PathBuf;
 
 struct Arguments {
     host: IpAddr,
     port: u16,
+    recording_path: Option<PathBuf>,
 }
 
 fn parse_arguments() -> Arguments {
@@ -24,6 +26,14 @@ fn parse_arguments() -> Arguments {
                 .value_name("HOST")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("record")
+                .short("R")
+                .long("record")
+                .value_name("FILE")
+                .help("Records the delta stream to FILE for later replay")
+                .takes_value(true),
+        )
         .get_matches();
 
     const DEFAULT_PORT: u16 = 6956;
@@ -41,7 +51,13 @@ fn parse_arguments() -> Arguments {
         })
         .unwrap_or(DEFAULT_HOST);
 
-    Arguments { host, port }
+    let recording_path = matches.value_of("record").map(PathBuf::from);
+
+    Arguments {
+        host,
+        port,
+        recording_path,
+    }
 }
 
 fn main() {
@@ -49,5 +65,12 @@ fn main() {
 
     simple_logger::init().unwrap();
 
-    start_server((arguments.host, arguments.port));
+    let address = (arguments.host, arguments.port);
+
+    let server_handle = match arguments.recording_path {
+        Some(recording_path) => start_server_with_recording(address, recording_path),
+        None => start_server(address),
+    };
+
+    server_handle.join();
 }
@@ -1,10 +1,13 @@
-use crate::connection::Connection;
-use crate::connection::SocketError;
+use crate::connection::{Connection, DeltaQueue, SocketError};
 use crate::connection_acceptor::Client;
+use crate::constant::MAX_QUEUED_DELTAS_PER_CLIENT;
 use crate::controller::{CurrentSnapshotFn, Presenter, Snapshot};
 use crate::fixed_interval_sleeper::{FixedIntervalSleeper, FixedIntervalSleeperError};
+use crate::shutdown::ShutdownSignal;
 use log::{debug, error, warn};
+use myelin_visualization_core::framing::{frame_message, MessageKind};
 use myelin_visualization_core::serialization::ViewModelSerializer;
+use myelin_visualization_core::simulation_stats::SimulationStats;
 use nameof::name_of;
 use std::error::Error;
 use std::fmt::{self, Debug, Display};
@@ -18,6 +21,8 @@ pub(crate) struct ClientHandler {
     serializer: Box<dyn ViewModelSerializer>,
     connection: Connection,
     current_snapshot_fn: Arc<CurrentSnapshotFn>,
+    pending_deltas: DeltaQueue,
+    shutdown_signal: ShutdownSignal,
 }
 
 impl ClientHandler {
@@ -28,6 +33,7 @@ impl ClientHandler {
         serializer: Box<dyn ViewModelSerializer>,
         connection: Connection,
         current_snapshot_fn: Arc<CurrentSnapshotFn>,
+        shutdown_signal: ShutdownSignal,
     ) -> Self {
         Self {
             interval,
@@ -36,33 +42,42 @@ impl ClientHandler {
             serializer,
             connection,
             current_snapshot_fn,
+            pending_deltas: DeltaQueue::with_capacity(MAX_QUEUED_DELTAS_PER_CLIENT),
+            shutdown_signal,
         }
     }
 
-    fn step_and_return_current_snapshot(
-        &mut self,
-        last_snapshot: &Snapshot,
-    ) -> Result<Snapshot, StepError> {
-        let (sleeper_result, snapshot) = sleep_for_fixed_interval!(self.interval, self.sleeper, {
+    /// Calculates the delta between `last_snapshot` and the current snapshot,
+    /// queues it and sends all queued deltas, followed by the current [`SimulationStats`].
+    /// `last_snapshot` is updated to the current snapshot even if sending fails,
+    /// as unsent deltas stay queued and are retried on the next step.
+    /// Sending blocks until the socket accepts the message, so only failed sends stay queued.
+    fn step(&mut self, last_snapshot: &mut Snapshot) -> Result<(), StepError> {
+        let (sleeper_result, _) = sleep_for_fixed_interval!(self.interval, self.sleeper, {
             let current_snapshot = (self.current_snapshot_fn)();
 
             let deltas = self
                 .presenter
-                .calculate_deltas(last_snapshot, &current_snapshot);
-
-            if !deltas.is_empty() {
-                let serialized = self
-                    .serializer
-                    .serialize_view_model_delta(&deltas)
-                    .map_err(StepError::Serialization)?;
-
-                self.connection
-                    .socket
-                    .send_message(&serialized)
-                    .map_err(StepError::Socket)?;
-            }
+                .calculate_deltas(last_snapshot, &current_snapshot.objects);
+
+            let simulation_stats = if deltas.is_empty() {
+                None
+            } else {
+                self.pending_deltas.push(deltas);
+
+                let simulation_stats = self
+                    .presenter
+                    .calculate_simulation_stats(current_snapshot.step, &current_snapshot.objects);
+                Some(simulation_stats)
+            };
+
+            *last_snapshot = current_snapshot.objects;
 
-            current_snapshot
+            self.send_pending_deltas()?;
+
+            if let Some(simulation_stats) = simulation_stats {
+                self.send_simulation_stats(&simulation_stats)?;
+            }
         });
 
         if let Err(error) = sleeper_result {
@@ -73,16 +88,52 @@ impl ClientHandler {
             }
         }
 
-        Ok(snapshot)
+        Ok(())
+    }
+
+    fn send_pending_deltas(&mut self) -> Result<(), StepError> {
+        while let Some(deltas) = self.pending_deltas.front() {
+            let serialized = self
+                .serializer
+                .serialize_view_model_delta(deltas)
+                .map_err(StepError::Serialization)?;
+
+            self.send_message(MessageKind::ViewModelDelta, &serialized)?;
+
+            self.pending_deltas.pop_front();
+        }
+
+        Ok(())
+    }
+
+    fn send_simulation_stats(
+        &mut self,
+        simulation_stats: &SimulationStats,
+    ) -> Result<(), StepError> {
+        let serialized = self
+            .serializer
+            .serialize_simulation_stats(simulation_stats)
+            .map_err(StepError::Serialization)?;
+
+        self.send_message(MessageKind::SimulationStats, &serialized)
+    }
+
+    fn send_message(&mut self, kind: MessageKind, payload: &[u8]) -> Result<(), StepError> {
+        self.connection
+            .socket
+            .send_message(&frame_message(kind, payload))
+            .map_err(StepError::Socket)
     }
 }
 
 impl Client for ClientHandler {
+    /// Sends deltas to the client until it disconnects or a shutdown is requested.
+    /// The connection is closed when the [`ClientHandler`] is dropped.
     fn run(&mut self) {
         let mut last_snapshot = Snapshot::new();
-        loop {
-            match self.step_and_return_current_snapshot(&last_snapshot) {
-                Ok(snapshot) => last_snapshot = snapshot,
+        while !self.shutdown_signal.is_shutdown_requested() {
+            match self.step(&mut last_snapshot) {
+                Ok(()) => {}
                 Err(StepError::Socket(ref err)) if err.is_broken_pipe() => {
                     debug!("Client {} disconnected", self.connection.id);
                     break;
@@ -112,8 +163,8 @@ enum StepError {
 impl Display for StepError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            StepError::Serialization(ref err) => write!(f, "Failed to serialize delta: {}", err),
-            StepError::Socket(ref err) => write!(f, "Failed to send delta: {}", err),
+            StepError::Serialization(ref err) => write!(f, "Failed to serialize message: {}", err),
+            StepError::Socket(ref err) => write!(f, "Failed to send message: {}", err),
         }
     }
 }
@@ -122,7 +173,7 @@ impl Display for StepError {
 mod tests {
     use super::*;
     use crate::connection::{SocketErrorMock, SocketMock};
-    use crate::controller::{PresenterMock, Snapshot};
+    use crate::controller::{NumberedSnapshot, PresenterMock, Snapshot};
     use crate::fixed_interval_sleeper::FixedIntervalSleeperMock;
     use maplit::hashmap;
     use myelin_engine::prelude::*;
@@ -137,6 +188,7 @@ mod tests {
     use uuid::Uuid;
 
     const INTERVAL: u64 = 1000 / 30;
+    const STEP: u64 = 7;
 
     #[test]
     fn can_be_constructed() {
@@ -149,7 +201,7 @@ mod tests {
             id: Uuid::new_v4(),
             socket,
         };
-        let current_snapshot_fn = Arc::new(Snapshot::new);
+        let current_snapshot_fn = Arc::new(NumberedSnapshot::default);
         let _client = ClientHandler::new(
             interval,
             box sleeper,
@@ -157,6 +209,7 @@ mod tests {
             serializer,
             connection,
             current_snapshot_fn,
+            ShutdownSignal::new(),
         );
     }
 
@@ -175,18 +228,38 @@ mod tests {
                 |arg| arg.partial_eq_owned(snapshot()),
             )
             .returns(delta());
+        presenter
+            .expect_calculate_simulation_stats(
+                |arg| arg.partial_eq(STEP),
+                |arg| arg.partial_eq_owned(snapshot()),
+            )
+            .returns(simulation_stats());
         let mut serializer = box SerializerMock::default();
-        let expected_payload = vec![0xFF, 0x01, 0x32];
-        serializer
-            .expect_serialize_view_model_delta_and_return(delta(), Ok(expected_payload.clone()));
+        let expected_delta_payload = vec![0xFF, 0x01, 0x32];
+        serializer.expect_serialize_view_model_delta_and_return(
+            delta(),
+            Ok(expected_delta_payload.clone()),
+        );
+        let expected_stats_payload = vec![0x12, 0x34];
+        serializer.expect_serialize_simulation_stats_and_return(
+            simulation_stats(),
+            Ok(expected_stats_payload.clone()),
+        );
         let mut socket = box SocketMock::default();
-        socket.expect_send_message_and_return(expected_payload, Ok(()));
+        socket.expect_send_message_and_return(
+            frame_message(MessageKind::ViewModelDelta, &expected_delta_payload),
+            Ok(()),
+        );
+        socket.expect_send_message_and_return(
+            frame_message(MessageKind::SimulationStats, &expected_stats_payload),
+            Ok(()),
+        );
         let connection = Connection {
             id: Uuid::new_v4(),
             socket,
         };
 
-        let current_snapshot_fn = Arc::new(snapshot);
+        let current_snapshot_fn = Arc::new(numbered_snapshot);
         let mut client = ClientHandler::new(
             interval,
             box sleeper,
@@ -194,10 +267,11 @@ mod tests {
             serializer,
             connection,
             current_snapshot_fn,
+            ShutdownSignal::new(),
         );
-        let last_snapshot = Snapshot::new();
-        let current_snapshot = client.step_and_return_current_snapshot(&last_snapshot);
-        assert_eq!(snapshot(), current_snapshot.unwrap());
+        let mut last_snapshot = Snapshot::new();
+        client.step(&mut last_snapshot).unwrap();
+        assert_eq!(snapshot(), last_snapshot);
     }
 
     #[test]
@@ -222,7 +296,7 @@ mod tests {
             socket,
         };
 
-        let current_snapshot_fn = Arc::new(snapshot);
+        let current_snapshot_fn = Arc::new(numbered_snapshot);
         let mut client = ClientHandler::new(
             interval,
             box sleeper,
@@ -230,10 +304,11 @@ mod tests {
             serializer,
             connection,
             current_snapshot_fn,
+            ShutdownSignal::new(),
         );
-        let last_snapshot = Snapshot::new();
-        let current_snapshot = client.step_and_return_current_snapshot(&last_snapshot);
-        assert_eq!(snapshot(), current_snapshot.unwrap());
+        let mut last_snapshot = Snapshot::new();
+        client.step(&mut last_snapshot).unwrap();
+        assert_eq!(snapshot(), last_snapshot);
     }
 
     #[should_panic]
@@ -252,6 +327,12 @@ mod tests {
                 |arg| arg.partial_eq_owned(snapshot()),
             )
             .returns(delta());
+        presenter
+            .expect_calculate_simulation_stats(
+                |arg| arg.partial_eq(STEP),
+                |arg| arg.partial_eq_owned(snapshot()),
+            )
+            .returns(simulation_stats());
         let mut serializer = box SerializerMock::default();
         let err = ErrorMock;
         serializer.expect_serialize_view_model_delta_and_return(delta(), Err(err));
@@ -261,7 +342,7 @@ mod tests {
             socket,
         };
 
-        let current_snapshot_fn = Arc::new(snapshot);
+        let current_snapshot_fn = Arc::new(numbered_snapshot);
         let mut client = ClientHandler::new(
             interval,
             box sleeper,
@@ -269,9 +350,10 @@ mod tests {
             serializer,
             connection,
             current_snapshot_fn,
+            ShutdownSignal::new(),
         );
-        let last_snapshot = Snapshot::new();
-        let _current_snapshot = client.step_and_return_current_snapshot(&last_snapshot);
+        let mut last_snapshot = Snapshot::new();
+        client.step(&mut last_snapshot).unwrap();
     }
 
     #[should_panic]
@@ -290,19 +372,28 @@ mod tests {
                 |arg| arg.partial_eq_owned(snapshot()),
             )
             .returns(delta());
+        presenter
+            .expect_calculate_simulation_stats(
+                |arg| arg.partial_eq(STEP),
+                |arg| arg.partial_eq_owned(snapshot()),
+            )
+            .returns(simulation_stats());
         let mut serializer = box SerializerMock::default();
         let expected_payload = vec![0xFF, 0x01, 0x32];
         serializer
             .expect_serialize_view_model_delta_and_return(delta(), Ok(expected_payload.clone()));
         let mut socket = box SocketMock::default();
         let err = SocketErrorMock;
-        socket.expect_send_message_and_return(expected_payload, Err(err));
+        socket.expect_send_message_and_return(
+            frame_message(MessageKind::ViewModelDelta, &expected_payload),
+            Err(err),
+        );
         let connection = Connection {
             id: Uuid::new_v4(),
             socket,
         };
 
-        let current_snapshot_fn = Arc::new(snapshot);
+        let current_snapshot_fn = Arc::new(numbered_snapshot);
         let mut client = ClientHandler::new(
             interval,
             box sleeper,
@@ -310,10 +401,38 @@ mod tests {
             serializer,
             connection,
             current_snapshot_fn,
+            ShutdownSignal::new(),
         );
-        let last_snapshot = Snapshot::new();
-        let current_snapshot = client.step_and_return_current_snapshot(&last_snapshot);
-        assert_eq!(snapshot(), current_snapshot.unwrap());
+        let mut last_snapshot = Snapshot::new();
+        client.step(&mut last_snapshot).unwrap();
+        assert_eq!(snapshot(), last_snapshot);
+    }
+
+    #[test]
+    fn run_returns_when_shutdown_is_requested() {
+        let interval = Duration::from_millis(INTERVAL);
+        let sleeper = FixedIntervalSleeperMock::new();
+        let presenter = box PresenterMock::new();
+        let serializer = box SerializerMock::default();
+        let socket = box SocketMock::default();
+        let connection = Connection {
+            id: Uuid::new_v4(),
+            socket,
+        };
+        let shutdown_signal = ShutdownSignal::new();
+        shutdown_signal.request_shutdown();
+
+        let current_snapshot_fn = Arc::new(numbered_snapshot);
+        let mut client = ClientHandler::new(
+            interval,
+            box sleeper,
+            presenter,
+            serializer,
+            connection,
+            current_snapshot_fn,
+            shutdown_signal,
+        );
+        client.run();
     }
 
     fn snapshot() -> Snapshot {
@@ -344,6 +463,20 @@ mod tests {
         expected_current_snapshot
     }
 
+    fn numbered_snapshot() -> NumberedSnapshot {
+        NumberedSnapshot {
+            step: STEP,
+            objects: snapshot(),
+        }
+    }
+
+    fn simulation_stats() -> SimulationStats {
+        SimulationStats {
+            step: STEP,
+            counts_by_kind: hashmap! { Kind::Plant => 1 },
+        }
+    }
+
     fn delta() -> ViewModelDelta {
         let updated_object = ObjectDescriptionDelta {
             location: Some(Point { x: 12.0, y: 32.0 }),
@@ -359,8 +492,11 @@ mod tests {
     struct SerializerMock {
         expect_serialize_view_model_delta_and_return:
             Option<(ViewModelDelta, Result<Vec<u8>, ErrorMock>)>,
+        expect_serialize_simulation_stats_and_return:
+            Option<(SimulationStats, Result<Vec<u8>, ErrorMock>)>,
 
         serialize_view_model_delta_was_called: RefCell<bool>,
+        serialize_simulation_stats_was_called: RefCell<bool>,
     }
 
     impl SerializerMock {
@@ -372,6 +508,15 @@ mod tests {
             self.expect_serialize_view_model_delta_and_return =
                 Some((view_model_delta, return_value));
         }
+
+        fn expect_serialize_simulation_stats_and_return(
+            &mut self,
+            simulation_stats: SimulationStats,
+            return_value: Result<Vec<u8>, ErrorMock>,
+        ) {
+            self.expect_serialize_simulation_stats_and_return =
+                Some((simulation_stats, return_value));
+        }
     }
 
     impl ViewModelSerializer for SerializerMock {
@@ -396,6 +541,28 @@ mod tests {
                 panic!("serialize_view_model_delta() was called unexpectedly")
             }
         }
+
+        fn serialize_simulation_stats(
+            &self,
+            simulation_stats: &SimulationStats,
+        ) -> Result<Vec<u8>, Box<dyn Error>> {
+            *self.serialize_simulation_stats_was_called.borrow_mut() = true;
+
+            if let Some((ref expected_simulation_stats, ref return_value)) =
+                self.expect_serialize_simulation_stats_and_return
+            {
+                assert_eq!(
+                    *expected_simulation_stats, *simulation_stats,
+                    "serialize_simulation_stats() was called with {:?}, expected {:?}",
+                    simulation_stats, expected_simulation_stats,
+                );
+                return_value
+                    .clone()
+                    .map_err(|mock| box mock as Box<dyn Error>)
+            } else {
+                panic!("serialize_simulation_stats() was called unexpectedly")
+            }
+        }
     }
 
     impl Drop for SerializerMock {
@@ -406,6 +573,13 @@ mod tests {
                     "serialize_view_model_delta() was not called, but expected"
                 )
             }
+
+            if !panicking() && self.expect_serialize_simulation_stats_and_return.is_some() {
+                assert!(
+                    *self.serialize_simulation_stats_was_called.borrow(),
+                    "serialize_simulation_stats() was not called, but expected"
+                )
+            }
         }
     }
 
@@ -1,8 +1,12 @@
+pub(crate) use self::delta_queue::*;
+pub(crate) use self::recorder::*;
 pub(crate) use self::websocket::*;
 use std::error::Error;
 use std::fmt::Debug;
 use uuid::Uuid;
 
+mod delta_queue;
+mod recorder;
 mod websocket;
 
 #[cfg(test)]
@@ -31,63 +35,62 @@ impl PartialEq for Connection {
 #[cfg(test)]
 mod mock {
     use super::*;
+    use std::collections::VecDeque;
     use std::fmt::{self, Display};
-    use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::Mutex;
     use std::thread::panicking;
 
     #[derive(Debug, Default)]
     pub(crate) struct SocketMock {
         #[allow(clippy::type_complexity)]
-        expect_send_message_and_return: Mutex<Option<(Vec<u8>, Result<(), SocketErrorMock>)>>,
-        send_message_was_called: AtomicBool,
+        expected_send_message_calls: Mutex<VecDeque<(Vec<u8>, Result<(), SocketErrorMock>)>>,
     }
 
     impl SocketMock {
+        /// Expects another call to `send_message()`.
+        /// Expected calls have to happen in the order they were registered in.
         pub(crate) fn expect_send_message_and_return(
             &mut self,
             payload: Vec<u8>,
             return_value: Result<(), SocketErrorMock>,
         ) {
-            self.expect_send_message_and_return = Mutex::new(Some((payload, return_value)));
+            self.expected_send_message_calls
+                .get_mut()
+                .unwrap()
+                .push_back((payload, return_value));
         }
     }
 
     impl Socket for SocketMock {
         fn send_message(&mut self, payload: &[u8]) -> Result<(), Box<dyn SocketError>> {
-            self.send_message_was_called.store(true, Ordering::SeqCst);
-
-            if let Some((ref expected_payload, ref return_value)) =
-                *self.expect_send_message_and_return.lock().unwrap()
-            {
-                assert_eq!(
-                    *expected_payload,
-                    payload.to_vec(),
-                    "send_message() was called with {:?}, expected {:?}",
-                    payload,
-                    expected_payload,
-                );
-                return_value
-                    .clone()
-                    .map_err(|mock| box mock as Box<dyn SocketError>)
-            } else {
-                panic!("send_message() was called unexpectedly")
-            }
+            let (expected_payload, return_value) = self
+                .expected_send_message_calls
+                .get_mut()
+                .unwrap()
+                .pop_front()
+                .expect("send_message() was called unexpectedly");
+
+            assert_eq!(
+                expected_payload,
+                payload.to_vec(),
+                "send_message() was called with {:?}, expected {:?}",
+                payload,
+                expected_payload,
+            );
+
+            return_value.map_err(|mock| box mock as Box<dyn SocketError>)
         }
     }
 
     impl Drop for SocketMock {
         fn drop(&mut self) {
-            if !panicking()
-                && self
-                    .expect_send_message_and_return
-                    .lock()
-                    .unwrap()
-                    .is_some()
-            {
+            if !panicking() {
                 assert!(
-                    self.send_message_was_called.load(Ordering::SeqCst),
-                    "send_message() was not called, but expected"
+                    self.expected_send_message_calls
+                        .get_mut()
+                        .unwrap()
+                        .is_empty(),
+                    "send_message() was called less often than expected"
                 )
             }
         }
@@ -0,0 +1,116 @@
+use myelin_visualization_core::view_model_delta::{Compose, ViewModelDelta};
+use std::collections::VecDeque;
+
+/// A bounded queue of deltas that have not yet been sent to a client.
+/// When the queue is full, the two oldest deltas are composed into one,
+/// so that no information is lost while memory stays bounded.
+///
+/// Deltas are sent over a blocking socket, so a slow client stalls its handler
+/// instead of filling the queue. The queue therefore only holds deltas
+/// whose sending failed, until they are retried on the next step.
+#[derive(Debug)]
+pub(crate) struct DeltaQueue {
+    deltas: VecDeque<ViewModelDelta>,
+    capacity: usize,
+}
+
+impl DeltaQueue {
+    /// Creates a new [`DeltaQueue`] that holds at most `capacity` deltas.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "A delta queue needs to hold at least one delta"
+        );
+
+        Self {
+            deltas: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub(crate) fn push(&mut self, delta: ViewModelDelta) {
+        self.deltas.push_back(delta);
+
+        while self.deltas.len() > self.capacity {
+            let oldest_delta = self.deltas.pop_front().unwrap();
+            let second_oldest_delta = self.deltas.pop_front().unwrap();
+            self.deltas
+                .push_front(oldest_delta.compose(second_oldest_delta));
+        }
+    }
+
+    pub(crate) fn front(&self) -> Option<&ViewModelDelta> {
+        self.deltas.front()
+    }
+
+    pub(crate) fn pop_front(&mut self) -> Option<ViewModelDelta> {
+        self.deltas.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use maplit::hashmap;
+    use myelin_engine::prelude::*;
+    use myelin_visualization_core::view_model_delta::{ObjectDelta, ObjectDescriptionDelta};
+
+    fn location_delta(id: Id, x: f64) -> ViewModelDelta {
+        hashmap! {
+            id => ObjectDelta::Updated(ObjectDescriptionDelta {
+                location: Some(Point { x, y: 0.0 }),
+                ..ObjectDescriptionDelta::default()
+            })
+        }
+    }
+
+    #[test]
+    fn queue_never_exceeds_capacity() {
+        const CAPACITY: usize = 3;
+        let mut queue = DeltaQueue::with_capacity(CAPACITY);
+
+        for index in 0..20 {
+            queue.push(location_delta(index % 4, index as f64));
+            assert!(queue.deltas.len() <= CAPACITY);
+        }
+    }
+
+    #[test]
+    fn deltas_are_returned_in_order() {
+        let mut queue = DeltaQueue::with_capacity(3);
+        queue.push(location_delta(1, 1.0));
+        queue.push(location_delta(2, 2.0));
+
+        assert_eq!(Some(location_delta(1, 1.0)), queue.pop_front());
+        assert_eq!(Some(location_delta(2, 2.0)), queue.pop_front());
+        assert_eq!(None, queue.pop_front());
+    }
+
+    #[test]
+    fn coalesced_deltas_leave_correct_final_state() {
+        let mut queue = DeltaQueue::with_capacity(2);
+        queue.push(location_delta(1, 1.0));
+        queue.push(location_delta(2, 2.0));
+        queue.push(location_delta(1, 3.0));
+        queue.push(hashmap! { 3 => ObjectDelta::Deleted });
+
+        let mut final_delta = ViewModelDelta::new();
+        while let Some(delta) = queue.pop_front() {
+            final_delta = final_delta.compose(delta);
+        }
+
+        let expected = location_delta(1, 3.0)
+            .compose(location_delta(2, 2.0))
+            .compose(hashmap! { 3 => ObjectDelta::Deleted });
+        assert_eq!(expected, final_delta);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_with_zero_capacity() {
+        DeltaQueue::with_capacity(0);
+    }
+}
@@ -0,0 +1,112 @@
+use super::{Socket, SocketError};
+use myelin_visualization_core::recording::{write_recorded_delta, RecordedDelta};
+use nameof::name_of;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::io::{self, Write};
+use std::time::Instant;
+
+/// A [`Socket`] that appends every message it receives to a recording,
+/// timestamped with the time elapsed since the recorder was created.
+pub(crate) struct DeltaRecorder {
+    writer: Box<dyn Write + Send + Sync>,
+    started_at: Instant,
+}
+
+impl DeltaRecorder {
+    pub(crate) fn new(writer: Box<dyn Write + Send + Sync>) -> Self {
+        Self {
+            writer,
+            started_at: Instant::now(),
+        }
+    }
+}
+
+impl Debug for DeltaRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(name_of!(type DeltaRecorder))
+            .field(name_of!(started_at in DeltaRecorder), &self.started_at)
+            .finish()
+    }
+}
+
+impl Socket for DeltaRecorder {
+    fn send_message(&mut self, payload: &[u8]) -> Result<(), Box<dyn SocketError>> {
+        let recorded_delta = RecordedDelta {
+            timestamp: self.started_at.elapsed(),
+            serialized_delta: payload.to_vec(),
+        };
+
+        write_recorded_delta(&mut self.writer, &recorded_delta)
+            .and_then(|_| self.writer.flush())
+            .map_err(|err| box DeltaRecorderError(err) as Box<dyn SocketError>)
+    }
+}
+
+#[derive(Debug)]
+struct DeltaRecorderError(io::Error);
+
+impl Error for DeltaRecorderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl SocketError for DeltaRecorderError {
+    /// A recording that can't be written to is treated like a disconnected client,
+    /// so that recording stops instead of failing on every step.
+    fn is_broken_pipe(&self) -> bool {
+        true
+    }
+}
+
+impl Display for DeltaRecorderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to write recording: {}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use myelin_visualization_core::recording::read_recorded_deltas;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn records_deltas_in_order() {
+        let payloads = vec![vec![1, 2, 3], vec![4], vec![], vec![5, 6]];
+        let buffer = SharedBuffer::default();
+        let mut recorder = DeltaRecorder::new(box buffer.clone());
+
+        for payload in &payloads {
+            recorder.send_message(payload).unwrap();
+        }
+
+        let recording = buffer.0.lock().unwrap().clone();
+        let recorded_deltas = read_recorded_deltas(&recording).unwrap();
+
+        let recorded_payloads: Vec<_> = recorded_deltas
+            .iter()
+            .map(|recorded_delta| recorded_delta.serialized_delta.clone())
+            .collect();
+        assert_eq!(payloads, recorded_payloads);
+
+        let timestamps_are_ordered = recorded_deltas
+            .windows(2)
+            .all(|pair| pair[0].timestamp <= pair[1].timestamp);
+        assert!(timestamps_are_ordered);
+    }
+}
@@ -1,12 +1,19 @@
+use crate::constant::{CLIENT_HANDSHAKE_TIMEOUT, CONNECTION_ACCEPTOR_POLL_INTERVAL};
 use crate::controller::{ConnectionAcceptor, CurrentSnapshotFn};
+use crate::shutdown::ShutdownSignal;
+use log::warn;
 use nameof::name_of;
 use std::fmt::{self, Debug};
-use std::io;
-use std::net::{SocketAddr, TcpStream};
+use std::io::{self, ErrorKind as IoErrorKind};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use websocket::server::upgrade::{sync::Buffer, WsUpgrade as Request};
-use websocket::server::NoTlsAcceptor;
-use websocket::sync::{Client as WsClient, Server};
+use std::thread;
+use websocket::message::Message;
+use websocket::server::upgrade::sync::{Buffer, IntoWs};
+use websocket::server::upgrade::WsUpgrade as Request;
+use websocket::sync::Client as WsClient;
+use websocket::WebSocketError;
 
 pub(crate) trait Client: Debug {
     fn run(&mut self);
@@ -15,11 +22,16 @@ pub(crate) type ClientFactoryFn =
     dyn Fn(WsClient<TcpStream>, Arc<CurrentSnapshotFn>) -> Box<dyn Client> + Send + Sync;
 pub(crate) type ThreadSpawnFn = dyn Fn(Box<dyn FnOnce() + Send>) + Send + Sync;
 
+/// The close code sent to clients that are rejected because the server is full
+const TRY_AGAIN_LATER_CLOSE_CODE: u16 = 1013;
+
 pub(crate) struct WebsocketConnectionAcceptor {
-    websocket_server: Server<NoTlsAcceptor>,
+    listener: TcpListener,
     client_factory_fn: Arc<ClientFactoryFn>,
     thread_spawn_fn: Box<ThreadSpawnFn>,
     current_snapshot_fn: Arc<CurrentSnapshotFn>,
+    shutdown_signal: ShutdownSignal,
+    client_slots: ClientSlots,
 }
 
 impl WebsocketConnectionAcceptor {
@@ -28,27 +40,74 @@ impl WebsocketConnectionAcceptor {
         client_factory_fn: Arc<ClientFactoryFn>,
         thread_spawn_fn: Box<ThreadSpawnFn>,
         current_snapshot_fn: Arc<CurrentSnapshotFn>,
+        shutdown_signal: ShutdownSignal,
+        max_clients: usize,
     ) -> Result<Self, io::Error> {
+        let listener = TcpListener::bind(address)?;
+        // The listener is polled, so that a requested shutdown
+        // is noticed even when no clients are connecting.
+        listener.set_nonblocking(true)?;
+
         Ok(Self {
-            websocket_server: Server::bind(address)?,
+            listener,
             client_factory_fn,
             thread_spawn_fn,
             current_snapshot_fn,
+            shutdown_signal,
+            client_slots: ClientSlots::new(max_clients),
         })
     }
 }
 
 impl ConnectionAcceptor for WebsocketConnectionAcceptor {
-    fn run(self: Box<Self>) {
-        for request in self.websocket_server.filter_map(Result::ok) {
+    /// Accepts connections until a shutdown is requested.
+    /// The websocket upgrade of every connection happens on its own thread,
+    /// so that clients that never send anything do not block the acceptor.
+    /// Connections exceeding the maximum number of clients are closed right away.
+    /// The listening socket is closed when this method returns.
+    fn run(mut self: Box<Self>) {
+        while !self.shutdown_signal.is_shutdown_requested() {
+            let stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(ref err) if err.kind() == IoErrorKind::WouldBlock => {
+                    thread::sleep(CONNECTION_ACCEPTOR_POLL_INTERVAL);
+                    continue;
+                }
+                Err(_) => continue,
+            };
+
+            if let Err(err) = prepare_stream(&stream) {
+                warn!("Unable to configure client stream: {}", err);
+                continue;
+            }
+
             let client_factory_fn = self.client_factory_fn.clone();
             let current_snapshot_fn = self.current_snapshot_fn.clone();
+            let client_slot = self.client_slots.try_occupy();
             (self.thread_spawn_fn)(box move || {
+                let request = match stream.into_ws() {
+                    Ok(request) => request,
+                    Err((_, _, _, err)) => {
+                        warn!("Client did not send a valid websocket upgrade: {}", err);
+                        return;
+                    }
+                };
+
                 if should_accept(&request) {
                     if let Ok(mut client_stream) = request.accept() {
-                        client_stream.recv_message().unwrap();
-                        let mut client = (client_factory_fn)(client_stream, current_snapshot_fn);
-                        client.run();
+                        match client_slot {
+                            Some(_client_slot) => {
+                                if let Err(err) = receive_handshake(&mut client_stream) {
+                                    warn!("Client did not complete its handshake: {}", err);
+                                    return;
+                                }
+
+                                let mut client =
+                                    (client_factory_fn)(client_stream, current_snapshot_fn);
+                                client.run();
+                            }
+                            None => reject_client(client_stream),
+                        }
                     }
                 }
             })
@@ -56,7 +115,7 @@ impl ConnectionAcceptor for WebsocketConnectionAcceptor {
     }
 
     fn address(&self) -> SocketAddr {
-        self.websocket_server
+        self.listener
             .local_addr()
             .expect("Unable to get local_addr() from socket")
     }
@@ -73,16 +132,104 @@ fn should_accept(_request: &Request<TcpStream, Option<Buffer>>) -> bool {
     true
 }
 
+/// Accepted streams may inherit the non-blocking mode of the listening socket,
+/// so the stream is made blocking explicitly, but with a timeout,
+/// in order to not wait forever on a client that never sends anything.
+/// The timeout covers both the websocket upgrade and the handshake.
+fn prepare_stream(stream: &TcpStream) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CLIENT_HANDSHAKE_TIMEOUT))
+}
+
+/// Waits for the first message of a client, which it sends once it is ready to receive deltas.
+fn receive_handshake(client_stream: &mut WsClient<TcpStream>) -> Result<(), WebSocketError> {
+    client_stream.recv_message()?;
+    client_stream.stream_ref().set_read_timeout(None)?;
+    Ok(())
+}
+
+fn reject_client(mut client_stream: WsClient<TcpStream>) {
+    let message = Message::close_because(
+        TRY_AGAIN_LATER_CLOSE_CODE,
+        "The maximum number of clients has been reached",
+    );
+
+    if let Err(err) = client_stream.send_message(&message) {
+        warn!("Failed to reject client: {}", err);
+    }
+}
+
+/// Limits the number of clients that are served at the same time.
+#[derive(Debug)]
+struct ClientSlots {
+    occupied_slots: Arc<AtomicUsize>,
+    max_clients: usize,
+}
+
+impl ClientSlots {
+    fn new(max_clients: usize) -> Self {
+        Self {
+            occupied_slots: Arc::default(),
+            max_clients,
+        }
+    }
+
+    /// Occupies a slot if one is free.
+    /// The slot is freed when the returned [`ClientSlot`] is dropped.
+    fn try_occupy(&self) -> Option<ClientSlot> {
+        let mut occupied_slots = self.occupied_slots.load(Ordering::SeqCst);
+
+        loop {
+            if occupied_slots >= self.max_clients {
+                return None;
+            }
+
+            match self.occupied_slots.compare_exchange(
+                occupied_slots,
+                occupied_slots + 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return Some(ClientSlot(self.occupied_slots.clone())),
+                Err(actual_occupied_slots) => occupied_slots = actual_occupied_slots,
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ClientSlot(Arc<AtomicUsize>);
+
+impl Drop for ClientSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::client::ClientHandler;
+    use crate::connection::{Connection, SocketErrorMock, SocketMock};
+    use crate::controller::{NumberedSnapshot, PresenterMock};
+    use crate::fixed_interval_sleeper::FixedIntervalSleeperMock;
+    use maplit::hashmap;
+    use myelin_visualization_core::framing::{frame_message, MessageKind};
+    use myelin_visualization_core::serialization::{BincodeSerializer, ViewModelSerializer};
+    use myelin_visualization_core::simulation_stats::SimulationStats;
+    use myelin_visualization_core::view_model_delta::{ObjectDelta, ViewModelDelta};
     use std::net::{Ipv4Addr, SocketAddrV4};
-    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::atomic::AtomicBool;
+    use std::sync::mpsc::{channel, Receiver};
+    use std::sync::Mutex;
     use std::thread::{self, panicking};
-    use websocket::message::Message;
+    use std::time::Duration;
+    use uuid::Uuid;
+    use websocket::message::OwnedMessage;
     use websocket::ClientBuilder;
 
     const RANDOM_PORT: u16 = 0;
+    const MAX_CLIENTS: usize = 2;
 
     #[test]
     fn address_returns_correct_socket_address() {
@@ -95,6 +242,8 @@ mod tests {
             client_factory_fn,
             main_thread_spawn_fn,
             Arc::new(|| panic!("current_snapshot_fn was not expected to be called")),
+            ShutdownSignal::new(),
+            MAX_CLIENTS,
         )
         .unwrap();
 
@@ -117,6 +266,8 @@ mod tests {
             client_factory_fn,
             main_thread_spawn_fn,
             Arc::new(|| panic!("current_snapshot_fn was not expected to be called")),
+            ShutdownSignal::new(),
+            MAX_CLIENTS,
         )
         .unwrap();
 
@@ -133,6 +284,230 @@ mod tests {
         client.send_message(&Message::binary(&[] as &[u8])).unwrap();
     }
 
+    #[test]
+    fn releases_address_on_shutdown() {
+        let address = localhost();
+        let client_factory_fn = mock_client_factory_fn(None);
+        let main_thread_spawn_fn = main_thread_spawn_fn();
+        let shutdown_signal = ShutdownSignal::new();
+
+        let connection_acceptor = box WebsocketConnectionAcceptor::try_new(
+            address,
+            client_factory_fn,
+            main_thread_spawn_fn,
+            Arc::new(|| panic!("current_snapshot_fn was not expected to be called")),
+            shutdown_signal.clone(),
+            MAX_CLIENTS,
+        )
+        .unwrap();
+
+        let address = connection_acceptor.address();
+        let acceptor_thread = thread::spawn(move || {
+            connection_acceptor.run();
+        });
+
+        shutdown_signal.request_shutdown();
+        acceptor_thread.join().unwrap();
+
+        assert!(TcpListener::bind(address).is_ok());
+    }
+
+    #[test]
+    fn rejects_clients_beyond_max_clients() {
+        let (release_client, client_released) = channel();
+        let client_released = Mutex::new(Some(client_released));
+        let client_factory_fn: Arc<ClientFactoryFn> = Arc::new(move |_, _| {
+            let client_released = client_released
+                .lock()
+                .unwrap()
+                .take()
+                .expect("Only one call to client_factory_fn was expected");
+            box BlockingClientMock(Mutex::new(client_released)) as Box<dyn Client>
+        });
+        let shutdown_signal = ShutdownSignal::new();
+
+        let connection_acceptor = box WebsocketConnectionAcceptor::try_new(
+            localhost(),
+            client_factory_fn,
+            thread_spawn_fn(),
+            Arc::new(|| panic!("current_snapshot_fn was not expected to be called")),
+            shutdown_signal.clone(),
+            1,
+        )
+        .unwrap();
+
+        let address = connection_acceptor.address();
+        let acceptor_thread = thread::spawn(move || {
+            connection_acceptor.run();
+        });
+
+        let mut accepted_client = connect(address);
+        accepted_client
+            .send_message(&Message::binary(&[] as &[u8]))
+            .unwrap();
+
+        let mut rejected_client = connect(address);
+        match rejected_client.recv_message().unwrap() {
+            OwnedMessage::Close(Some(close_data)) => {
+                assert_eq!(TRY_AGAIN_LATER_CLOSE_CODE, close_data.status_code)
+            }
+            message => panic!("Expected a close message, got {:?}", message),
+        }
+
+        release_client.send(()).unwrap();
+        shutdown_signal.request_shutdown();
+        acceptor_thread.join().unwrap();
+    }
+
+    #[test]
+    fn client_slot_is_freed_when_client_disconnects() {
+        let client_factory_calls = Arc::new(AtomicUsize::new(0));
+        let client_factory_fn: Arc<ClientFactoryFn> = {
+            let client_factory_calls = client_factory_calls.clone();
+            Arc::new(move |_, current_snapshot_fn| {
+                client_factory_calls.fetch_add(1, Ordering::SeqCst);
+                box disconnecting_client_handler(current_snapshot_fn) as Box<dyn Client>
+            })
+        };
+        let shutdown_signal = ShutdownSignal::new();
+
+        let connection_acceptor = box WebsocketConnectionAcceptor::try_new(
+            localhost(),
+            client_factory_fn,
+            main_thread_spawn_fn(),
+            Arc::new(NumberedSnapshot::default),
+            shutdown_signal.clone(),
+            1,
+        )
+        .unwrap();
+
+        let address = connection_acceptor.address();
+        let acceptor_thread = thread::spawn(move || {
+            connection_acceptor.run();
+        });
+
+        for _ in 0..2 {
+            let mut client = connect(address);
+            client.send_message(&Message::binary(&[] as &[u8])).unwrap();
+        }
+
+        shutdown_signal.request_shutdown();
+        acceptor_thread.join().unwrap();
+
+        assert_eq!(2, client_factory_calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn silent_client_does_not_block_other_clients_or_shutdown() {
+        let (client_created, client_was_created) = channel();
+        let client_created = Mutex::new(client_created);
+        let client_factory_fn: Arc<ClientFactoryFn> = Arc::new(move |_, _| {
+            client_created.lock().unwrap().send(()).unwrap();
+            let mut client = ClientMock::default();
+            client.expect_run();
+            box client as Box<dyn Client>
+        });
+        let shutdown_signal = ShutdownSignal::new();
+
+        let connection_acceptor = box WebsocketConnectionAcceptor::try_new(
+            localhost(),
+            client_factory_fn,
+            thread_spawn_fn(),
+            Arc::new(|| panic!("current_snapshot_fn was not expected to be called")),
+            shutdown_signal.clone(),
+            MAX_CLIENTS,
+        )
+        .unwrap();
+
+        let address = connection_acceptor.address();
+        let (acceptor_stopped, acceptor_was_stopped) = channel();
+        thread::spawn(move || {
+            connection_acceptor.run();
+            acceptor_stopped.send(()).unwrap();
+        });
+
+        let _silent_client = TcpStream::connect(address).unwrap();
+
+        let mut client = connect(address);
+        client.send_message(&Message::binary(&[] as &[u8])).unwrap();
+        assert!(client_was_created
+            .recv_timeout(CLIENT_HANDSHAKE_TIMEOUT / 2)
+            .is_ok());
+
+        shutdown_signal.request_shutdown();
+        assert!(acceptor_was_stopped
+            .recv_timeout(CLIENT_HANDSHAKE_TIMEOUT / 2)
+            .is_ok());
+    }
+
+    #[test]
+    fn client_slots_are_limited() {
+        let client_slots = ClientSlots::new(MAX_CLIENTS);
+
+        let _occupied_slots: Vec<_> = (0..MAX_CLIENTS)
+            .map(|_| client_slots.try_occupy().expect("Slot should be free"))
+            .collect();
+
+        assert!(client_slots.try_occupy().is_none());
+    }
+
+    #[test]
+    fn freed_client_slot_can_be_occupied_again() {
+        let client_slots = ClientSlots::new(1);
+
+        let client_slot = client_slots.try_occupy();
+        assert!(client_slot.is_some());
+        assert!(client_slots.try_occupy().is_none());
+
+        drop(client_slot);
+
+        assert!(client_slots.try_occupy().is_some());
+    }
+
+    /// A [`ClientHandler`] whose client disconnects when the first delta is sent
+    fn disconnecting_client_handler(current_snapshot_fn: Arc<CurrentSnapshotFn>) -> ClientHandler {
+        let delta: ViewModelDelta = hashmap! { 12 => ObjectDelta::Deleted };
+
+        let mut sleeper = FixedIntervalSleeperMock::new();
+        sleeper.expect_register_work_started();
+        let mut presenter = PresenterMock::new();
+        presenter
+            .expect_calculate_deltas(|arg| arg.any(), |arg| arg.any())
+            .returns(delta.clone());
+        presenter
+            .expect_calculate_simulation_stats(|arg| arg.any(), |arg| arg.any())
+            .returns(SimulationStats::default());
+        let serializer = BincodeSerializer::default();
+        let mut socket = SocketMock::default();
+        socket.expect_send_message_and_return(
+            frame_message(
+                MessageKind::ViewModelDelta,
+                &serializer.serialize_view_model_delta(&delta).unwrap(),
+            ),
+            Err(SocketErrorMock),
+        );
+
+        ClientHandler::new(
+            Duration::from_millis(1000 / 30),
+            box sleeper,
+            box presenter,
+            box serializer,
+            Connection {
+                id: Uuid::new_v4(),
+                socket: box socket,
+            },
+            current_snapshot_fn,
+            ShutdownSignal::new(),
+        )
+    }
+
+    fn connect(address: SocketAddr) -> WsClient<TcpStream> {
+        ClientBuilder::new(&format!("ws://{}", address))
+            .unwrap()
+            .connect_insecure()
+            .unwrap()
+    }
+
     fn localhost() -> SocketAddr {
         let address = SocketAddrV4::new(Ipv4Addr::LOCALHOST, RANDOM_PORT);
         SocketAddr::V4(address)
@@ -152,6 +527,12 @@ mod tests {
         box move |function| function()
     }
 
+    fn thread_spawn_fn() -> Box<ThreadSpawnFn> {
+        box move |function| {
+            thread::spawn(function);
+        }
+    }
+
     #[derive(Debug, Default)]
     struct ClientMock {
         expect_run: AtomicBool,
@@ -183,6 +564,16 @@ mod tests {
         }
     }
 
+    /// A client that keeps its slot occupied until it is released
+    #[derive(Debug)]
+    struct BlockingClientMock(Mutex<Receiver<()>>);
+
+    impl Client for BlockingClientMock {
+        fn run(&mut self) {
+            let _ = self.0.lock().unwrap().recv();
+        }
+    }
+
     impl Drop for ClientMock {
         fn drop(&mut self) {
             if !panicking() && self.expect_run.load(Ordering::SeqCst) {
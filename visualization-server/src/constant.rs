@@ -1 +1,19 @@
+use std::time::Duration;
+
 pub(crate) const SIMULATED_TIMESTEP_IN_SI_UNITS: f64 = 1.0 / 60.0;
+
+/// The maximum number of deltas whose sending failed that are kept per client
+/// before they are composed into one.
+pub(crate) const MAX_QUEUED_DELTAS_PER_CLIENT: usize = 8;
+
+/// How long the connection acceptor waits before checking
+/// for new connections or a requested shutdown again.
+pub(crate) const CONNECTION_ACCEPTOR_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The maximum number of clients that are served at the same time.
+/// Further clients are rejected until a slot is freed.
+pub(crate) const MAX_CLIENTS: usize = 16;
+
+/// How long a newly connected client may take to send its first message
+/// before it is disconnected.
+pub(crate) const CLIENT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
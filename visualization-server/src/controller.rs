@@ -1,5 +1,7 @@
+use crate::shutdown::ShutdownSignal;
 use myelin_engine::prelude::*;
 use myelin_object_data::{AdditionalObjectDescription, ObjectDescription};
+use myelin_visualization_core::simulation_stats::SimulationStats;
 use myelin_visualization_core::view_model_delta::ViewModelDelta;
 use nameof::name_of;
 use std::collections::HashMap;
@@ -14,9 +16,17 @@ use mockiato::mockable;
 /// The snapshot provided by myelin-engine contains `ObjectDescription`,
 /// which we are not interested in.
 pub(crate) type Snapshot = HashMap<Id, ObjectDescription>;
+
+/// A [`Snapshot`] together with the number of steps that had been simulated when it was taken
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct NumberedSnapshot {
+    pub(crate) step: u64,
+    pub(crate) objects: Snapshot,
+}
+
 pub(crate) type ConnectionAcceptorFactoryFn =
     dyn Fn(Arc<CurrentSnapshotFn>) -> Box<dyn ConnectionAcceptor> + Send + Sync;
-pub(crate) type CurrentSnapshotFn = dyn Fn() -> Snapshot + Send + Sync;
+pub(crate) type CurrentSnapshotFn = dyn Fn() -> NumberedSnapshot + Send + Sync;
 pub(crate) type ThreadSpawnFn<'a> = dyn Fn(Box<dyn FnOnce() + Send>) + Send + Sync + 'a;
 
 pub(crate) trait Controller: Debug {
@@ -30,6 +40,8 @@ pub(crate) trait Presenter: Debug {
         visualized_snapshot: &Snapshot,
         simulation_snapshot: &Snapshot,
     ) -> ViewModelDelta;
+
+    fn calculate_simulation_stats(&self, step: u64, snapshot: &Snapshot) -> SimulationStats;
 }
 
 #[cfg_attr(test, mockable)]
@@ -42,9 +54,10 @@ pub(crate) trait ConnectionAcceptor: Debug {
 pub(crate) struct ControllerImpl<'a> {
     simulation: Box<dyn Simulation<AdditionalObjectDescription> + 'a>,
     connection_acceptor_factory_fn: Arc<ConnectionAcceptorFactoryFn>,
-    current_snapshot: Arc<RwLock<Snapshot>>,
+    current_snapshot: Arc<RwLock<NumberedSnapshot>>,
     thread_spawn_fn: Box<ThreadSpawnFn<'a>>,
     expected_delta: Duration,
+    shutdown_signal: ShutdownSignal,
 }
 
 impl<'a> Debug for ControllerImpl<'a> {
@@ -58,7 +71,7 @@ impl<'a> Debug for ControllerImpl<'a> {
 impl<'a> Controller for ControllerImpl<'a> {
     fn run(&mut self) {
         self.run_connection_acceptor();
-        loop {
+        while !self.shutdown_signal.is_shutdown_requested() {
             self.step_simulation();
         }
     }
@@ -70,6 +83,7 @@ impl<'a> ControllerImpl<'a> {
         connection_acceptor_factory_fn: Arc<ConnectionAcceptorFactoryFn>,
         thread_spawn_fn: Box<ThreadSpawnFn<'a>>,
         expected_delta: Duration,
+        shutdown_signal: ShutdownSignal,
     ) -> Self {
         Self {
             simulation,
@@ -77,6 +91,7 @@ impl<'a> ControllerImpl<'a> {
             expected_delta,
             thread_spawn_fn,
             current_snapshot: Arc::default(),
+            shutdown_signal,
         }
     }
 
@@ -93,13 +108,16 @@ impl<'a> ControllerImpl<'a> {
 
     fn step_simulation(&mut self) {
         self.simulation.step();
-        let current_snapshot: Snapshot = self
+        let objects: Snapshot = self
             .simulation
             .objects()
             .into_iter()
             .map(|object| (object.id, object.description))
             .collect();
-        *self.current_snapshot.write().unwrap() = current_snapshot;
+
+        let mut current_snapshot = self.current_snapshot.write().unwrap();
+        current_snapshot.step += 1;
+        current_snapshot.objects = objects;
     }
 }
 
@@ -108,7 +126,6 @@ mod tests {
     use super::*;
     use maplit::hashmap;
     use myelin_object_data::Kind;
-    use std::collections::HashMap;
     use std::sync::Mutex;
 
     const EXPECTED_DELTA: Duration = Duration::from_millis((1.0f64 / 60.0f64) as u64);
@@ -120,6 +137,7 @@ mod tests {
             Arc::new(move |_| box ConnectionAcceptorMock::new() as Box<dyn ConnectionAcceptor>),
             main_thread_spawn_fn(),
             EXPECTED_DELTA,
+            ShutdownSignal::new(),
         );
     }
 
@@ -134,6 +152,7 @@ mod tests {
             }),
             main_thread_spawn_fn(),
             EXPECTED_DELTA,
+            ShutdownSignal::new(),
         );
         controller.run_connection_acceptor();
     }
@@ -148,6 +167,7 @@ mod tests {
             Arc::new(move |_| panic!("No connection acceptor is expected to be created")),
             main_thread_spawn_fn(),
             EXPECTED_DELTA,
+            ShutdownSignal::new(),
         );
         controller.step_simulation();
     }
@@ -170,6 +190,7 @@ mod tests {
             Arc::new(move |_| panic!("No connection acceptor is expected to be created")),
             main_thread_spawn_fn(),
             EXPECTED_DELTA,
+            ShutdownSignal::new(),
         );
         controller.step_simulation();
     }
@@ -190,12 +211,13 @@ mod tests {
             }),
             main_thread_spawn_fn(),
             EXPECTED_DELTA,
+            ShutdownSignal::new(),
         );
         controller.run_connection_acceptor();
 
         let current_snapshot_fn = current_snapshot_fn.lock().unwrap();
         let actual_snapshot = (current_snapshot_fn.as_ref().unwrap())();
-        assert_eq!(HashMap::new(), actual_snapshot);
+        assert_eq!(NumberedSnapshot::default(), actual_snapshot);
     }
 
     #[test]
@@ -205,8 +227,11 @@ mod tests {
         let mut simulation = SimulationMock::new();
         simulation.expect_step();
 
-        let expected_snapshot = hashmap! {
-           0 => object_description()
+        let expected_snapshot = NumberedSnapshot {
+            step: 1,
+            objects: hashmap! {
+                0 => object_description()
+            },
         };
 
         simulation.expect_objects().returns(vec![Object {
@@ -227,6 +252,7 @@ mod tests {
             }),
             main_thread_spawn_fn(),
             EXPECTED_DELTA,
+            ShutdownSignal::new(),
         );
         controller.run_connection_acceptor();
         controller.step_simulation();
@@ -236,6 +262,25 @@ mod tests {
         assert_eq!(expected_snapshot, actual_snapshot);
     }
 
+    #[test]
+    fn run_returns_when_shutdown_is_requested() {
+        let shutdown_signal = ShutdownSignal::new();
+        shutdown_signal.request_shutdown();
+
+        let mut controller = ControllerImpl::new(
+            box SimulationMock::new(),
+            Arc::new(move |_| {
+                let mut connection_acceptor = box ConnectionAcceptorMock::new();
+                connection_acceptor.expect_run();
+                connection_acceptor as Box<dyn ConnectionAcceptor>
+            }),
+            main_thread_spawn_fn(),
+            EXPECTED_DELTA,
+            shutdown_signal,
+        );
+        controller.run();
+    }
+
     fn main_thread_spawn_fn<'a>() -> Box<ThreadSpawnFn<'a>> {
         box move |function| function()
     }
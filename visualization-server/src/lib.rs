@@ -24,5 +24,6 @@ mod constant;
 mod controller;
 mod presenter;
 mod server;
+mod shutdown;
 
-pub use self::server::start_server;
+pub use self::server::{start_server, start_server_with_recording, ServerHandle};
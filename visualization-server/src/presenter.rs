@@ -1,6 +1,7 @@
 use crate::controller::{Presenter, Snapshot};
 use myelin_engine::prelude::*;
 use myelin_object_data::ObjectDescription;
+use myelin_visualization_core::simulation_stats::SimulationStats;
 use myelin_visualization_core::view_model_delta::{
     ObjectDelta, ObjectDescriptionDelta, ViewModelDelta,
 };
@@ -38,6 +39,21 @@ impl Presenter for DeltaPresenter {
 
         deltas
     }
+
+    fn calculate_simulation_stats(&self, step: u64, snapshot: &Snapshot) -> SimulationStats {
+        let mut counts_by_kind = HashMap::new();
+
+        for object in snapshot.values() {
+            *counts_by_kind
+                .entry(object.associated_data.kind)
+                .or_insert(0) += 1;
+        }
+
+        SimulationStats {
+            step,
+            counts_by_kind,
+        }
+    }
 }
 
 fn map_to_updated_or_created(
@@ -103,6 +119,10 @@ mod tests {
     use myelin_object_data::{AdditionalObjectDescription, Kind};
 
     fn object_description() -> ObjectDescription {
+        object_description_of_kind(Kind::Plant)
+    }
+
+    fn object_description_of_kind(kind: Kind) -> ObjectDescription {
         ObjectBuilder::default()
             .shape(
                 PolygonBuilder::default()
@@ -115,7 +135,7 @@ mod tests {
             )
             .associated_data(AdditionalObjectDescription {
                 name: None,
-                kind: Kind::Plant,
+                kind,
                 height: 1.0,
             })
             .mobility(Mobility::Immovable)
@@ -199,6 +219,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn calculate_deltas_only_contains_rotation_of_rotated_object() {
+        let mut object = object_description();
+
+        let mut first_snapshot = Snapshot::new();
+        first_snapshot.insert(42, object.clone());
+
+        object.rotation = Radians::try_new(1.0).unwrap();
+
+        let mut second_snapshot = Snapshot::new();
+        second_snapshot.insert(42, object.clone());
+
+        let delta_presenter = DeltaPresenter::default();
+        let delta = delta_presenter.calculate_deltas(&first_snapshot, &second_snapshot);
+
+        let expected_delta = ObjectDescriptionDelta {
+            rotation: Some(object.rotation),
+            ..ObjectDescriptionDelta::default()
+        };
+
+        assert_eq!(
+            hashmap! {
+                42 => ObjectDelta::Updated(expected_delta),
+            },
+            delta
+        );
+    }
+
     #[test]
     fn calculate_deltas_handles_added_object() {
         let object = object_description();
@@ -231,4 +279,39 @@ mod tests {
             delta
         );
     }
+
+    #[test]
+    fn calculate_simulation_stats_counts_objects_by_kind() {
+        let snapshot = hashmap! {
+            1 => object_description_of_kind(Kind::Organism),
+            2 => object_description_of_kind(Kind::Plant),
+            3 => object_description_of_kind(Kind::Organism),
+            4 => object_description_of_kind(Kind::Water),
+            5 => object_description_of_kind(Kind::Plant),
+            6 => object_description_of_kind(Kind::Organism),
+        };
+
+        let delta_presenter = DeltaPresenter::default();
+        let simulation_stats = delta_presenter.calculate_simulation_stats(17, &snapshot);
+
+        assert_eq!(
+            SimulationStats {
+                step: 17,
+                counts_by_kind: hashmap! {
+                    Kind::Organism => 3,
+                    Kind::Plant => 2,
+                    Kind::Water => 1,
+                },
+            },
+            simulation_stats
+        );
+    }
+
+    #[test]
+    fn calculate_simulation_stats_handles_empty_snapshot() {
+        let delta_presenter = DeltaPresenter::default();
+        let simulation_stats = delta_presenter.calculate_simulation_stats(0, &Snapshot::new());
+
+        assert_eq!(SimulationStats::default(), simulation_stats);
+    }
 }
@@ -1,14 +1,16 @@
 use crate::client::ClientHandler;
-use crate::connection::{Connection, WebsocketClient};
+use crate::connection::{Connection, DeltaRecorder, WebsocketClient};
 use crate::connection_acceptor::{
     Client, ClientFactoryFn, ThreadSpawnFn, WebsocketConnectionAcceptor,
 };
 use crate::constant::*;
 use crate::controller::{
-    ConnectionAcceptor, ConnectionAcceptorFactoryFn, Controller, ControllerImpl, Presenter,
+    ConnectionAcceptor, ConnectionAcceptorFactoryFn, Controller, ControllerImpl, CurrentSnapshotFn,
+    Presenter,
 };
 use crate::fixed_interval_sleeper::{FixedIntervalSleeper, FixedIntervalSleeperImpl};
 use crate::presenter::DeltaPresenter;
+use crate::shutdown::{ShutdownSignal, WorkerThreads};
 use myelin_engine::{prelude::*, simulation::SimulationBuilder};
 use myelin_genetics::{
     genome::Genome,
@@ -32,38 +34,136 @@ use myelin_object_behavior::{
 use myelin_object_data::{AdditionalObjectDescription, Kind};
 use myelin_random::{Random, RandomImpl};
 use myelin_visualization_core::serialization::{BincodeSerializer, ViewModelSerializer};
+use myelin_visualization_core::view_model_delta::DeltaEncoding;
 use myelin_worldgen::{
     HardcodedGenerator, NameProvider, NameProviderBuilder, NameProviderFactory,
     ShuffledNameProviderFactory, WorldGenerator,
 };
-use std::fs::read_to_string;
+use std::fs::{read_to_string, File};
+use std::io::BufWriter;
 use std::net::SocketAddr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::Arc;
-use std::thread;
+use std::thread::{self, JoinHandle};
 use std::time::Duration;
 use uuid::Uuid;
 use wonderbox::Container;
 
-/// Starts the simulation and a websocket server, that broadcasts
+/// Starts the simulation and a websocket server in the background, that broadcasts
 /// `ViewModel`s on each step to all clients.
-pub fn start_server<A>(addr: A)
+pub fn start_server<A>(addr: A) -> ServerHandle
 where
     A: Into<SocketAddr> + Send,
 {
-    let container = create_composition_root(addr.into());
+    spawn_server(addr.into(), None, PathBuf::from(ORGANISM_NAMES_PATH))
+}
+
+/// Same as [`start_server`], but additionally records the deltas
+/// that a freshly connected client would receive to the file at `recording_path`.
+/// The recording can be replayed by the visualization client.
+pub fn start_server_with_recording<A>(addr: A, recording_path: PathBuf) -> ServerHandle
+where
+    A: Into<SocketAddr> + Send,
+{
+    spawn_server(
+        addr.into(),
+        Some(recording_path),
+        PathBuf::from(ORGANISM_NAMES_PATH),
+    )
+}
+
+/// A handle to a server started by [`start_server`] or [`start_server_with_recording`].
+#[derive(Debug)]
+pub struct ServerHandle {
+    shutdown_signal: ShutdownSignal,
+    server_thread: JoinHandle<()>,
+}
+
+impl ServerHandle {
+    /// Stops accepting new connections, disconnects all clients
+    /// and blocks until all threads of the server have terminated.
+    pub fn shutdown(self) {
+        self.shutdown_signal.request_shutdown();
+        self.join();
+    }
+
+    /// Blocks until the server has stopped.
+    ///
+    /// # Panics
+    /// Panics if the server panicked.
+    pub fn join(self) {
+        self.server_thread
+            .join()
+            .expect("The server thread panicked");
+    }
+}
+
+/// The names given to organisms, relative to the working directory
+const ORGANISM_NAMES_PATH: &str = "./object-names/organisms.txt";
+
+fn spawn_server(
+    addr: SocketAddr,
+    recording_path: Option<PathBuf>,
+    organism_names_path: PathBuf,
+) -> ServerHandle {
+    let shutdown_signal = ShutdownSignal::new();
+    let server_shutdown_signal = shutdown_signal.clone();
+    let server_thread = thread::spawn(move || {
+        run_server(
+            addr,
+            recording_path,
+            organism_names_path,
+            server_shutdown_signal,
+        )
+    });
+
+    ServerHandle {
+        shutdown_signal,
+        server_thread,
+    }
+}
+
+fn run_server(
+    addr: SocketAddr,
+    recording_path: Option<PathBuf>,
+    organism_names_path: PathBuf,
+    shutdown_signal: ShutdownSignal,
+) {
+    let worker_threads = WorkerThreads::new();
+    let container = create_composition_root(
+        addr,
+        recording_path,
+        organism_names_path,
+        shutdown_signal,
+        worker_threads.clone(),
+    );
     let mut controller = container.resolve::<Box<dyn Controller>>();
     controller.run();
+    worker_threads.join_all();
 }
 
 struct ServerAddress(SocketAddr);
 
-fn create_composition_root(addr: SocketAddr) -> Container {
+struct RecordingPath(Option<PathBuf>);
+
+struct OrganismNamesPath(PathBuf);
+
+fn create_composition_root(
+    addr: SocketAddr,
+    recording_path: Option<PathBuf>,
+    organism_names_path: PathBuf,
+    shutdown_signal: ShutdownSignal,
+    worker_threads: WorkerThreads,
+) -> Container {
     let mut container = Container::new();
 
     container
         .register(move |_| ServerAddress(addr))
+        .register(move |_| RecordingPath(recording_path.clone()))
+        .register(move |_| OrganismNamesPath(organism_names_path.clone()))
+        .register(move |_| shutdown_signal.clone())
+        .register(move |_| worker_threads.clone())
         .extend(utility_container())
         .extend(server_container())
         .extend(client_container())
@@ -81,9 +181,10 @@ fn utility_container() -> Container {
     container
         .register(|_| box RandomImpl::new() as Box<dyn Random>)
         .register(|_| box FixedIntervalSleeperImpl::new() as Box<dyn FixedIntervalSleeper>)
-        .register(|_| {
-            box (|function| {
-                thread::spawn(function);
+        .register(|container| {
+            let worker_threads = container.resolve::<WorkerThreads>();
+            box (move |function| {
+                worker_threads.register(thread::spawn(function));
             }) as Box<ThreadSpawnFn>
         });
 
@@ -94,7 +195,10 @@ fn server_container() -> Container {
     let mut container = Container::new();
 
     container
-        .register(|_| box BincodeSerializer::new() as Box<dyn ViewModelSerializer>)
+        .register(|_| {
+            box BincodeSerializer::with_encoding(DeltaEncoding::Compact)
+                as Box<dyn ViewModelSerializer>
+        })
         .register(|container| {
             let expected_delta = Duration::from_secs_f64(SIMULATED_TIMESTEP_IN_SI_UNITS);
 
@@ -107,6 +211,7 @@ fn server_container() -> Container {
                 connection_acceptor_factory_fn,
                 thread_spawn_fn,
                 expected_delta,
+                container.resolve(),
             ) as Box<dyn Controller>
         });
 
@@ -138,6 +243,7 @@ fn client_container() -> Container {
                     view_model_serializer,
                     connection,
                     current_snapshot_fn,
+                    container.resolve(),
                 ) as Box<dyn Client>
             }) as Arc<ClientFactoryFn>
         })
@@ -148,11 +254,22 @@ fn client_container() -> Container {
                 let addr = container.resolve::<ServerAddress>().0;
                 let thread_spawn_fn = container.resolve::<Box<ThreadSpawnFn>>();
 
+                if let RecordingPath(Some(recording_path)) = container.resolve::<RecordingPath>() {
+                    let container = container.clone();
+                    let current_snapshot_fn = current_snapshot_fn.clone();
+                    thread_spawn_fn(box move || {
+                        create_recording_client(&container, &recording_path, current_snapshot_fn)
+                            .run()
+                    });
+                }
+
                 box WebsocketConnectionAcceptor::try_new(
                     addr,
                     client_factory_fn,
                     thread_spawn_fn,
                     current_snapshot_fn,
+                    container.resolve(),
+                    MAX_CLIENTS,
                 )
                 .expect("Failed to create websocket connection acceptor")
                     as Box<dyn ConnectionAcceptor>
@@ -162,6 +279,29 @@ fn client_container() -> Container {
     container
 }
 
+fn create_recording_client(
+    container: &Container,
+    recording_path: &Path,
+    current_snapshot_fn: Arc<CurrentSnapshotFn>,
+) -> Box<dyn Client> {
+    let recording_file = File::create(recording_path).expect("Failed to create recording file");
+
+    let connection = Connection {
+        id: Uuid::new_v4(),
+        socket: box DeltaRecorder::new(box BufWriter::new(recording_file)),
+    };
+
+    box ClientHandler::new(
+        Duration::from_secs_f64(SIMULATED_TIMESTEP_IN_SI_UNITS),
+        container.resolve(),
+        container.resolve(),
+        container.resolve(),
+        connection,
+        current_snapshot_fn,
+        container.resolve(),
+    )
+}
+
 fn genetics_container() -> Container {
     let mut container = Container::new();
 
@@ -295,7 +435,8 @@ fn object_behavior_container() -> Container {
 
 fn create_name_provider(container: &Container) -> Box<dyn NameProvider> {
     let mut name_provider_builder = NameProviderBuilder::new(container.resolve());
-    let organism_names = load_names_from_file(Path::new("./object-names/organisms.txt"));
+    let organism_names_path = container.resolve::<OrganismNamesPath>().0;
+    let organism_names = load_names_from_file(&organism_names_path);
     name_provider_builder.add_names(&organism_names, Kind::Organism);
     name_provider_builder.build()
 }
@@ -325,3 +466,60 @@ fn load_names_from_file(path: &Path) -> Vec<String> {
         .map(String::from)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constant::CLIENT_HANDSHAKE_TIMEOUT;
+    use std::net::{Ipv4Addr, SocketAddrV4, TcpListener, TcpStream};
+    use std::sync::mpsc::channel;
+    use std::time::Instant;
+    use websocket::ClientBuilder;
+
+    const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn shutdown_returns_while_a_client_has_not_completed_its_handshake() {
+        let address = free_local_address();
+        let server_handle = spawn_server(address, None, organism_names_path());
+        let _silent_client = connect_when_listening(address);
+
+        let (shut_down, server_shut_down) = channel();
+        thread::spawn(move || {
+            server_handle.shutdown();
+            shut_down.send(()).unwrap();
+        });
+
+        assert!(server_shut_down
+            .recv_timeout(CLIENT_HANDSHAKE_TIMEOUT * 2)
+            .is_ok());
+    }
+
+    fn organism_names_path() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("../object-names/organisms.txt")
+    }
+
+    fn free_local_address() -> SocketAddr {
+        const RANDOM_PORT: u16 = 0;
+        TcpListener::bind(SocketAddrV4::new(Ipv4Addr::LOCALHOST, RANDOM_PORT))
+            .and_then(|listener| listener.local_addr())
+            .unwrap()
+    }
+
+    fn connect_when_listening(address: SocketAddr) -> websocket::sync::Client<TcpStream> {
+        let start = Instant::now();
+
+        loop {
+            match ClientBuilder::new(&format!("ws://{}", address))
+                .unwrap()
+                .connect_insecure()
+            {
+                Ok(client) => return client,
+                Err(_) if start.elapsed() < STARTUP_TIMEOUT => {
+                    thread::sleep(Duration::from_millis(50))
+                }
+                Err(err) => panic!("Unable to connect to server: {}", err),
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Cooperative shutdown of the server's threads
+
+use log::error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// A flag shared between all threads of a server, which is
+/// regularly checked by them in order to stop their work.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ShutdownSignal(Arc<AtomicBool>);
+
+impl ShutdownSignal {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn request_shutdown(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn is_shutdown_requested(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// The threads that were spawned by the server and need
+/// to be joined before the server has fully stopped.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct WorkerThreads(Arc<Mutex<Vec<JoinHandle<()>>>>);
+
+impl WorkerThreads {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn register(&self, worker_thread: JoinHandle<()>) {
+        self.0.lock().unwrap().push(worker_thread);
+    }
+
+    /// Joins all registered threads, including the ones
+    /// that are registered while joining.
+    pub(crate) fn join_all(&self) {
+        loop {
+            let worker_thread = self.0.lock().unwrap().pop();
+
+            match worker_thread {
+                Some(worker_thread) => {
+                    if worker_thread.join().is_err() {
+                        error!("A worker thread panicked while shutting down");
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::thread;
+
+    #[test]
+    fn shutdown_is_not_requested_initially() {
+        assert!(!ShutdownSignal::new().is_shutdown_requested());
+    }
+
+    #[test]
+    fn requested_shutdown_is_visible_to_clones() {
+        let shutdown_signal = ShutdownSignal::new();
+        let cloned_shutdown_signal = shutdown_signal.clone();
+
+        shutdown_signal.request_shutdown();
+
+        assert!(cloned_shutdown_signal.is_shutdown_requested());
+    }
+
+    #[test]
+    fn join_all_joins_threads_registered_while_joining() {
+        let worker_threads = WorkerThreads::new();
+        let finished_threads = Arc::new(AtomicUsize::new(0));
+
+        let nested_worker_threads = worker_threads.clone();
+        let nested_finished_threads = finished_threads.clone();
+        worker_threads.register(thread::spawn(move || {
+            let finished_threads = nested_finished_threads.clone();
+            nested_worker_threads.register(thread::spawn(move || {
+                finished_threads.fetch_add(1, Ordering::SeqCst);
+            }));
+            nested_finished_threads.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        worker_threads.join_all();
+
+        assert_eq!(2, finished_threads.load(Ordering::SeqCst));
+    }
+}
@@ -181,6 +181,7 @@ impl<'a> HardcodedGenerator<'a> {
             )
             .location(500.0, 500.0)
             .mobility(Mobility::Immovable)
+            .passable(is_passable(Kind::Water))
             .associated_data(object_data)
             .build()
             .expect("Failed to build water");
@@ -260,6 +261,7 @@ impl<'a> HardcodedGenerator<'a> {
             )
             .location(location.0, location.1)
             .mobility(Mobility::Immovable)
+            .passable(is_passable(Kind::Terrain))
             .associated_data(object_data)
             .build()
             .expect("Failed to build terrain")
@@ -284,7 +286,7 @@ impl<'a> HardcodedGenerator<'a> {
             )
             .location(x, y)
             .mobility(Mobility::Immovable)
-            .passable(true)
+            .passable(is_passable(Kind::Plant))
             .associated_data(object_data)
             .build()
             .expect("Failed to build plant")
@@ -316,6 +318,12 @@ impl<'a> HardcodedGenerator<'a> {
     }
 }
 
+/// Bodies can only be passable as a whole, so objects are made passable
+/// if organisms, the only moving objects, don't collide with them.
+fn is_passable(kind: Kind) -> bool {
+    kind.is_passable_by(Kind::Organism)
+}
+
 impl<'a> WorldGenerator<'a> for HardcodedGenerator<'a> {
     fn generate(&mut self) -> Box<dyn Simulation<AdditionalObjectDescription> + 'a> {
         let mut simulation = (self.simulation_factory.0)();